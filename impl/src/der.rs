@@ -0,0 +1,303 @@
+//! Parser and encoder for the `der!` macro
+//!
+//! The input is a value notation close to ASN.1:
+//!
+//! ```text
+//! value := SEQUENCE { value, ... } | SET { value, ... }
+//!        | [ (APPLICATION|PRIVATE)? n ] (EXPLICIT|IMPLICIT)? value
+//!        | OID 1.2.3 | NULL | BOOLEAN (true|false) | INTEGER -?n
+//!        | OCTET STRING ("hex" | b"bytes") | BIT STRING "hex"
+//!        | (UTF8String|PrintableString|IA5String|NumericString|VisibleString) "string"
+//! ```
+
+use crate::{encode_base128, oid_to_bytes};
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use std::iter::Peekable;
+use syn::{Error, Lit};
+
+type Tokens = Peekable<proc_macro2::token_stream::IntoIter>;
+
+const CLASS_UNIVERSAL: u8 = 0b00 << 6;
+const CLASS_APPLICATION: u8 = 0b01 << 6;
+const CLASS_CONTEXT_SPECIFIC: u8 = 0b10 << 6;
+const CLASS_PRIVATE: u8 = 0b11 << 6;
+const CONSTRUCTED: u8 = 0x20;
+
+pub(crate) fn encode(input: TokenStream) -> Result<Vec<u8>, Error> {
+    let mut tokens = input.into_iter().peekable();
+    let v = parse_value(&mut tokens, Span::call_site())?;
+    match tokens.next() {
+        Some(t) => Err(Error::new(t.span(), "unexpected token after value")),
+        None => Ok(v),
+    }
+}
+
+fn parse_value(tokens: &mut Tokens, span: Span) -> Result<Vec<u8>, Error> {
+    let token = match tokens.next() {
+        Some(t) => t,
+        None => return Err(Error::new(span, "expected value")),
+    };
+    match &token {
+        TokenTree::Group(g) if g.delimiter() == Delimiter::Bracket => {
+            let (class, tag) = parse_tag(g.stream(), g.span())?;
+            let implicit = match tokens.peek() {
+                Some(TokenTree::Ident(i)) if i == "IMPLICIT" => true,
+                Some(TokenTree::Ident(i)) if i == "EXPLICIT" => false,
+                _ => {
+                    return Ok(encode_tlv(
+                        class | CONSTRUCTED,
+                        tag,
+                        &parse_value(tokens, g.span())?,
+                    ))
+                }
+            };
+            let mode = tokens.next().unwrap();
+            let inner = parse_value(tokens, mode.span())?;
+            if implicit {
+                Ok(replace_tag(class, tag, &inner))
+            } else {
+                Ok(encode_tlv(class | CONSTRUCTED, tag, &inner))
+            }
+        }
+        TokenTree::Ident(ident) => {
+            let span = ident.span();
+            match ident.to_string().as_ref() {
+                "SEQUENCE" => {
+                    let items = parse_items(tokens, span)?;
+                    Ok(encode_tlv(CONSTRUCTED, 16, &items.concat()))
+                }
+                "SET" => {
+                    // DER: elements are sorted by their encoding
+                    let mut items = parse_items(tokens, span)?;
+                    items.sort();
+                    Ok(encode_tlv(CONSTRUCTED, 17, &items.concat()))
+                }
+                "NULL" => Ok(encode_tlv(CLASS_UNIVERSAL, 5, &[])),
+                "BOOLEAN" => match tokens.next() {
+                    Some(TokenTree::Ident(b)) if b == "true" => {
+                        Ok(encode_tlv(CLASS_UNIVERSAL, 1, &[0xff]))
+                    }
+                    Some(TokenTree::Ident(b)) if b == "false" => {
+                        Ok(encode_tlv(CLASS_UNIVERSAL, 1, &[0x00]))
+                    }
+                    t => Err(error_at(t.as_ref(), span, "expected `true` or `false`")),
+                },
+                "INTEGER" => {
+                    let content = parse_integer(tokens, span)?;
+                    Ok(encode_tlv(CLASS_UNIVERSAL, 2, &content))
+                }
+                "OCTET" => {
+                    let span = expect_ident(tokens, span, "STRING")?;
+                    let content = match parse_lit(tokens, span)? {
+                        (Lit::Str(s), span) => parse_hex(&s.value(), span)?,
+                        (Lit::ByteStr(s), _) => s.value(),
+                        (_, span) => {
+                            return Err(Error::new(span, "expected hex string or byte string"))
+                        }
+                    };
+                    Ok(encode_tlv(CLASS_UNIVERSAL, 4, &content))
+                }
+                "BIT" => {
+                    let span = expect_ident(tokens, span, "STRING")?;
+                    // value is a whole number of octets: 0 unused bits
+                    let mut content = vec![0];
+                    match parse_lit(tokens, span)? {
+                        (Lit::Str(s), span) => content.extend(parse_hex(&s.value(), span)?),
+                        (_, span) => return Err(Error::new(span, "expected hex string")),
+                    }
+                    Ok(encode_tlv(CLASS_UNIVERSAL, 3, &content))
+                }
+                "OID" => {
+                    // components are not a single token (`1.2.3` is lexed as `1.2`, `.`, `3`),
+                    // so collect everything up to the next comma
+                    let mut s = String::new();
+                    while let Some(t) = tokens.peek() {
+                        if matches!(t, TokenTree::Punct(p) if p.as_char() == ',') {
+                            break;
+                        }
+                        s.push_str(&tokens.next().unwrap().to_string());
+                    }
+                    let content = oid_to_bytes(&s, false)
+                        .map_err(|msg| Error::new(span, format!("Invalid OID({})", msg)))?;
+                    Ok(encode_tlv(CLASS_UNIVERSAL, 6, &content))
+                }
+                "UTF8String" => encode_string(tokens, span, 12, |_| true),
+                "NumericString" => {
+                    encode_string(tokens, span, 18, |c| c.is_ascii_digit() || c == ' ')
+                }
+                "PrintableString" => encode_string(tokens, span, 19, |c| {
+                    c.is_ascii_alphanumeric() || " '()+,-./:=?".contains(c)
+                }),
+                "IA5String" => encode_string(tokens, span, 22, |c| c.is_ascii()),
+                "VisibleString" => {
+                    encode_string(tokens, span, 26, |c| c.is_ascii() && !c.is_ascii_control())
+                }
+                s => Err(Error::new(span, format!("unsupported type `{}`", s))),
+            }
+        }
+        t => Err(Error::new(t.span(), "expected value")),
+    }
+}
+
+/// Parse `{ value, value, ... }`, returning the encoding of each value
+fn parse_items(tokens: &mut Tokens, span: Span) -> Result<Vec<Vec<u8>>, Error> {
+    let group = match tokens.next() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => g,
+        t => return Err(error_at(t.as_ref(), span, "expected `{`")),
+    };
+    let mut inner = group.stream().into_iter().peekable();
+    let mut items = Vec::new();
+    while inner.peek().is_some() {
+        items.push(parse_value(&mut inner, group.span())?);
+        match inner.next() {
+            None => break,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => (),
+            Some(t) => return Err(Error::new(t.span(), "expected `,`")),
+        }
+    }
+    Ok(items)
+}
+
+/// Parse the content of `[ (APPLICATION|PRIVATE)? n ]`
+fn parse_tag(stream: TokenStream, span: Span) -> Result<(u8, u32), Error> {
+    let mut tokens = stream.into_iter().peekable();
+    let class = match tokens.peek() {
+        Some(TokenTree::Ident(i)) if i == "APPLICATION" => CLASS_APPLICATION,
+        Some(TokenTree::Ident(i)) if i == "PRIVATE" => CLASS_PRIVATE,
+        _ => CLASS_CONTEXT_SPECIFIC,
+    };
+    if class != CLASS_CONTEXT_SPECIFIC {
+        tokens.next();
+    }
+    let tag = match parse_lit(&mut tokens, span)? {
+        (Lit::Int(i), _) => i.base10_parse::<u32>()?,
+        (_, span) => return Err(Error::new(span, "expected tag number")),
+    };
+    if let Some(t) = tokens.next() {
+        return Err(Error::new(t.span(), "unexpected token in tag"));
+    }
+    Ok((class, tag))
+}
+
+/// Parse an optionally negative integer literal, and return its DER content
+fn parse_integer(tokens: &mut Tokens, span: Span) -> Result<Vec<u8>, Error> {
+    let negative = matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '-');
+    if negative {
+        tokens.next();
+    }
+    let value = match parse_lit(tokens, span)? {
+        (Lit::Int(i), _) => i.base10_parse::<i128>()?,
+        (_, span) => return Err(Error::new(span, "expected integer")),
+    };
+    let value = if negative { -value } else { value };
+    let bytes = value.to_be_bytes();
+    // skip leading bytes if they are only sign extension
+    let skip = bytes
+        .windows(2)
+        .take_while(|w| (w[0] == 0x00 && w[1] < 0x80) || (w[0] == 0xff && w[1] >= 0x80))
+        .count();
+    Ok(bytes[skip..].to_vec())
+}
+
+fn encode_string<F>(
+    tokens: &mut Tokens,
+    span: Span,
+    tag: u32,
+    is_valid: F,
+) -> Result<Vec<u8>, Error>
+where
+    F: Fn(char) -> bool,
+{
+    match parse_lit(tokens, span)? {
+        (Lit::Str(s), span) => {
+            let value = s.value();
+            if let Some(c) = value.chars().find(|&c| !is_valid(c)) {
+                return Err(Error::new(
+                    span,
+                    format!("invalid character {:?} in string", c),
+                ));
+            }
+            Ok(encode_tlv(CLASS_UNIVERSAL, tag, value.as_bytes()))
+        }
+        (_, span) => Err(Error::new(span, "expected string")),
+    }
+}
+
+fn parse_lit(tokens: &mut Tokens, span: Span) -> Result<(Lit, Span), Error> {
+    match tokens.next() {
+        Some(TokenTree::Literal(l)) => {
+            let span = l.span();
+            Ok((Lit::new(l), span))
+        }
+        t => Err(error_at(t.as_ref(), span, "expected literal")),
+    }
+}
+
+fn expect_ident(tokens: &mut Tokens, span: Span, expected: &str) -> Result<Span, Error> {
+    match tokens.next() {
+        Some(TokenTree::Ident(i)) if i == expected => Ok(i.span()),
+        t => Err(error_at(
+            t.as_ref(),
+            span,
+            &format!("expected `{}`", expected),
+        )),
+    }
+}
+
+fn parse_hex(s: &str, span: Span) -> Result<Vec<u8>, Error> {
+    let digits = s
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::new(span, "invalid hex string"))?;
+    if digits.len() % 2 != 0 {
+        return Err(Error::new(span, "hex string has an odd number of digits"));
+    }
+    Ok(digits.chunks(2).map(|d| (d[0] << 4) | d[1]).collect())
+}
+
+/// Build an error pointing to the token if present, or to `span` if input ended
+fn error_at(token: Option<&TokenTree>, span: Span, msg: &str) -> Error {
+    Error::new(token.map_or(span, |t| t.span()), msg)
+}
+
+fn encode_identifier(class_constructed: u8, tag: u32) -> Vec<u8> {
+    if tag < 31 {
+        vec![class_constructed | tag as u8]
+    } else {
+        let mut v = vec![class_constructed | 0x1f];
+        v.extend(encode_base128(u128::from(tag)));
+        v
+    }
+}
+
+fn encode_tlv(class_constructed: u8, tag: u32, content: &[u8]) -> Vec<u8> {
+    let mut v = encode_identifier(class_constructed, tag);
+    let len = content.len();
+    if len < 128 {
+        v.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let bytes: Vec<_> = bytes.iter().skip_while(|&&b| b == 0).collect();
+        v.push(0x80 | bytes.len() as u8);
+        v.extend(bytes);
+    }
+    v.extend_from_slice(content);
+    v
+}
+
+/// Replace the identifier of the encoded value, keeping the constructed bit
+fn replace_tag(class: u8, tag: u32, encoded: &[u8]) -> Vec<u8> {
+    let constructed = encoded[0] & CONSTRUCTED;
+    let mut idx = 1;
+    if encoded[0] & 0x1f == 0x1f {
+        while encoded[idx] & 0x80 != 0 {
+            idx += 1;
+        }
+        idx += 1;
+    }
+    let mut v = encode_identifier(class | constructed, tag);
+    v.extend_from_slice(&encoded[idx..]);
+    v
+}
@@ -2,6 +2,8 @@ extern crate proc_macro;
 use proc_macro::{Span, TokenStream};
 use syn::{parse_macro_input, Error, LitInt};
 
+mod der;
+
 #[proc_macro]
 pub fn encode_oid(input: TokenStream) -> TokenStream {
     let token_stream = input.to_string();
@@ -10,21 +12,30 @@ pub fn encode_oid(input: TokenStream) -> TokenStream {
     } else {
         (token_stream.as_ref(), false)
     };
+    let v = match oid_to_bytes(s, relative) {
+        Ok(v) => v,
+        Err(msg) => return create_error(msg),
+    };
+    bytes_to_array(&v)
+}
+
+/// Encode the OID content bytes, from its dotted string representation
+fn oid_to_bytes(s: &str, relative: bool) -> Result<Vec<u8>, &'static str> {
     let items: Result<Vec<_>, _> = s.split('.').map(|x| x.trim().parse::<u128>()).collect();
     let mut items: &[_] = match items.as_ref() {
         Ok(v) => v.as_ref(),
-        Err(_) => return create_error("Could not parse OID"),
+        Err(_) => return Err("Could not parse OID"),
     };
     let mut v = Vec::new();
     if !relative {
         if items.len() < 2 {
             if items.len() == 1 && items[0] == 0 {
-                return "[0]".parse().unwrap();
+                return Ok(vec![0]);
             }
-            return create_error("Need at least two components for non-relative oid");
+            return Err("Need at least two components for non-relative oid");
         }
         if items[0] > 2 || items[1] > 39 {
-            return create_error("First components are too big");
+            return Err("First components are too big");
         }
         let first_byte = (items[0] * 40 + items[1]) as u8;
         v.push(first_byte);
@@ -34,6 +45,11 @@ pub fn encode_oid(input: TokenStream) -> TokenStream {
         let enc = encode_base128(int);
         v.extend_from_slice(&enc);
     }
+    Ok(v)
+}
+
+/// Build a byte array literal
+fn bytes_to_array(v: &[u8]) -> TokenStream {
     // "fn answer() -> u32 { 42 }".parse().unwrap()
     let mut s = String::with_capacity(2 + 6 * v.len());
     s.push('[');
@@ -70,6 +86,14 @@ fn encode_base128(int: u128) -> Vec<u8> {
     }
 }
 
+#[proc_macro]
+pub fn encode_der(input: TokenStream) -> TokenStream {
+    match der::encode(input.into()) {
+        Ok(v) => bytes_to_array(&v),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 #[proc_macro]
 pub fn encode_int(input: TokenStream) -> TokenStream {
     let lit = parse_macro_input!(input as LitInt);
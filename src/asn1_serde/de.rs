@@ -0,0 +1,601 @@
+use super::types::DER_NEWTYPE;
+use crate::ber::MAX_RECURSION;
+use crate::{
+    Any, BmpString, CheckDerConstraints, Class, Enumerated, Error, FromBer, FromDer, GeneralString,
+    GeneralizedTime, GraphicString, Ia5String, Integer, Null, NumericString, OctetString, Oid,
+    ParseResult, PrintableString, Result, Tag, TeletexString, UniversalString, UtcTime, Utf8String,
+    VideotexString, VisibleString,
+};
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use core::convert::TryFrom;
+use core::fmt::Display;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Unexpected, Visitor};
+use serde::Deserialize;
+
+/// Deserialize a value from BER, using its `Deserialize` implementation
+///
+/// See [`Asn1Serializer`](crate::Asn1Serializer) for the mapping of serde types to ASN.1 types.
+pub fn serde_from_ber<'a, T>(bytes: &'a [u8]) -> ParseResult<'a, T>
+where
+    T: Deserialize<'a>,
+{
+    let mut deserializer = Asn1Deserializer::from_ber(bytes);
+    let t = T::deserialize(&mut deserializer)?;
+    Ok((deserializer.input, t))
+}
+
+/// Deserialize a value from DER, using its `Deserialize` implementation
+///
+/// See [`Asn1Serializer`](crate::Asn1Serializer) for the mapping of serde types to ASN.1 types.
+///
+/// ```rust
+/// use asn1_rs::serde_from_der;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// let bytes = &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02];
+/// let (rem, p) = serde_from_der::<Point>(bytes).expect("parsing failed");
+/// assert!(rem.is_empty());
+/// assert_eq!(p, Point { x: 1, y: 2 });
+/// ```
+pub fn serde_from_der<'a, T>(bytes: &'a [u8]) -> ParseResult<'a, T>
+where
+    T: Deserialize<'a>,
+{
+    let mut deserializer = Asn1Deserializer::from_der(bytes);
+    let t = T::deserialize(&mut deserializer)?;
+    Ok((deserializer.input, t))
+}
+
+/// A serde `Deserializer`, parsing BER or DER
+///
+/// Objects are read one after the other from the input. When parsing DER, the DER constraints
+/// are checked for each object.
+///
+/// This deserializer is self-describing: `deserialize_any` maps each universal ASN.1 type to the
+/// closest serde type. Objects with another class are read as sequences if constructed, and as
+/// bytes otherwise.
+///
+/// The nesting of constructed objects is limited, to avoid stack overflows on malicious input
+/// (error [`Error::BerMaxDepth`]).
+#[derive(Debug)]
+pub struct Asn1Deserializer<'de> {
+    input: &'de [u8],
+    der: bool,
+    /// Number of nested constructed objects that can still be entered
+    depth: usize,
+}
+
+impl<'de> Asn1Deserializer<'de> {
+    /// Build a new deserializer, parsing BER objects
+    pub const fn from_ber(input: &'de [u8]) -> Self {
+        Asn1Deserializer {
+            input,
+            der: false,
+            depth: MAX_RECURSION,
+        }
+    }
+
+    /// Build a new deserializer, parsing DER objects
+    pub const fn from_der(input: &'de [u8]) -> Self {
+        Asn1Deserializer {
+            input,
+            der: true,
+            depth: MAX_RECURSION,
+        }
+    }
+
+    /// Return the input that has not been parsed yet
+    pub const fn remaining(&self) -> &'de [u8] {
+        self.input
+    }
+
+    /// Build a deserializer for the content of a constructed object
+    fn content(&self, any: &Any<'de>) -> Result<Self> {
+        if self.depth == 0 {
+            return Err(Error::BerMaxDepth);
+        }
+        Ok(Asn1Deserializer {
+            input: any.data,
+            der: self.der,
+            depth: self.depth - 1,
+        })
+    }
+
+    fn next_any(&mut self) -> Result<Any<'de>> {
+        let (rem, any) = if self.der {
+            Any::from_der(self.input)?
+        } else {
+            Any::from_ber(self.input)?
+        };
+        self.input = rem;
+        Ok(any)
+    }
+
+    fn convert<T>(&self, any: Any<'de>) -> Result<T>
+    where
+        T: TryFrom<Any<'de>, Error = Error> + CheckDerConstraints,
+    {
+        // convert first: DER constraints may assume that the content is valid
+        let t = T::try_from(any.clone())?;
+        if self.der {
+            T::check_constraints(&any)?;
+        }
+        Ok(t)
+    }
+
+    fn parse<T>(&mut self) -> Result<T>
+    where
+        T: TryFrom<Any<'de>, Error = Error> + CheckDerConstraints,
+    {
+        let any = self.next_any()?;
+        self.convert(any)
+    }
+
+    /// Parse a string object of any string type
+    fn parse_str(&mut self) -> Result<Cow<'de, str>> {
+        let any = self.next_any()?;
+        self.convert_str(any)
+    }
+
+    fn convert_str(&self, any: Any<'de>) -> Result<Cow<'de, str>> {
+        macro_rules! convert_string {
+            ($($ty:ident),*) => {
+                match any.tag() {
+                    $( Tag::$ty => Ok(self.convert::<$ty>(any)?.data), )*
+                    tag => Err(Error::unexpected_tag(Some(Tag::Utf8String), tag)),
+                }
+            };
+        }
+        convert_string!(
+            Utf8String,
+            PrintableString,
+            Ia5String,
+            NumericString,
+            VisibleString,
+            GraphicString,
+            GeneralString,
+            TeletexString,
+            VideotexString,
+            BmpString,
+            UniversalString
+        )
+    }
+
+    /// Check that the content of a constructed object was entirely read
+    fn end(&self) -> Result<()> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(de::Error::custom("trailing data in constructed object"))
+        }
+    }
+
+    /// Visit the content of a constructed object as a sequence
+    ///
+    /// If `len` is provided, exactly `len` elements are read (absent trailing elements are read
+    /// from empty input, which is valid only for `OPTIONAL` elements).
+    fn visit_content<V>(&self, any: &Any<'de>, len: Option<usize>, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let mut content = self.content(any)?;
+        let value = visitor.visit_seq(Elements {
+            de: &mut content,
+            remaining: len,
+        })?;
+        content.end()?;
+        Ok(value)
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::SerdeError(msg.to_string())
+    }
+}
+
+macro_rules! deserialize_primitive {
+    ($($method:ident => $visit:ident, $ty:ty;)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse::<$ty>()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Asn1Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        if !any.header.is_universal() {
+            return if any.header.is_constructed() {
+                self.visit_content(&any, None, visitor)
+            } else {
+                visitor.visit_borrowed_bytes(any.data)
+            };
+        }
+        match any.tag() {
+            Tag::Boolean => visitor.visit_bool(self.convert(any)?),
+            Tag::Integer => {
+                let i: Integer = self.convert(any)?;
+                if let Ok(v) = i.as_i64() {
+                    visitor.visit_i64(v)
+                } else if let Ok(v) = i.as_u64() {
+                    visitor.visit_u64(v)
+                } else if let Ok(v) = i.as_i128() {
+                    visitor.visit_i128(v)
+                } else {
+                    visitor.visit_u128(i.as_u128()?)
+                }
+            }
+            Tag::Enumerated => visitor.visit_i64(self.convert::<Enumerated>(any)?.0),
+            Tag::RealType => visitor.visit_f64(self.convert(any)?),
+            Tag::Null => {
+                self.convert::<Null>(any)?;
+                visitor.visit_unit()
+            }
+            Tag::OctetString => match self.convert::<OctetString>(any)?.into_cow() {
+                Cow::Borrowed(b) => visitor.visit_borrowed_bytes(b),
+                Cow::Owned(v) => visitor.visit_byte_buf(v),
+            },
+            Tag::Oid | Tag::RelativeOid => {
+                let oid: Oid = self.convert(any)?;
+                visitor.visit_string(oid.to_id_string())
+            }
+            Tag::UtcTime | Tag::GeneralizedTime => {
+                // check the value, but keep the original string
+                let data = any.data;
+                if any.tag() == Tag::UtcTime {
+                    self.convert::<UtcTime>(any)?;
+                } else {
+                    self.convert::<GeneralizedTime>(any)?;
+                }
+                visitor.visit_borrowed_str(core::str::from_utf8(data)?)
+            }
+            Tag::Sequence | Tag::Set => self.visit_content(&any, None, visitor),
+            _ => match self.convert_str(any.clone()) {
+                Ok(Cow::Borrowed(s)) => visitor.visit_borrowed_str(s),
+                Ok(Cow::Owned(s)) => visitor.visit_string(s),
+                // not a string: return raw content
+                Err(_) => visitor.visit_borrowed_bytes(any.data),
+            },
+        }
+    }
+
+    deserialize_primitive! {
+        deserialize_bool => visit_bool, bool;
+        deserialize_i8 => visit_i8, i8;
+        deserialize_i16 => visit_i16, i16;
+        deserialize_i32 => visit_i32, i32;
+        deserialize_i64 => visit_i64, i64;
+        deserialize_i128 => visit_i128, i128;
+        deserialize_u8 => visit_u8, u8;
+        deserialize_u16 => visit_u16, u16;
+        deserialize_u32 => visit_u32, u32;
+        deserialize_u64 => visit_u64, u64;
+        deserialize_u128 => visit_u128, u128;
+        deserialize_f32 => visit_f32, f32;
+        deserialize_f64 => visit_f64, f64;
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let s = self.parse_str()?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(de::Error::invalid_value(
+                Unexpected::Str(&s),
+                &"a character",
+            )),
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.parse_str()? {
+            Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+            Cow::Owned(s) => visitor.visit_string(s),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.parse::<OctetString>()?.into_cow() {
+            Cow::Borrowed(b) => visitor.visit_borrowed_bytes(b),
+            Cow::Owned(v) => visitor.visit_byte_buf(v),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // OPTIONAL: absent values are not encoded
+        if self.input.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.parse::<Null>()?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if name == DER_NEWTYPE {
+            // the value is an ASN.1 type: provide its complete encoding
+            let start = self.input;
+            self.next_any()?;
+            let encoding = &start[..start.len() - self.input.len()];
+            return visitor.visit_borrowed_bytes(encoding);
+        }
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Sequence)?;
+        self.visit_content(&any, None, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Sequence)?;
+        self.visit_content(&any, Some(len), visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.tag().assert_eq(Tag::Sequence)?;
+        let mut content = self.content(&any)?;
+        let value = visitor.visit_map(Entries {
+            de: &mut content,
+            entry: None,
+        })?;
+        content.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let any = self.next_any()?;
+        any.header.assert_class(Class::ContextSpecific)?;
+        visitor.visit_enum(Variant { de: self, any })
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.next_any()?;
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Access to the elements of a `SEQUENCE`
+struct Elements<'a, 'de> {
+    de: &'a mut Asn1Deserializer<'de>,
+    /// Number of elements to read, if known
+    remaining: Option<usize>,
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for Elements<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.remaining {
+            Some(0) => return Ok(None),
+            Some(ref mut n) => *n -= 1,
+            None if self.de.input.is_empty() => return Ok(None),
+            None => (),
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining
+    }
+}
+
+/// Access to the entries of a map (`SEQUENCE OF SEQUENCE { key, value }`)
+struct Entries<'a, 'de> {
+    de: &'a mut Asn1Deserializer<'de>,
+    /// The entry being read
+    entry: Option<Asn1Deserializer<'de>>,
+}
+
+impl<'a, 'de> de::MapAccess<'de> for Entries<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.de.input.is_empty() {
+            return Ok(None);
+        }
+        let any = self.de.next_any()?;
+        any.tag().assert_eq(Tag::Sequence)?;
+        let entry = self.entry.insert(self.de.content(&any)?);
+        seed.deserialize(entry).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let mut entry = self
+            .entry
+            .take()
+            .ok_or_else(|| <Error as de::Error>::custom("map value without key"))?;
+        let value = seed.deserialize(&mut entry)?;
+        entry.end()?;
+        Ok(value)
+    }
+}
+
+/// Access to an enum variant (`CHOICE`), tagged with the variant index
+struct Variant<'a, 'de> {
+    de: &'a mut Asn1Deserializer<'de>,
+    any: Any<'de>,
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for Variant<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let index = IntoDeserializer::<Error>::into_deserializer(self.any.tag().0);
+        let value = seed.deserialize(index)?;
+        Ok((value, self))
+    }
+}
+
+impl<'a, 'de> de::VariantAccess<'de> for Variant<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        // [index] IMPLICIT NULL
+        self.any.header.assert_primitive()?;
+        if !self.any.data.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        // [index] EXPLICIT
+        self.any.header.assert_constructed()?;
+        let mut content = self.de.content(&self.any)?;
+        let value = seed.deserialize(&mut content)?;
+        content.end()?;
+        Ok(value)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // [index] IMPLICIT SEQUENCE
+        self.any.header.assert_constructed()?;
+        self.de.visit_content(&self.any, Some(len), visitor)
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.tuple_variant(fields.len(), visitor)
+    }
+}
@@ -0,0 +1,18 @@
+//! serde integration: encode and decode Rust types using their `Serialize` and `Deserialize`
+//! implementations
+//!
+//! [`Asn1Serializer`] produces DER, and [`Asn1Deserializer`] parses BER or DER. Both use the same
+//! mapping of the serde data model to ASN.1 types, described in the documentation of
+//! [`Asn1Serializer`].
+//!
+//! The ASN.1 types of this crate also implement `Serialize` and `Deserialize`, using textual
+//! forms in human-readable formats (for ex. dotted notation for [`Oid`](crate::Oid), ISO 8601
+//! for [`GeneralizedTime`](crate::GeneralizedTime), hex for [`OctetString`](crate::OctetString))
+//! and their DER encoding otherwise.
+
+mod de;
+mod ser;
+mod types;
+
+pub use de::*;
+pub use ser::*;
@@ -0,0 +1,462 @@
+use super::types::DER_NEWTYPE;
+use crate::{
+    Any, Class, Error, FromDer, Header, Length, Null, OctetString, Real, SerializeError,
+    SerializeResult, Tag, ToDer,
+};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use serde::ser::{self, Serialize};
+
+/// Serialize a value to DER, using its `Serialize` implementation
+///
+/// See [`Asn1Serializer`] for the mapping of serde types to ASN.1 types.
+///
+/// ```rust
+/// use asn1_rs::serde_to_der;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// let v = serde_to_der(&Point { x: 1, y: 2 }).expect("serialization failed");
+/// assert_eq!(&v, &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]);
+/// ```
+pub fn serde_to_der<T>(value: &T) -> SerializeResult<Vec<u8>>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Asn1Serializer)
+}
+
+/// A serde `Serializer`, producing DER
+///
+/// Each value is serialized to its complete encoding (header and content). serde types are
+/// mapped to ASN.1 types as follows:
+///
+/// | serde | ASN.1 |
+/// |---|---|
+/// | `bool` | `BOOLEAN` |
+/// | integers | `INTEGER` |
+/// | `f32`, `f64` | `REAL` |
+/// | `char`, string | `UTF8String` |
+/// | bytes | `OCTET STRING` |
+/// | `Option` | `OPTIONAL`: `None` is not encoded |
+/// | unit, unit struct | `NULL` |
+/// | newtype struct | the inner value |
+/// | struct, tuple, tuple struct | `SEQUENCE` (field names are not encoded) |
+/// | sequence | `SEQUENCE OF` |
+/// | map | `SEQUENCE OF SEQUENCE { key, value }` |
+/// | enum | `CHOICE`, using context-specific tags |
+/// | [`Any`], [`Oid`](crate::Oid), [`Integer`](crate::Integer), [`BitString`](crate::BitString), [`GeneralizedTime`](crate::GeneralizedTime), [`UtcTime`](crate::UtcTime) | the type itself |
+///
+/// Note that serde serializes `Vec<u8>` and `&[u8]` as sequences: use the `serde_bytes` crate
+/// to encode them as `OCTET STRING`.
+///
+/// Enum variants are tagged using their index: unit variants are encoded as
+/// `[index] IMPLICIT NULL`, newtype variants as `[index] EXPLICIT` (containing the inner value),
+/// and tuple or struct variants as `[index] IMPLICIT SEQUENCE`.
+///
+/// Since absent `OPTIONAL` values are not encoded, they can be recognized by
+/// [`Asn1Deserializer`](crate::Asn1Deserializer) only at the end of a `SEQUENCE`. To avoid
+/// ambiguous encodings, serialization fails if an absent value is followed by another element,
+/// or is an element of a `SEQUENCE OF` or a map key, or if the value of `Some` is absent
+/// (for ex. `Some(None)`).
+#[derive(Clone, Copy, Debug)]
+pub struct Asn1Serializer;
+
+/// Return the encoding of a constructed object, with the provided content
+fn encode_constructed(class: Class, tag: Tag, content: &[u8]) -> SerializeResult<Vec<u8>> {
+    let header = Header::new(class, true, tag, Length::Definite(content.len()));
+    let mut v = header.to_der_vec()?;
+    v.extend_from_slice(content);
+    Ok(v)
+}
+
+impl ser::Error for SerializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerializeError::ASN1Error(Error::SerdeError(msg.to_string()))
+    }
+}
+
+impl ser::Serializer for Asn1Serializer {
+    type Ok = Vec<u8>;
+    type Error = SerializeError;
+
+    type SerializeSeq = SerializeConstructed;
+    type SerializeTuple = SerializeConstructed;
+    type SerializeTupleStruct = SerializeConstructed;
+    type SerializeTupleVariant = SerializeConstructed;
+    type SerializeMap = SerializeConstructed;
+    type SerializeStruct = SerializeConstructed;
+    type SerializeStructVariant = SerializeConstructed;
+
+    fn serialize_bool(self, v: bool) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_i8(self, v: i8) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_i16(self, v: i16) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_i32(self, v: i32) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_i64(self, v: i64) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_i128(self, v: i128) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_u8(self, v: u8) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_u16(self, v: u16) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_u32(self, v: u32) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_u64(self, v: u64) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_u128(self, v: u128) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_f32(self, v: f32) -> SerializeResult<Vec<u8>> {
+        Real::from(v).to_der_vec()
+    }
+
+    fn serialize_f64(self, v: f64) -> SerializeResult<Vec<u8>> {
+        Real::from(v).to_der_vec()
+    }
+
+    fn serialize_char(self, v: char) -> SerializeResult<Vec<u8>> {
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
+    }
+
+    fn serialize_str(self, v: &str) -> SerializeResult<Vec<u8>> {
+        v.to_der_vec()
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> SerializeResult<Vec<u8>> {
+        OctetString::new(v).to_der_vec()
+    }
+
+    fn serialize_none(self) -> SerializeResult<Vec<u8>> {
+        // OPTIONAL: absent values are not encoded
+        Ok(Vec::new())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> SerializeResult<Vec<u8>>
+    where
+        T: Serialize + ?Sized,
+    {
+        let v = value.serialize(self)?;
+        if v.is_empty() {
+            return Err(ser::Error::custom("absent value in Some"));
+        }
+        Ok(v)
+    }
+
+    fn serialize_unit(self) -> SerializeResult<Vec<u8>> {
+        Null::new().to_der_vec()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> SerializeResult<Vec<u8>> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> SerializeResult<Vec<u8>> {
+        // [index] IMPLICIT NULL
+        let tag = Tag(variant_index);
+        Header::new(Class::ContextSpecific, false, tag, Length::Definite(0)).to_der_vec()
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> SerializeResult<Vec<u8>>
+    where
+        T: Serialize + ?Sized,
+    {
+        let v = value.serialize(self)?;
+        if name == DER_NEWTYPE {
+            // the value is the encoding of an ASN.1 type, serialized as OCTET STRING: unwrap it
+            let (_, any) = Any::from_der(&v).map_err(Error::from)?;
+            return Ok(any.data.to_vec());
+        }
+        Ok(v)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> SerializeResult<Vec<u8>>
+    where
+        T: Serialize + ?Sized,
+    {
+        // [index] EXPLICIT
+        let content = value.serialize(self)?;
+        encode_constructed(Class::ContextSpecific, Tag(variant_index), &content)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> SerializeResult<SerializeConstructed> {
+        let mut s = SerializeConstructed::new(Class::Universal, Tag::Sequence);
+        s.sequence_of = true;
+        Ok(s)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> SerializeResult<SerializeConstructed> {
+        Ok(SerializeConstructed::new(Class::Universal, Tag::Sequence))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerializeResult<SerializeConstructed> {
+        Ok(SerializeConstructed::new(Class::Universal, Tag::Sequence))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> SerializeResult<SerializeConstructed> {
+        // [index] IMPLICIT SEQUENCE
+        let tag = Tag(variant_index);
+        Ok(SerializeConstructed::new(Class::ContextSpecific, tag))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> SerializeResult<SerializeConstructed> {
+        Ok(SerializeConstructed::new(Class::Universal, Tag::Sequence))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerializeResult<SerializeConstructed> {
+        Ok(SerializeConstructed::new(Class::Universal, Tag::Sequence))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> SerializeResult<SerializeConstructed> {
+        // [index] IMPLICIT SEQUENCE
+        let tag = Tag(variant_index);
+        Ok(SerializeConstructed::new(Class::ContextSpecific, tag))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Serializer for constructed objects, returned by [`Asn1Serializer`]
+///
+/// The encodings of all elements are concatenated, and wrapped in a header when done.
+#[derive(Debug)]
+pub struct SerializeConstructed {
+    class: Class,
+    tag: Tag,
+    content: Vec<u8>,
+    /// Encoding of the last map key, waiting for its value
+    key: Vec<u8>,
+    /// `SEQUENCE OF`: elements cannot be absent
+    sequence_of: bool,
+    /// An element was absent, so all following elements must be absent
+    absent: bool,
+}
+
+impl SerializeConstructed {
+    fn new(class: Class, tag: Tag) -> Self {
+        SerializeConstructed {
+            class,
+            tag,
+            content: Vec::new(),
+            key: Vec::new(),
+            sequence_of: false,
+            absent: false,
+        }
+    }
+
+    fn push<T>(&mut self, value: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let v = value.serialize(Asn1Serializer)?;
+        if v.is_empty() {
+            if self.sequence_of {
+                return Err(ser::Error::custom("absent value in SEQUENCE OF"));
+            }
+            self.absent = true;
+        } else if self.absent {
+            return Err(ser::Error::custom(
+                "absent OPTIONAL value followed by another element",
+            ));
+        }
+        self.content.extend_from_slice(&v);
+        Ok(())
+    }
+
+    fn finish(self) -> SerializeResult<Vec<u8>> {
+        encode_constructed(self.class, self.tag, &self.content)
+    }
+}
+
+impl ser::SerializeSeq for SerializeConstructed {
+    type Ok = Vec<u8>;
+    type Error = SerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult<Vec<u8>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeConstructed {
+    type Ok = Vec<u8>;
+    type Error = SerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult<Vec<u8>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeConstructed {
+    type Ok = Vec<u8>;
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult<Vec<u8>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeConstructed {
+    type Ok = Vec<u8>;
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult<Vec<u8>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for SerializeConstructed {
+    type Ok = Vec<u8>;
+    type Error = SerializeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = key.serialize(Asn1Serializer)?;
+        if self.key.is_empty() {
+            return Err(ser::Error::custom("absent map key"));
+        }
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        // each entry is a SEQUENCE { key, value }
+        let mut entry = core::mem::take(&mut self.key);
+        entry.extend_from_slice(&value.serialize(Asn1Serializer)?);
+        let v = encode_constructed(Class::Universal, Tag::Sequence, &entry)?;
+        self.content.extend_from_slice(&v);
+        Ok(())
+    }
+
+    fn end(self) -> SerializeResult<Vec<u8>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeConstructed {
+    type Ok = Vec<u8>;
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult<Vec<u8>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeConstructed {
+    type Ok = Vec<u8>;
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> SerializeResult<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> SerializeResult<Vec<u8>> {
+        self.finish()
+    }
+}
@@ -0,0 +1,527 @@
+//! serde implementations for ASN.1 types
+//!
+//! Human-readable formats use textual forms: dotted notation for OIDs, numbers for integers
+//! (or hex strings if they do not fit into 64 bits), ISO 8601 for times and hex for binary
+//! data. Other formats use the DER encoding of the value, wrapped in a newtype struct named
+//! [`DER_NEWTYPE`] so that [`Asn1Serializer`](crate::Asn1Serializer) and
+//! [`Asn1Deserializer`](crate::Asn1Deserializer) can copy it as is.
+
+use crate::{
+    ASN1DateTime, ASN1TimeZone, Any, Asn1Value, BitString, Error, FromDer, GeneralizedTime,
+    Integer, OctetString, Oid, ParseResult, Result, Tag, ToDer, UtcTime,
+};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+/// Name of the newtype struct wrapping DER encodings in non human-readable formats
+pub(crate) const DER_NEWTYPE: &str = "$asn1_rs::Der";
+
+/// A DER encoding, serialized as bytes
+struct DerBytes<'a>(&'a [u8]);
+
+impl Serialize for DerBytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+fn serialize_der<T, S>(value: &T, serializer: S) -> core::result::Result<S::Ok, S::Error>
+where
+    T: ToDer,
+    S: Serializer,
+{
+    let v = value.to_der_vec().map_err(ser::Error::custom)?;
+    serializer.serialize_newtype_struct(DER_NEWTYPE, &DerBytes(&v))
+}
+
+/// Visitor for a DER encoding, building an owned value with `parse`
+struct DerVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&[u8]) -> Result<T>,
+}
+
+impl<'de, T> Visitor<'de> for DerVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DER encoding of {}", self.expecting)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> core::result::Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(self)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> core::result::Result<T, E> {
+        (self.parse)(v).map_err(E::custom)
+    }
+}
+
+fn deserialize_der<'de, T, D>(
+    deserializer: D,
+    expecting: &'static str,
+    parse: fn(&[u8]) -> Result<T>,
+) -> core::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(DER_NEWTYPE, DerVisitor { expecting, parse })
+}
+
+/// Visitor for a string, building a value with `parse`
+struct StrVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Result<T>,
+}
+
+impl<'de, T> Visitor<'de> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> core::result::Result<T, E> {
+        (self.parse)(v).map_err(E::custom)
+    }
+}
+
+fn deserialize_str<'de, T, D>(
+    deserializer: D,
+    expecting: &'static str,
+    parse: fn(&str) -> Result<T>,
+) -> core::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(StrVisitor { expecting, parse })
+}
+
+/// Return the value if all input was parsed
+fn complete<T>(result: ParseResult<T>) -> Result<T> {
+    let (rem, t) = result?;
+    if !rem.is_empty() {
+        return Err(Error::InvalidLength);
+    }
+    Ok(t)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Serialized as the dotted notation (for ex. `"1.2.840.113549"`, or `"rel. 1.2"` for a
+/// relative OID) in human-readable formats
+impl Serialize for Oid<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serialize_der(self, serializer)
+        }
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for Oid<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserialize_str(deserializer, "an OID in dotted notation", |s| {
+                let oid = match s.strip_prefix("rel. ") {
+                    Some(s) => s
+                        .split('.')
+                        .map(u64::from_str)
+                        .collect::<core::result::Result<Vec<_>, _>>()
+                        .ok()
+                        .and_then(|arcs| Oid::from_relative(&arcs).ok()),
+                    None => Oid::from_str(s).ok(),
+                };
+                oid.ok_or_else(|| Tag::Oid.invalid_value("invalid OID string"))
+            })
+        } else {
+            deserialize_der(deserializer, "an OID", |bytes| {
+                let oid = if bytes.first() == Some(&(Tag::RelativeOid.0 as u8)) {
+                    complete(Oid::from_der_relative(bytes))?
+                } else {
+                    complete(Oid::from_der(bytes))?
+                };
+                Ok(oid.to_owned())
+            })
+        }
+    }
+}
+
+/// Serialized as a number in human-readable formats, or as a hex string of the two's complement
+/// encoding (for ex. `"0x010000000000000000"`) if it does not fit into 64 bits
+impl Serialize for Integer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            if let Ok(v) = self.as_i64() {
+                serializer.serialize_i64(v)
+            } else if let Ok(v) = self.as_u64() {
+                serializer.serialize_u64(v)
+            } else {
+                serializer.collect_str(&format_args!("0x{}", to_hex(&self.data)))
+            }
+        } else {
+            serialize_der(self, serializer)
+        }
+    }
+}
+
+struct IntegerVisitor;
+
+impl<'de> Visitor<'de> for IntegerVisitor {
+    type Value = Integer<'static>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an integer, or a decimal or hex string")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> core::result::Result<Self::Value, E> {
+        Ok(Integer::from_i64(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> core::result::Result<Self::Value, E> {
+        Ok(Integer::from_u64(v))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> core::result::Result<Self::Value, E> {
+        Ok(Integer::from_i128(v))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> core::result::Result<Self::Value, E> {
+        Ok(Integer::from_u128(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> core::result::Result<Self::Value, E> {
+        if let Some(hex) = v.strip_prefix("0x") {
+            match from_hex(hex) {
+                Some(data) if !data.is_empty() => Ok(Integer {
+                    data: Cow::Owned(data),
+                }),
+                _ => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+            }
+        } else if let Ok(i) = v.parse::<i128>() {
+            Ok(Integer::from_i128(i))
+        } else if let Ok(i) = v.parse::<u128>() {
+            Ok(Integer::from_u128(i))
+        } else {
+            Err(E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for Integer<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(IntegerVisitor)
+        } else {
+            deserialize_der(deserializer, "an INTEGER", |bytes| {
+                let int = complete(Integer::from_der(bytes))?;
+                Ok(Integer {
+                    data: Cow::Owned(int.data.into_owned()),
+                })
+            })
+        }
+    }
+}
+
+/// Serialized using the ASN.1 value notation in human-readable formats: `'0A3B'H` if the
+/// number of bits is a multiple of 8, `'1010'B` otherwise
+impl Serialize for BitString<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let s = if self.unused_bits == 0 {
+                format!("'{}'H", to_hex(&self.data).to_uppercase())
+            } else {
+                let bits: String = (0..self.num_bits())
+                    .map(|i| if self.is_set(i) { '1' } else { '0' })
+                    .collect();
+                format!("'{}'B", bits)
+            };
+            serializer.serialize_str(&s)
+        } else {
+            serialize_der(self, serializer)
+        }
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for BitString<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserialize_str(deserializer, "a bstring or hstring", |s| {
+                let invalid = || Tag::BitString.invalid_value("invalid bstring or hstring");
+                let s = s.strip_prefix('\'').ok_or_else(invalid)?;
+                if let Some(hex) = s.strip_suffix("'H") {
+                    let data = from_hex(hex).ok_or_else(invalid)?;
+                    Ok(BitString {
+                        unused_bits: 0,
+                        data: Cow::Owned(data),
+                    })
+                } else if let Some(bits) = s.strip_suffix("'B") {
+                    let bits = bits
+                        .chars()
+                        .map(|c| match c {
+                            '0' => Ok(false),
+                            '1' => Ok(true),
+                            _ => Err(invalid()),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(BitString::from_bits(bits))
+                } else {
+                    Err(invalid())
+                }
+            })
+        } else {
+            deserialize_der(deserializer, "a BIT STRING", |bytes| {
+                let b = complete(BitString::from_der(bytes))?;
+                Ok(BitString {
+                    unused_bits: b.unused_bits,
+                    data: Cow::Owned(b.data.into_owned()),
+                })
+            })
+        }
+    }
+}
+
+/// Serialized as a hex string in human-readable formats, and as bytes otherwise
+impl Serialize for OctetString<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&to_hex(&self.data))
+        } else {
+            serializer.serialize_bytes(&self.data)
+        }
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> core::result::Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> core::result::Result<Vec<u8>, E> {
+        Ok(v)
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for OctetString<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        let data = if deserializer.is_human_readable() {
+            deserialize_str(deserializer, "a hex string", |s| {
+                from_hex(s).ok_or_else(|| Tag::OctetString.invalid_value("invalid hex string"))
+            })?
+        } else {
+            deserializer.deserialize_byte_buf(BytesVisitor)?
+        };
+        Ok(OctetString {
+            data: Cow::Owned(data),
+        })
+    }
+}
+
+/// Format a date and time using ISO 8601 (for ex. `2022-01-02T03:04:05.678+01:00`)
+fn to_iso8601(dt: &ASN1DateTime, year: u32) -> String {
+    let mut s = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year, dt.month, dt.day, dt.hour, dt.minute, dt.second
+    );
+    if let Some(ms) = dt.millisecond {
+        s.push_str(&format!(".{:03}", ms));
+    }
+    match dt.tz {
+        ASN1TimeZone::Undefined => (),
+        ASN1TimeZone::Z => s.push('Z'),
+        ASN1TimeZone::Offset(hh, mm) => {
+            let sign = if hh < 0 { '-' } else { '+' };
+            s.push_str(&format!("{}{:02}:{:02}", sign, hh.abs(), mm));
+        }
+    }
+    s
+}
+
+/// Convert an ISO 8601 date and time to the `GeneralizedTime` format
+///
+/// Only the separators are checked: the result must be parsed to check the values.
+fn iso8601_to_asn1(s: &str) -> Option<String> {
+    let b = s.as_bytes();
+    if !s.is_ascii()
+        || b.len() < 19
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || !(b[10] == b'T' || b[10] == b' ')
+        || b[13] != b':'
+        || b[16] != b':'
+    {
+        return None;
+    }
+    let mut out = String::with_capacity(s.len());
+    for range in [0..4, 5..7, 8..10, 11..13, 14..16, 17..19] {
+        out.push_str(&s[range]);
+    }
+    // fraction of seconds and time zone, without the separator of the offset
+    out.extend(s[19..].chars().filter(|&c| c != ':'));
+    Some(out)
+}
+
+/// Serialized using ISO 8601 (for ex. `"2022-01-02T03:04:05Z"`) in human-readable formats
+impl Serialize for GeneralizedTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&to_iso8601(&self.0, self.0.year))
+        } else {
+            serialize_der(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GeneralizedTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserialize_str(deserializer, "an ISO 8601 date and time", |s| {
+                let s = iso8601_to_asn1(s).ok_or_else(|| {
+                    Tag::GeneralizedTime.invalid_value("invalid ISO 8601 date and time")
+                })?;
+                GeneralizedTime::from_bytes(s.as_bytes())
+            })
+        } else {
+            deserialize_der(deserializer, "a GeneralizedTime", |bytes| {
+                complete(GeneralizedTime::from_der(bytes))
+            })
+        }
+    }
+}
+
+/// Serialized using ISO 8601 (for ex. `"2022-01-02T03:04:05Z"`) in human-readable formats
+///
+/// The two-digit year is interpreted as in X.509 (RFC 5280): `YY` is `19YY` if greater than or
+/// equal to 50, and `20YY` otherwise.
+impl Serialize for UtcTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let year = self.0.year % 100;
+            let year = if year >= 50 { 1900 + year } else { 2000 + year };
+            serializer.serialize_str(&to_iso8601(&self.0, year))
+        } else {
+            serialize_der(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UtcTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserialize_str(deserializer, "an ISO 8601 date and time", |s| {
+                let s = iso8601_to_asn1(s)
+                    .ok_or_else(|| Tag::UtcTime.invalid_value("invalid ISO 8601 date and time"))?;
+                match s[..4].parse::<u32>() {
+                    Ok(1950..=2049) => UtcTime::from_bytes(&s.as_bytes()[2..]),
+                    _ => Err(Tag::UtcTime.invalid_value("year out of range (1950 to 2049)")),
+                }
+            })
+        } else {
+            deserialize_der(deserializer, "a UTCTime", |bytes| {
+                complete(UtcTime::from_der(bytes))
+            })
+        }
+    }
+}
+
+/// Serialized as the hex string of its encoding in human-readable formats
+impl Serialize for Any<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let v = self.to_der_vec().map_err(ser::Error::custom)?;
+            serializer.serialize_str(&to_hex(&v))
+        } else {
+            serialize_der(self, serializer)
+        }
+    }
+}
+
+struct AnyVisitor;
+
+impl<'de> Visitor<'de> for AnyVisitor {
+    type Value = Any<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("borrowed DER encoding")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> core::result::Result<Any<'de>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(self)
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> core::result::Result<Any<'de>, E> {
+        complete(Any::from_der(v)).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, _v: &[u8]) -> core::result::Result<Any<'de>, E> {
+        Err(E::custom(Error::LifetimeError))
+    }
+}
+
+/// `Any` borrows its content, so it can be deserialized only from formats providing borrowed
+/// bytes (for ex. [`Asn1Deserializer`](crate::Asn1Deserializer)), and not from human-readable
+/// formats. Use [`Asn1Value`] to deserialize owned values from any format.
+impl<'de: 'a, 'a> Deserialize<'de> for Any<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        deserializer.deserialize_newtype_struct(DER_NEWTYPE, AnyVisitor)
+    }
+}
+
+/// Serialized as the hex string of its DER encoding in human-readable formats, like [`Any`]
+impl Serialize for Asn1Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let v = self.to_der_vec().map_err(ser::Error::custom)?;
+            serializer.serialize_str(&to_hex(&v))
+        } else {
+            serialize_der(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Asn1Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserialize_str(deserializer, "a hex string of a DER encoding", |s| {
+                let v = from_hex(s).ok_or(Error::SerdeError("invalid hex string".into()))?;
+                complete(Asn1Value::from_der(&v))
+            })
+        } else {
+            deserialize_der(deserializer, "an ASN.1 value", |bytes| {
+                complete(Asn1Value::from_der(bytes))
+            })
+        }
+    }
+}
@@ -2,6 +2,7 @@ use crate::ber::*;
 use crate::*;
 use alloc::borrow::Cow;
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::convert::{TryFrom, TryInto};
 
 /// The `Any` object is not strictly an ASN.1 type, but holds a generic description of any object
@@ -10,6 +11,8 @@ use core::convert::{TryFrom, TryInto};
 /// It contains a header, and either a reference to or owned data for the object content.
 ///
 /// Note: this type is only provided in **borrowed** version (*i.e.* it cannot own the inner data).
+/// [`Asn1Value`] is an owned alternative, which can also be deserialized from human-readable
+/// formats when the `serde` feature is enabled.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Any<'a> {
     /// The object header
@@ -105,6 +108,54 @@ impl<'a> Any<'a> {
         header.assert_definite()?;
         ber_get_object_content(i, header, 8)
     }
+
+    /// Get the content of a string object, concatenating segments if the object is constructed
+    ///
+    /// Data is borrowed if the object is primitive. See [`Any::string_segments`].
+    pub(crate) fn string_content(&self, segment_tag: Tag) -> Result<Cow<'a, [u8]>> {
+        if self.header.is_primitive() {
+            return Ok(Cow::Borrowed(self.data));
+        }
+        let segments = self.string_segments(segment_tag)?;
+        Ok(Cow::Owned(segments.concat()))
+    }
+
+    /// Get the (primitive) segments of a string object
+    ///
+    /// X.690 section 8.6.4 and 8.7.3: a constructed string contains a list of segments, encoded
+    /// with tag `segment_tag` (`BIT STRING` for bit strings, and `OCTET STRING` for all other
+    /// string types). Segments can themselves be constructed.
+    ///
+    /// If the object is primitive, the only segment is the object content.
+    pub(crate) fn string_segments(&self, segment_tag: Tag) -> Result<Vec<&'a [u8]>> {
+        let mut segments = Vec::new();
+        self.collect_segments(segment_tag, &mut segments, MAX_RECURSION)?;
+        Ok(segments)
+    }
+
+    fn collect_segments(
+        &self,
+        segment_tag: Tag,
+        segments: &mut Vec<&'a [u8]>,
+        max_depth: usize,
+    ) -> Result<()> {
+        if self.header.is_primitive() {
+            segments.push(self.data);
+            return Ok(());
+        }
+        if max_depth == 0 {
+            return Err(Error::BerMaxDepth);
+        }
+        let mut i = self.data;
+        while !i.is_empty() {
+            let (rem, segment) = Any::from_ber(i)?;
+            segment.header.assert_class(Class::Universal)?;
+            segment.tag().assert_eq(segment_tag)?;
+            segment.collect_segments(segment_tag, segments, max_depth - 1)?;
+            i = rem;
+        }
+        Ok(())
+    }
 }
 
 macro_rules! impl_any_into {
@@ -287,6 +338,14 @@ impl<'a> FromDer<'a> for Any<'a> {
     }
 }
 
+impl<'a> FromCer<'a> for Any<'a> {
+    fn from_cer(bytes: &'a [u8]) -> ParseResult<Self> {
+        let (i, header) = Header::from_cer(bytes)?;
+        let (i, data) = ber_get_object_content(i, &header, MAX_RECURSION)?;
+        Ok((i, Any { header, data }))
+    }
+}
+
 impl CheckDerConstraints for Any<'_> {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.length().assert_definite()?;
@@ -1,10 +1,19 @@
+use crate::cer::*;
 use crate::*;
 use alloc::borrow::Cow;
+use alloc::vec::Vec;
 #[cfg(feature = "bits")]
 use bitvec::{order::Msb0, slice::BitSlice};
 use core::convert::TryFrom;
 
 /// ASN.1 `BITSTRING` type
+///
+/// Bits are numbered from the most significant bit of the first byte (bit 0), as in ASN.1
+/// named bit lists. For example, bit 0 of `KeyUsage` (`digitalSignature`) is `0x80` in the
+/// first byte.
+///
+/// To build values from flags, see [`BitString::from_bits`], [`BitString::set`] and the
+/// [`named_bit_string!`](crate::named_bit_string) macro.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BitString<'a> {
     pub unused_bits: u8,
@@ -30,6 +39,94 @@ impl<'a> BitString<'a> {
         (self.data[byte_pos] & (1 << b)) != 0
     }
 
+    /// Build a `BitString` from a sequence of bits (owned)
+    ///
+    /// The first item is bit 0 (most significant bit of the first byte). The number of unused
+    /// bits is computed from the number of items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::BitString;
+    ///
+    /// let b = BitString::from_bits([true, false, true, true]);
+    /// assert_eq!(b.unused_bits, 4);
+    /// assert_eq!(b.as_ref(), &[0b1011_0000]);
+    /// ```
+    pub fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> BitString<'static> {
+        let mut data = Vec::new();
+        let mut len = 0;
+        for bit in bits {
+            if len % 8 == 0 {
+                data.push(0);
+            }
+            if bit {
+                data[len / 8] |= 0x80 >> (len % 8);
+            }
+            len += 1;
+        }
+        BitString {
+            unused_bits: ((8 - len % 8) % 8) as u8,
+            data: Cow::Owned(data),
+        }
+    }
+
+    /// Return the number of bits (excluding unused bits)
+    pub fn num_bits(&self) -> usize {
+        (self.data.len() * 8).saturating_sub(self.unused_bits as usize)
+    }
+
+    /// Set bit `bitnum`
+    ///
+    /// If `bitnum` is after the last bit, the bit string is extended with zero bits.
+    pub fn set(&mut self, bitnum: usize) {
+        if bitnum >= self.num_bits() {
+            let len = bitnum + 1;
+            self.data.to_mut().resize((len + 7) / 8, 0);
+            self.unused_bits = ((8 - len % 8) % 8) as u8;
+        }
+        self.data.to_mut()[bitnum / 8] |= 0x80 >> (bitnum % 8);
+    }
+
+    /// Clear bit `bitnum`
+    ///
+    /// This does not change the length of the bit string (see
+    /// [`BitString::trim_trailing_zeros`]).
+    pub fn clear(&mut self, bitnum: usize) {
+        if bitnum < self.num_bits() {
+            self.data.to_mut()[bitnum / 8] &= !(0x80 >> (bitnum % 8));
+        }
+    }
+
+    /// Remove trailing zero bits
+    ///
+    /// This is required by DER for types defined with a named bit list (X.690 section 11.2.2).
+    /// Unused bits are also cleared.
+    pub fn trim_trailing_zeros(&mut self) {
+        let mut len = self.num_bits();
+        while len > 0 && !self.is_set(len - 1) {
+            len -= 1;
+        }
+        if len == self.num_bits() && self.padding_is_zero() {
+            return;
+        }
+        let data = self.data.to_mut();
+        data.truncate((len + 7) / 8);
+        self.unused_bits = ((8 - len % 8) % 8) as u8;
+        if let Some(last) = data.last_mut() {
+            *last &= 0xff << self.unused_bits;
+        }
+    }
+
+    fn padding_is_zero(&self) -> bool {
+        match self.data.last() {
+            Some(&last) => {
+                self.unused_bits >= 8 || last.trailing_zeros() >= self.unused_bits as u32
+            }
+            None => true,
+        }
+    }
+
     /// Constructs a shared `&BitSlice` reference over the object data.
     #[cfg(feature = "bits")]
     pub fn as_bitslice(&self) -> Option<&BitSlice<u8, Msb0>> {
@@ -57,12 +154,31 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for BitString<'a> {
 
     fn try_from(any: &'b Any<'a>) -> Result<BitString<'a>> {
         any.tag().assert_eq(Self::TAG)?;
-        if any.data.is_empty() {
-            return Err(Error::InvalidLength);
+        if any.header.is_primitive() {
+            if any.data.is_empty() {
+                return Err(Error::InvalidLength);
+            }
+            let s = any.data;
+            let (unused_bits, data) = (s[0], Cow::Borrowed(&s[1..]));
+            return Ok(BitString { unused_bits, data });
+        }
+        // X.690 section 8.6.4: constructed bit strings are made of segments, each starting with
+        // the number of unused bits (which must be 0, except for the last segment)
+        let segments = any.string_segments(Tag::BitString)?;
+        let mut data = Vec::new();
+        let mut unused_bits = 0;
+        for (idx, segment) in segments.iter().enumerate() {
+            let (&unused, bits) = segment.split_first().ok_or(Error::InvalidLength)?;
+            if unused != 0 && idx + 1 != segments.len() {
+                return Err(Error::BerValueError);
+            }
+            data.extend_from_slice(bits);
+            unused_bits = unused;
         }
-        let s = any.data;
-        let (unused_bits, data) = (s[0], Cow::Borrowed(&s[1..]));
-        Ok(BitString { unused_bits, data })
+        Ok(BitString {
+            unused_bits,
+            data: Cow::Owned(data),
+        })
     }
 }
 
@@ -70,32 +186,59 @@ impl<'a> CheckDerConstraints for BitString<'a> {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 10.2
         any.header.assert_primitive()?;
-        // Check that padding bits are all 0 (X.690 section 11.2.1)
-        match any.data.len() {
-            0 => Err(Error::InvalidLength),
-            1 => {
-                // X.690 section 11.2.2 Note 2
-                if any.data[0] == 0 {
-                    Ok(())
-                } else {
-                    Err(Error::InvalidLength)
-                }
-            }
-            len => {
-                let unused_bits = any.data[0];
-                let last_byte = any.data[len - 1];
-                if last_byte.trailing_zeros() < unused_bits as u32 {
-                    return Err(Error::DerConstraintFailed(DerConstraint::UnusedBitsNotZero));
-                }
+        check_padding(any.data)
+    }
+}
 
+/// Check that padding bits are all 0 (X.690 section 11.2.1)
+///
+/// `data` is the encoded content, starting with the number of unused bits.
+fn check_padding(data: &[u8]) -> Result<()> {
+    match data.len() {
+        0 => Err(Error::InvalidLength),
+        1 => {
+            // X.690 section 11.2.2 Note 2
+            if data[0] == 0 {
                 Ok(())
+            } else {
+                Err(Error::InvalidLength)
             }
         }
+        len => {
+            let unused_bits = data[0];
+            let last_byte = data[len - 1];
+            if last_byte.trailing_zeros() < unused_bits as u32 {
+                return Err(Error::DerConstraintFailed(DerConstraint::UnusedBitsNotZero));
+            }
+
+            Ok(())
+        }
     }
 }
 
 impl DerAutoDerive for BitString<'_> {}
 
+impl<'a> CheckCerConstraints for BitString<'a> {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        // X.690 section 9.2
+        let segments = cer_string_segments(any, Tag::BitString)?;
+        match segments.split_last() {
+            Some((last, others)) => {
+                // only the last segment can have unused bits
+                if others.iter().any(|segment| segment[0] != 0) {
+                    return Err(Error::CerConstraintFailed(
+                        CerConstraint::InvalidStringSegment,
+                    ));
+                }
+                check_padding(last)
+            }
+            None => Err(Error::InvalidLength),
+        }
+    }
+}
+
+impl CerAutoDerive for BitString<'_> {}
+
 impl<'a> Tagged for BitString<'a> {
     const TAG: Tag = Tag::BitString;
 }
@@ -103,6 +246,9 @@ impl<'a> Tagged for BitString<'a> {
 #[cfg(feature = "std")]
 impl ToDer for BitString<'_> {
     fn to_der_len(&self) -> Result<usize> {
+        if self.unused_bits > 7 {
+            return Err(Error::InvalidLength);
+        }
         let sz = self.data.len();
         if sz < 127 {
             // 1 (class+tag) + 1 (length) +  1 (unused bits) + len
@@ -125,12 +271,201 @@ impl ToDer for BitString<'_> {
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        // X.690 section 11.2.1: unused bits are 0, and there are none if the value is empty
+        let (last, data) = match self.data.split_last() {
+            Some((last, data)) => (last, data),
+            None => return writer.write(&[0]).map_err(Into::into),
+        };
+        if self.unused_bits > 7 {
+            return Err(SerializeError::InvalidLength);
+        }
         let sz = writer.write(&[self.unused_bits])?;
-        let sz = sz + writer.write(&self.data)?;
+        let sz = sz + writer.write(data)?;
+        let sz = sz + writer.write(&[last & (0xff << self.unused_bits)])?;
         Ok(sz)
     }
 }
 
+#[cfg(feature = "std")]
+impl ToCer for BitString<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        // content (unused bits octet and data) fits in one primitive segment
+        if self.data.len() < CER_SEGMENT_LEN {
+            return self.write_der(writer);
+        }
+        if self.unused_bits > 7 {
+            return Err(SerializeError::InvalidLength);
+        }
+        let mut data = self.data.to_vec();
+        if let Some(last) = data.last_mut() {
+            *last &= 0xff << self.unused_bits;
+        }
+        // X.690 section 9.2: each segment has its own unused bits octet, so it contains
+        // 999 octets of data
+        let num_segments = (data.len() + CER_SEGMENT_LEN - 2) / (CER_SEGMENT_LEN - 1);
+        write_cer_constructed(writer, Class::Universal, Self::TAG, |writer| {
+            let mut sz = 0;
+            for (idx, chunk) in data.chunks(CER_SEGMENT_LEN - 1).enumerate() {
+                let unused_bits = if idx + 1 == num_segments {
+                    self.unused_bits
+                } else {
+                    0
+                };
+                let header = Header::new(
+                    Class::Universal,
+                    false,
+                    Tag::BitString,
+                    Length::Definite(1 + chunk.len()),
+                );
+                sz += header.write_der_header(writer)?;
+                sz += writer.write(&[unused_bits])?;
+                sz += writer.write(chunk)?;
+            }
+            Ok(sz)
+        })
+    }
+}
+
+/// Declare a structure for an ASN.1 `BIT STRING` with a named bit list
+///
+/// Each field is a `bool`, associated to the number of the bit in the ASN.1 definition.
+/// The macro implements the conversion from and to [`BitString`], parsing (BER and DER) and
+/// encoding (DER).
+///
+/// When encoding, trailing zero bits are removed, as required by DER for named bit lists
+/// (X.690 section 11.2.2). When parsing DER, values with trailing zero bits are rejected.
+/// Bits that are not named are ignored when parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{named_bit_string, FromDer, ToDer};
+///
+/// named_bit_string! {
+///     /// RFC 5280 `KeyUsage`
+///     #[derive(Debug, Default, PartialEq)]
+///     pub struct KeyUsage {
+///         digital_signature = 0,
+///         non_repudiation = 1,
+///         key_encipherment = 2,
+///         data_encipherment = 3,
+///         key_agreement = 4,
+///         key_cert_sign = 5,
+///         crl_sign = 6,
+///         encipher_only = 7,
+///         decipher_only = 8,
+///     }
+/// }
+///
+/// let ku = KeyUsage {
+///     digital_signature: true,
+///     key_cert_sign: true,
+///     ..Default::default()
+/// };
+/// # #[cfg(feature = "std")] {
+/// let bytes = ku.to_der_vec().expect("serialization failed");
+/// assert_eq!(&bytes, &[0x03, 0x02, 0x02, 0x84]);
+///
+/// let (_, decoded) = KeyUsage::from_der(&bytes).expect("parsing failed");
+/// assert_eq!(decoded, ku);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! named_bit_string {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident = $bit:expr ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $( $(#[$field_meta])* pub $field: bool, )*
+        }
+
+        impl $name {
+            /// Build the value from a `BitString`
+            pub fn from_bitstring(b: &$crate::BitString) -> Self {
+                $name {
+                    $( $field: b.is_set($bit), )*
+                }
+            }
+
+            /// Return the value as a `BitString`, with trailing zero bits removed
+            pub fn to_bitstring(&self) -> $crate::BitString<'static> {
+                let mut b = $crate::BitString::from_bits(::core::iter::empty());
+                $( if self.$field { b.set($bit); } )*
+                b
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<$crate::Any<'a>> for $name {
+            type Error = $crate::Error;
+
+            fn try_from(any: $crate::Any<'a>) -> $crate::Result<$name> {
+                let b = <$crate::BitString as ::core::convert::TryFrom<_>>::try_from(any)?;
+                Ok($name::from_bitstring(&b))
+            }
+        }
+
+        impl $crate::CheckDerConstraints for $name {
+            fn check_constraints(any: &$crate::Any) -> $crate::Result<()> {
+                <$crate::BitString as $crate::CheckDerConstraints>::check_constraints(any)?;
+                let b = <$crate::BitString as ::core::convert::TryFrom<_>>::try_from(any)?;
+                let len = b.num_bits();
+                if len > 0 && !b.is_set(len - 1) {
+                    return Err($crate::Error::DerConstraintFailed(
+                        $crate::DerConstraint::NamedBitsTrailingZero,
+                    ));
+                }
+                Ok(())
+            }
+        }
+
+        impl $crate::DerAutoDerive for $name {}
+
+        impl $crate::Tagged for $name {
+            const TAG: $crate::Tag = $crate::Tag::BitString;
+        }
+
+        $crate::__named_bit_string_to_der!($name);
+    };
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __named_bit_string_to_der {
+    ($name:ident) => {
+        impl $crate::ToDer for $name {
+            fn to_der_len(&self) -> $crate::Result<usize> {
+                self.to_bitstring().to_der_len()
+            }
+
+            fn write_der_header(
+                &self,
+                writer: &mut dyn ::std::io::Write,
+            ) -> $crate::SerializeResult<usize> {
+                self.to_bitstring().write_der_header(writer)
+            }
+
+            fn write_der_content(
+                &self,
+                writer: &mut dyn ::std::io::Write,
+            ) -> $crate::SerializeResult<usize> {
+                self.to_bitstring().write_der_content(writer)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __named_bit_string_to_der {
+    ($name:ident) => {};
+}
+
 #[cfg(test)]
 mod tests {
     use super::BitString;
@@ -144,6 +479,24 @@ mod tests {
         assert!(obj.is_set(17));
     }
 
+    #[test]
+    fn test_bitstring_set_clear() {
+        let mut obj = BitString::from_bits([]);
+        assert_eq!(obj.num_bits(), 0);
+        obj.set(0);
+        obj.set(9);
+        assert_eq!(obj.unused_bits, 6);
+        assert_eq!(obj.as_ref(), &[0x80, 0x40]);
+        obj.clear(9);
+        assert_eq!(obj.num_bits(), 10);
+        obj.trim_trailing_zeros();
+        assert_eq!(obj.unused_bits, 7);
+        assert_eq!(obj.as_ref(), &[0x80]);
+        obj.clear(0);
+        obj.trim_trailing_zeros();
+        assert_eq!(obj, BitString::new(0, &[]));
+    }
+
     #[cfg(feature = "bits")]
     #[test]
     fn test_bitstring_to_bitvec() {
@@ -67,6 +67,21 @@ impl<'a> CheckDerConstraints for Boolean {
 
 impl DerAutoDerive for Boolean {}
 
+impl CheckCerConstraints for Boolean {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for Boolean {}
+
+#[cfg(feature = "std")]
+impl ToCer for Boolean {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl<'a> Tagged for Boolean {
     const TAG: Tag = Tag::Boolean;
 }
@@ -0,0 +1,149 @@
+use crate::*;
+#[cfg(not(feature = "std"))]
+use alloc::format;
+use core::convert::TryFrom;
+use core::fmt;
+
+use super::integer::check_der_int_constraints;
+
+/// ASN.1 `INTEGER` type, with a value range constraint (`INTEGER (MIN..MAX)`)
+///
+/// The value is checked when parsing, or when building the object. Values outside the range
+/// return an `InvalidValue` error.
+///
+/// Use `i64::MIN` or `i64::MAX` for unbounded ranges, for ex. `INTEGER (0..MAX)` is
+/// `ConstrainedInt<0, { i64::MAX }>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{ConstrainedInt, FromDer};
+///
+/// type Version = ConstrainedInt<1, 255>;
+///
+/// let (_, v) = Version::from_der(&[0x02, 0x01, 0x03]).expect("parsing failed");
+/// assert_eq!(v.value(), 3);
+/// // value 0 is out of range
+/// assert!(Version::from_der(&[0x02, 0x01, 0x00]).is_err());
+/// assert!(Version::new(256).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstrainedInt<const MIN: i64, const MAX: i64>(i64);
+
+impl<const MIN: i64, const MAX: i64> ConstrainedInt<MIN, MAX> {
+    /// Lower bound of the range (inclusive)
+    pub const LOWER_BOUND: i64 = MIN;
+    /// Upper bound of the range (inclusive)
+    pub const UPPER_BOUND: i64 = MAX;
+
+    /// Build a new `ConstrainedInt`, checking that value is in range
+    pub fn new(value: i64) -> Result<Self> {
+        if value < MIN || value > MAX {
+            return Err(Error::invalid_value(
+                Tag::Integer,
+                format!("value {} out of range ({}..{})", value, MIN, MAX),
+            ));
+        }
+        Ok(ConstrainedInt(value))
+    }
+
+    /// Return the value
+    #[inline]
+    pub const fn value(&self) -> i64 {
+        self.0
+    }
+
+    /// Return the offset of the value from the lower bound
+    ///
+    /// This is the number encoded by range-based encodings, like the constrained whole
+    /// numbers of PER.
+    #[inline]
+    pub const fn offset(&self) -> u64 {
+        self.0.wrapping_sub(MIN) as u64
+    }
+
+    /// Return the number of values in the range, minus one (`MAX - MIN`)
+    #[inline]
+    pub const fn range() -> u64 {
+        MAX.wrapping_sub(MIN) as u64
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> fmt::Display for ConstrainedInt<MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> TryFrom<i64> for ConstrainedInt<MIN, MAX> {
+    type Error = Error;
+
+    fn try_from(value: i64) -> Result<Self> {
+        Self::new(value)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> From<ConstrainedInt<MIN, MAX>> for i64 {
+    fn from(i: ConstrainedInt<MIN, MAX>) -> Self {
+        i.0
+    }
+}
+
+impl<'a, const MIN: i64, const MAX: i64> TryFrom<Any<'a>> for ConstrainedInt<MIN, MAX> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Self> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b, const MIN: i64, const MAX: i64> TryFrom<&'b Any<'a>> for ConstrainedInt<MIN, MAX> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
+        let value = Integer::try_from(any)?.as_i64()?;
+        Self::new(value)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> CheckDerConstraints for ConstrainedInt<MIN, MAX> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        check_der_int_constraints(any)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> DerAutoDerive for ConstrainedInt<MIN, MAX> {}
+
+impl<const MIN: i64, const MAX: i64> CheckCerConstraints for ConstrainedInt<MIN, MAX> {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> CerAutoDerive for ConstrainedInt<MIN, MAX> {}
+
+#[cfg(feature = "std")]
+impl<const MIN: i64, const MAX: i64> ToCer for ConstrainedInt<MIN, MAX> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> Tagged for ConstrainedInt<MIN, MAX> {
+    const TAG: Tag = Tag::Integer;
+}
+
+#[cfg(feature = "std")]
+impl<const MIN: i64, const MAX: i64> ToDer for ConstrainedInt<MIN, MAX> {
+    fn to_der_len(&self) -> Result<usize> {
+        ConstInt::from_i64(self.0).to_der_len()
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        ConstInt::from_i64(self.0).write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        ConstInt::from_i64(self.0).write_der_content(writer)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::*;
 use core::convert::TryFrom;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct EmbeddedPdv<'a> {
     pub identification: PdvIdentification<'a>,
     pub data_value_descriptor: Option<ObjectDescriptor<'a>>,
@@ -39,75 +39,99 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for EmbeddedPdv<'a> {
         let data = any.data;
         // AUTOMATIC TAGS means all values will be tagged (IMPLICIT)
         // [0] -> identification
+        // (a CHOICE is always explicitly tagged)
         let (rem, seq0) =
             TaggedParser::<Explicit, Any>::parse_ber(Class::ContextSpecific, Tag(0), data)?;
-        let inner = seq0.inner;
-        let identification = match inner.tag() {
+        let identification = PdvIdentification::try_from(&seq0.inner)?;
+        // [1] -> data-value-descriptor ObjectDescriptor OPTIONAL
+        // The associated type (X.680 36.5) has `WITH COMPONENTS data-value-descriptor ABSENT`,
+        // but accept it when present so it can be round-tripped.
+        let (rem, data_value_descriptor) =
+            OptTaggedImplicit::<ObjectDescriptor, Error, 1>::from_ber(rem)?;
+        let data_value_descriptor = data_value_descriptor.map(|t| t.into_inner());
+        // [2] -> data-value OCTET STRING
+        let (_, data_value) =
+            TaggedParser::<Implicit, &[u8]>::parse_ber(Class::ContextSpecific, Tag(2), rem)?;
+        let data_value = data_value.inner;
+        let obj = EmbeddedPdv {
+            identification,
+            data_value_descriptor,
+            data_value,
+        };
+        Ok(obj)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for PdvIdentification<'a> {
+    type Error = Error;
+
+    /// Parse the selected alternative of the identification CHOICE
+    ///
+    /// `any` is the alternative itself (without the explicit `[0]` tag of the `identification`
+    /// component)
+    fn try_from(any: &'b Any<'a>) -> Result<Self> {
+        any.class().assert_eq(Class::ContextSpecific)?;
+        let identification = match any.tag() {
             Tag(0) => {
                 // syntaxes SEQUENCE {
-                //     abstract OBJECT IDENTIFIER,
-                //     transfer OBJECT IDENTIFIER
+                //     abstract [0] IMPLICIT OBJECT IDENTIFIER,
+                //     transfer [1] IMPLICIT OBJECT IDENTIFIER
                 // },
-                let (rem, s_abstract) = Oid::from_ber(inner.data)?;
-                let (_, s_transfer) = Oid::from_ber(rem)?;
+                any.header.assert_constructed()?;
+                let (rem, s_abstract) = TaggedImplicit::<Oid, Error, 0>::from_ber(any.data)?;
+                let (_, s_transfer) = TaggedImplicit::<Oid, Error, 1>::from_ber(rem)?;
                 PdvIdentification::Syntaxes {
-                    s_abstract,
-                    s_transfer,
+                    s_abstract: s_abstract.into_inner(),
+                    s_transfer: s_transfer.into_inner(),
                 }
             }
             Tag(1) => {
                 // syntax OBJECT IDENTIFIER
-                let oid = Oid::new(inner.data.into());
+                any.header.assert_primitive()?;
+                let oid = Oid::try_from(any)?;
                 PdvIdentification::Syntax(oid)
             }
             Tag(2) => {
                 // presentation-context-id INTEGER
-                let i = Integer::new(inner.data);
+                any.header.assert_primitive()?;
+                let i = Integer::new(any.data);
                 PdvIdentification::PresentationContextId(i)
             }
             Tag(3) => {
                 // context-negotiation SEQUENCE {
-                //     presentation-context-id INTEGER,
-                //     transfer-syntax OBJECT IDENTIFIER
+                //     presentation-context-id [0] IMPLICIT INTEGER,
+                //     transfer-syntax [1] IMPLICIT OBJECT IDENTIFIER
                 // },
-                let (rem, presentation_context_id) = Integer::from_ber(inner.data)?;
-                let (_, presentation_syntax) = Oid::from_ber(rem)?;
+                any.header.assert_constructed()?;
+                let (rem, presentation_context_id) =
+                    TaggedImplicit::<Integer, Error, 0>::from_ber(any.data)?;
+                let (_, presentation_syntax) = TaggedImplicit::<Oid, Error, 1>::from_ber(rem)?;
                 PdvIdentification::ContextNegotiation {
-                    presentation_context_id,
-                    presentation_syntax,
+                    presentation_context_id: presentation_context_id.into_inner(),
+                    presentation_syntax: presentation_syntax.into_inner(),
                 }
             }
             Tag(4) => {
                 // transfer-syntax OBJECT IDENTIFIER
-                let oid = Oid::new(inner.data.into());
+                any.header.assert_primitive()?;
+                let oid = Oid::try_from(any)?;
                 PdvIdentification::TransferSyntax(oid)
             }
             Tag(5) => {
                 // fixed NULL
+                any.header.assert_primitive()?;
+                if !any.data.is_empty() {
+                    return Err(Error::InvalidLength);
+                }
                 PdvIdentification::Fixed
             }
             _ => {
-                return Err(inner
+                return Err(any
                     .tag()
                     .invalid_value("Invalid identification tag in EMBEDDED PDV"))
             }
         };
-        // [1] -> data-value-descriptor ObjectDescriptor OPTIONAL
-        // *BUT* WITH COMPONENTS data-value-descriptor ABSENT
-        // XXX this should be parse_ber?
-        // let (rem, data_value_descriptor) =
-        //     TaggedOptional::from(1).parse_der(rem, |_, inner| ObjectDescriptor::from_ber(inner))?;
-        let (rem, data_value_descriptor) = (rem, None);
-        // [2] -> data-value OCTET STRING
-        let (_, data_value) =
-            TaggedParser::<Implicit, &[u8]>::parse_ber(Class::ContextSpecific, Tag(2), rem)?;
-        let data_value = data_value.inner;
-        let obj = EmbeddedPdv {
-            identification,
-            data_value_descriptor,
-            data_value,
-        };
-        Ok(obj)
+        Ok(identification)
     }
 }
 
@@ -120,3 +144,187 @@ impl CheckDerConstraints for EmbeddedPdv<'_> {
 }
 
 impl DerAutoDerive for EmbeddedPdv<'_> {}
+
+impl Tagged for EmbeddedPdv<'_> {
+    const TAG: Tag = Tag::EmbeddedPdv;
+}
+
+/// The tag of a `PdvIdentification` is the (context-specific) tag of the selected alternative
+impl DynTagged for PdvIdentification<'_> {
+    fn tag(&self) -> Tag {
+        match self {
+            PdvIdentification::Syntaxes { .. } => Tag(0),
+            PdvIdentification::Syntax(_) => Tag(1),
+            PdvIdentification::PresentationContextId(_) => Tag(2),
+            PdvIdentification::ContextNegotiation { .. } => Tag(3),
+            PdvIdentification::TransferSyntax(_) => Tag(4),
+            PdvIdentification::Fixed => Tag(5),
+        }
+    }
+}
+
+/// Get the length of `[tag] IMPLICIT` content, when encoded
+#[cfg(feature = "std")]
+fn implicit_len(tag: u32, constructed: bool, content_len: usize) -> Result<usize> {
+    let header = Header::new(
+        Class::ContextSpecific,
+        constructed,
+        Tag(tag),
+        Length::Definite(content_len),
+    );
+    Ok(header.to_der_len()? + content_len)
+}
+
+/// Write content bytes as `[tag] IMPLICIT`
+#[cfg(feature = "std")]
+fn write_implicit(
+    tag: u32,
+    constructed: bool,
+    content: &[u8],
+    writer: &mut dyn std::io::Write,
+) -> SerializeResult<usize> {
+    let header = Header::new(
+        Class::ContextSpecific,
+        constructed,
+        Tag(tag),
+        Length::Definite(content.len()),
+    );
+    let sz = header.write_der_header(writer)?;
+    let sz = sz + writer.write(content)?;
+    Ok(sz)
+}
+
+#[cfg(feature = "std")]
+impl PdvIdentification<'_> {
+    fn is_constructed(&self) -> bool {
+        matches!(
+            self,
+            PdvIdentification::Syntaxes { .. } | PdvIdentification::ContextNegotiation { .. }
+        )
+    }
+
+    fn content_len(&self) -> Result<usize> {
+        match self {
+            PdvIdentification::Syntaxes {
+                s_abstract,
+                s_transfer,
+            } => {
+                let sz = implicit_len(0, false, s_abstract.as_bytes().len())?;
+                let sz = sz + implicit_len(1, false, s_transfer.as_bytes().len())?;
+                Ok(sz)
+            }
+            PdvIdentification::Syntax(oid) | PdvIdentification::TransferSyntax(oid) => {
+                Ok(oid.as_bytes().len())
+            }
+            PdvIdentification::PresentationContextId(i) => Ok(i.as_ref().len()),
+            PdvIdentification::ContextNegotiation {
+                presentation_context_id,
+                presentation_syntax,
+            } => {
+                let sz = implicit_len(0, false, presentation_context_id.as_ref().len())?;
+                let sz = sz + implicit_len(1, false, presentation_syntax.as_bytes().len())?;
+                Ok(sz)
+            }
+            PdvIdentification::Fixed => Ok(0),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToDer for PdvIdentification<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        implicit_len(self.tag().0, self.is_constructed(), self.content_len()?)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let header = Header::new(
+            Class::ContextSpecific,
+            self.is_constructed(),
+            self.tag(),
+            Length::Definite(self.content_len()?),
+        );
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        match self {
+            PdvIdentification::Syntaxes {
+                s_abstract,
+                s_transfer,
+            } => {
+                let sz = write_implicit(0, false, s_abstract.as_bytes(), writer)?;
+                let sz = sz + write_implicit(1, false, s_transfer.as_bytes(), writer)?;
+                Ok(sz)
+            }
+            PdvIdentification::Syntax(oid) | PdvIdentification::TransferSyntax(oid) => {
+                writer.write(oid.as_bytes()).map_err(Into::into)
+            }
+            PdvIdentification::PresentationContextId(i) => {
+                writer.write(i.as_ref()).map_err(Into::into)
+            }
+            PdvIdentification::ContextNegotiation {
+                presentation_context_id,
+                presentation_syntax,
+            } => {
+                let sz = write_implicit(0, false, presentation_context_id.as_ref(), writer)?;
+                let sz = sz + write_implicit(1, false, presentation_syntax.as_bytes(), writer)?;
+                Ok(sz)
+            }
+            PdvIdentification::Fixed => Ok(0),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl EmbeddedPdv<'_> {
+    fn content_len(&self) -> Result<usize> {
+        // [0] EXPLICIT identification
+        let sz = implicit_len(0, true, self.identification.to_der_len()?)?;
+        // [1] IMPLICIT data-value-descriptor OPTIONAL
+        let sz = match &self.data_value_descriptor {
+            Some(descriptor) => sz + implicit_len(1, false, descriptor.as_ref().len())?,
+            None => sz,
+        };
+        // [2] IMPLICIT data-value
+        implicit_len(2, false, self.data_value.len()).map(|n| sz + n)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToDer for EmbeddedPdv<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = self.content_len()?;
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(sz));
+        Ok(header.to_der_len()? + sz)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let header = Header::new(
+            Class::Universal,
+            true,
+            Self::TAG,
+            Length::Definite(self.content_len()?),
+        );
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let identification_len = self.identification.to_der_len()?;
+        let header = Header::new(
+            Class::ContextSpecific,
+            true,
+            Tag(0),
+            Length::Definite(identification_len),
+        );
+        let sz = header.write_der_header(writer)?;
+        let sz = sz + self.identification.write_der(writer)?;
+        let sz = match &self.data_value_descriptor {
+            Some(descriptor) => {
+                sz + write_implicit(1, false, descriptor.as_ref().as_bytes(), writer)?
+            }
+            None => sz,
+        };
+        let sz = sz + write_implicit(2, false, self.data_value, writer)?;
+        Ok(sz)
+    }
+}
@@ -1,17 +1,22 @@
-use crate::ber::bytes_to_u64;
+use super::integer::check_der_int_constraints;
 use crate::*;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
 use core::convert::TryFrom;
 
 /// ASN.1 `ENUMERATED` type
 ///
+/// The value is signed, and decoded as a two's complement integer (like `INTEGER`).
+///
 /// # Limitations
 ///
-/// Supported values are limited to 0 .. 2^32
+/// Supported values are limited to the range of `i64`. Use [`BigEnumerated`] for values
+/// of any size.
 #[derive(Debug, PartialEq)]
-pub struct Enumerated(pub u32);
+pub struct Enumerated(pub i64);
 
 impl Enumerated {
-    pub const fn new(value: u32) -> Self {
+    pub const fn new(value: i64) -> Self {
         Enumerated(value)
     }
 }
@@ -30,42 +35,234 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for Enumerated {
     fn try_from(any: &'b Any<'a>) -> Result<Enumerated> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_primitive()?;
-        let res_u64 = bytes_to_u64(any.data)?;
-        if res_u64 > (<u32>::MAX as u64) {
-            return Err(Error::IntegerTooLarge);
-        }
-        let value = res_u64 as u32;
+        let value = decode_i64(any.data)?;
         Ok(Enumerated(value))
     }
 }
 
 impl CheckDerConstraints for Enumerated {
     fn check_constraints(any: &Any) -> Result<()> {
-        any.header.length.assert_definite()?;
-        Ok(())
+        check_der_int_constraints(any)
     }
 }
 
 impl DerAutoDerive for Enumerated {}
 
+impl CheckCerConstraints for Enumerated {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for Enumerated {}
+
+#[cfg(feature = "std")]
+impl ToCer for Enumerated {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl Tagged for Enumerated {
     const TAG: Tag = Tag::Enumerated;
 }
 
+/// Decode a two's complement integer
+fn decode_i64(bytes: &[u8]) -> Result<i64> {
+    if bytes.is_empty() {
+        return Err(Error::InvalidLength);
+    }
+    // skip leading bytes if they are only sign extension
+    let negative = bytes[0] >= 0x80;
+    let skip = bytes
+        .windows(2)
+        .take_while(|w| (w[0] == 0x00 && w[1] < 0x80) || (w[0] == 0xff && w[1] >= 0x80))
+        .count();
+    let bytes = &bytes[skip..];
+    if bytes.len() > 8 {
+        return Err(Error::IntegerTooLarge);
+    }
+    let mut buf = if negative { [0xff; 8] } else { [0; 8] };
+    buf[8 - bytes.len()..].copy_from_slice(bytes);
+    Ok(i64::from_be_bytes(buf))
+}
+
+/// Encode value as a two's complement integer, using the minimum number of bytes
+fn encode_i64(value: i64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    // skip leading bytes if they are only sign extension
+    let skip = bytes
+        .windows(2)
+        .take_while(|w| (w[0] == 0x00 && w[1] < 0x80) || (w[0] == 0xff && w[1] >= 0x80))
+        .count();
+    bytes[skip..].to_vec()
+}
+
 #[cfg(feature = "std")]
 impl ToDer for Enumerated {
     fn to_der_len(&self) -> Result<usize> {
-        Integer::from(self.0).to_der_len()
+        // content length is at most 8, so length is encoded in 1 byte
+        Ok(2 + encode_i64(self.0).len())
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let len = Integer::from(self.0).to_der_len()?;
+        let len = encode_i64(self.0).len();
         let header = Header::new(Class::Universal, false, Self::TAG, Length::Definite(len));
-        header.write_der_header(writer).map_err(Into::into)
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(&encode_i64(self.0)).map_err(Into::into)
+    }
+}
+
+/// ASN.1 `ENUMERATED` type, for values of any size
+///
+/// Like [`Integer`], the value is stored as raw (two's complement, big-endian) bytes and is
+/// borrowed when parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{BigEnumerated, FromDer};
+///
+/// let bytes = &[0x0a, 0x09, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+/// let (_, e) = BigEnumerated::from_der(bytes).expect("parsing failed");
+///
+/// assert_eq!(e.as_u64(), Ok(u64::MAX));
+/// assert!(e.as_i64().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigEnumerated<'a> {
+    pub(crate) data: Cow<'a, [u8]>,
+}
+
+impl<'a> BigEnumerated<'a> {
+    /// Creates a new `BigEnumerated` containing the given value (borrowed).
+    #[inline]
+    pub const fn new(s: &'a [u8]) -> Self {
+        BigEnumerated {
+            data: Cow::Borrowed(s),
+        }
+    }
+
+    /// Returns the value as an `Integer` (borrowed)
+    #[inline]
+    pub fn as_integer(&self) -> Integer<'_> {
+        Integer::new(&self.data)
+    }
+
+    /// Attempts to convert the value to an `i64`.
+    ///
+    /// This function returns an `IntegerTooLarge` error if the value will not fit into the output type.
+    pub fn as_i64(&self) -> Result<i64> {
+        decode_i64(&self.data)
+    }
+
+    /// Attempts to convert the value to an `u64`.
+    ///
+    /// This function returns an `IntegerTooLarge` error if the value will not fit into the output type,
+    /// or an `IntegerNegative` error if the value is negative.
+    pub fn as_u64(&self) -> Result<u64> {
+        self.as_integer().as_u64()
+    }
+
+    /// Returns a `BigInt` built from this value.
+    #[cfg(feature = "bigint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+    pub fn as_bigint(&self) -> BigInt {
+        self.as_integer().as_bigint()
+    }
+}
+
+impl<'a> AsRef<[u8]> for BigEnumerated<'a> {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl From<Enumerated> for BigEnumerated<'_> {
+    fn from(e: Enumerated) -> Self {
+        BigEnumerated {
+            data: Cow::Owned(encode_i64(e.0)),
+        }
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for BigEnumerated<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<BigEnumerated<'a>> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for BigEnumerated<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<BigEnumerated<'a>> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_primitive()?;
+        // X.690 section 8.4: the encoding has at least one octet
+        if any.data.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        Ok(BigEnumerated::new(any.data))
+    }
+}
+
+impl CheckDerConstraints for BigEnumerated<'_> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        check_der_int_constraints(any)
+    }
+}
+
+impl DerAutoDerive for BigEnumerated<'_> {}
+
+impl CheckCerConstraints for BigEnumerated<'_> {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for BigEnumerated<'_> {}
+
+#[cfg(feature = "std")]
+impl ToCer for BigEnumerated<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
+impl Tagged for BigEnumerated<'_> {
+    const TAG: Tag = Tag::Enumerated;
+}
+
+#[cfg(feature = "std")]
+impl ToDer for BigEnumerated<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = self.data.len();
+        if sz < 127 {
+            // 1 (class+tag) + 1 (length) + len
+            Ok(2 + sz)
+        } else {
+            // 1 (class+tag) + n (length) + len
+            let n = Length::Definite(sz).to_der_len()?;
+            Ok(1 + n + sz)
+        }
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let header = Header::new(
+            Class::Universal,
+            false,
+            Self::TAG,
+            Length::Definite(self.data.len()),
+        );
+        header.write_der_header(writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let int = Integer::from(self.0);
-        int.write_der_content(writer).map_err(Into::into)
+        writer.write(&self.data).map_err(Into::into)
     }
 }
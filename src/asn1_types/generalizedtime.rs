@@ -7,7 +7,7 @@ use core::fmt;
 #[cfg(feature = "datetime")]
 use time::OffsetDateTime;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GeneralizedTime(pub ASN1DateTime);
 
 impl GeneralizedTime {
@@ -244,10 +244,35 @@ impl<'a> CheckDerConstraints for GeneralizedTime {
 
 impl DerAutoDerive for GeneralizedTime {}
 
+impl CheckCerConstraints for GeneralizedTime {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for GeneralizedTime {}
+
+#[cfg(feature = "std")]
+impl ToCer for GeneralizedTime {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl<'a> Tagged for GeneralizedTime {
     const TAG: Tag = Tag::GeneralizedTime;
 }
 
+/// Return the fraction of seconds in DER (X.690 section 11.7.3): decimal point followed by the
+/// milliseconds without trailing zeroes, or nothing if zero
+#[cfg(feature = "std")]
+fn der_fraction(millisecond: Option<u16>) -> String {
+    match millisecond {
+        None | Some(0) => String::new(),
+        Some(v) => format!(".{:03}", v).trim_end_matches('0').to_string(),
+    }
+}
+
 #[cfg(feature = "std")]
 impl ToDer for GeneralizedTime {
     fn to_der_len(&self) -> Result<usize> {
@@ -261,30 +286,21 @@ impl ToDer for GeneralizedTime {
         // thus, length will always be on 1 byte (short length) and
         // class+structure+tag also on 1
         //
-        // total: = 1 (class+constructed+tag) + 1 (length) + 13 + fractional
-        let num_digits = match self.0.millisecond {
-            None => 0,
-            Some(v) => 1 + v.to_string().len(),
-        };
-        Ok(15 + num_digits)
+        // total: = 1 (class+constructed+tag) + 1 (length) + 15 + fractional
+        let num_digits = der_fraction(self.0.millisecond).len();
+        Ok(17 + num_digits)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
         // see above for length value
-        let num_digits = match self.0.millisecond {
-            None => 0,
-            Some(v) => 1 + v.to_string().len() as u8,
-        };
+        let num_digits = der_fraction(self.0.millisecond).len() as u8;
         writer
             .write(&[Self::TAG.0 as u8, 15 + num_digits])
             .map_err(Into::into)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let fractional = match self.0.millisecond {
-            None => "".to_string(),
-            Some(v) => format!(".{}", v),
-        };
+        let fractional = der_fraction(self.0.millisecond);
         let num_digits = fractional.len();
         let _ = write!(
             writer,
@@ -115,6 +115,21 @@ macro_rules! impl_int {
 
         impl DerAutoDerive for $int {}
 
+        impl CheckCerConstraints for $int {
+            fn check_cer_constraints(any: &Any) -> Result<()> {
+                Self::check_constraints(any)
+            }
+        }
+
+        impl CerAutoDerive for $int {}
+
+        #[cfg(feature = "std")]
+        impl ToCer for $int {
+            fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+                self.write_der(writer)
+            }
+        }
+
         impl Tagged for $int {
             const TAG: Tag = Tag::Integer;
         }
@@ -171,6 +186,21 @@ macro_rules! impl_uint {
 
         impl DerAutoDerive for $ty {}
 
+        impl CheckCerConstraints for $ty {
+            fn check_cer_constraints(any: &Any) -> Result<()> {
+                Self::check_constraints(any)
+            }
+        }
+
+        impl CerAutoDerive for $ty {}
+
+        #[cfg(feature = "std")]
+        impl ToCer for $ty {
+            fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+                self.write_der(writer)
+            }
+        }
+
         impl Tagged for $ty {
             const TAG: Tag = Tag::Integer;
         }
@@ -237,7 +267,7 @@ impl_int!(u128 => i128);
 /// assert_eq!(i.as_ref(), &[4]);
 /// // signed
 /// let j = Integer::from(-2);
-/// assert_eq!(j.as_ref(), &[0x0, 0xff, 0xff, 0xff, 0xfe]);
+/// assert_eq!(j.as_ref(), &[0xfe]);
 /// ```
 ///
 /// Converting an `Integer` to a primitive type (using the `TryInto` trait)
@@ -268,7 +298,7 @@ impl_int!(u128 => i128);
 /// let v = 4.to_der_vec().unwrap();
 /// assert_eq!(&v, &[2, 1, 4]);
 /// ```
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Integer<'a> {
     pub(crate) data: Cow<'a, [u8]>,
 }
@@ -306,7 +336,7 @@ impl<'a> Integer<'a> {
         }
     }
 
-    /// Build an `Integer` from a constant array of bytes representation of an integer.
+    /// Build an `Integer` from a constant array of bytes representation of an unsigned integer.
     pub fn from_const_array<const N: usize>(b: [u8; N]) -> Self {
         let mut idx = 0;
         // skip leading 0s
@@ -321,6 +351,13 @@ impl<'a> Integer<'a> {
             Integer {
                 data: Cow::Borrowed(&[0]),
             }
+        } else if b[idx] & 0x80 != 0 {
+            // the value is positive: add a leading 0 to clear the sign bit
+            let mut out = vec![0];
+            out.extend_from_slice(&b[idx..]);
+            Integer {
+                data: Cow::Owned(out),
+            }
         } else {
             Integer {
                 data: Cow::Owned(b[idx..].to_vec()),
@@ -329,8 +366,12 @@ impl<'a> Integer<'a> {
     }
 
     fn from_const_array_negative<const N: usize>(b: [u8; N]) -> Self {
-        let mut out = vec![0];
-        out.extend_from_slice(&b);
+        // remove leading 0xff bytes, as long as the value stays negative
+        let mut idx = 0;
+        while idx + 1 < N && b[idx] == 0xff && b[idx + 1] & 0x80 != 0 {
+            idx += 1;
+        }
+        let out = b[idx..].to_vec();
 
         Integer {
             data: Cow::Owned(out),
@@ -449,7 +490,7 @@ impl<'a> CheckDerConstraints for Integer<'a> {
     }
 }
 
-fn check_der_int_constraints(any: &Any) -> Result<()> {
+pub(crate) fn check_der_int_constraints(any: &Any) -> Result<()> {
     any.header.assert_primitive()?;
     any.header.length.assert_definite()?;
     match any.as_bytes() {
@@ -469,6 +510,21 @@ fn check_der_int_constraints(any: &Any) -> Result<()> {
 
 impl DerAutoDerive for Integer<'_> {}
 
+impl CheckCerConstraints for Integer<'_> {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for Integer<'_> {}
+
+#[cfg(feature = "std")]
+impl ToCer for Integer<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl<'a> Tagged for Integer<'a> {
     const TAG: Tag = Tag::Integer;
 }
@@ -2,6 +2,7 @@ mod any;
 mod bitstring;
 mod boolean;
 mod choice;
+mod constrained_int;
 mod embedded_pdv;
 mod end_of_content;
 mod enumerated;
@@ -15,12 +16,14 @@ mod optional;
 mod real;
 mod sequence;
 mod set;
+mod size_constrained;
 mod strings;
 mod tagged;
 mod utctime;
 
 pub use {
-    any::*, bitstring::*, boolean::*, choice::*, embedded_pdv::*, end_of_content::*, enumerated::*,
-    generalizedtime::*, integer::*, null::*, object_descriptor::*, octetstring::*, oid::*,
-    optional::*, real::*, sequence::*, set::*, strings::*, tagged::*, utctime::*,
+    any::*, bitstring::*, boolean::*, choice::*, constrained_int::*, embedded_pdv::*,
+    end_of_content::*, enumerated::*, generalizedtime::*, integer::*, null::*,
+    object_descriptor::*, octetstring::*, oid::*, optional::*, real::*, sequence::*, set::*,
+    size_constrained::*, strings::*, tagged::*, utctime::*,
 };
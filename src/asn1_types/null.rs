@@ -39,6 +39,21 @@ impl CheckDerConstraints for Null {
 
 impl DerAutoDerive for Null {}
 
+impl CheckCerConstraints for Null {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for Null {}
+
+#[cfg(feature = "std")]
+impl ToCer for Null {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl<'a> Tagged for Null {
     const TAG: Tag = Tag::Null;
 }
@@ -79,6 +94,21 @@ impl<'a> CheckDerConstraints for () {
 
 impl DerAutoDerive for () {}
 
+impl CheckCerConstraints for () {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for () {}
+
+#[cfg(feature = "std")]
+impl ToCer for () {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl<'a> Tagged for () {
     const TAG: Tag = Tag::Null;
 }
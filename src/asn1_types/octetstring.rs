@@ -1,3 +1,4 @@
+use crate::cer::*;
 use crate::*;
 use alloc::borrow::Cow;
 use core::convert::TryFrom;
@@ -5,7 +6,7 @@ use core::convert::TryFrom;
 /// ASN.1 `OCTETSTRING` type
 #[derive(Debug, PartialEq, Eq)]
 pub struct OctetString<'a> {
-    data: Cow<'a, [u8]>,
+    pub(crate) data: Cow<'a, [u8]>,
 }
 
 impl<'a> OctetString<'a> {
@@ -53,9 +54,9 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for OctetString<'a> {
 
     fn try_from(any: &'b Any<'a>) -> Result<OctetString<'a>> {
         any.tag().assert_eq(Self::TAG)?;
-        Ok(OctetString {
-            data: Cow::Borrowed(any.data),
-        })
+        // X.690 section 8.7.3: constructed strings are made of segments
+        let data = any.string_content(Tag::OctetString)?;
+        Ok(OctetString { data })
     }
 }
 
@@ -69,6 +70,16 @@ impl<'a> CheckDerConstraints for OctetString<'a> {
 
 impl DerAutoDerive for OctetString<'_> {}
 
+impl<'a> CheckCerConstraints for OctetString<'a> {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        // X.690 section 9.2
+        cer_string_segments(any, Tag::OctetString)?;
+        Ok(())
+    }
+}
+
+impl CerAutoDerive for OctetString<'_> {}
+
 impl<'a> Tagged for OctetString<'a> {
     const TAG: Tag = Tag::OctetString;
 }
@@ -102,6 +113,13 @@ impl ToDer for OctetString<'_> {
     }
 }
 
+#[cfg(feature = "std")]
+impl ToCer for OctetString<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        write_cer_string(writer, Self::TAG, &self.data)
+    }
+}
+
 impl<'a> TryFrom<Any<'a>> for &'a [u8] {
     type Error = Error;
 
@@ -155,3 +173,49 @@ impl ToDer for &'_ [u8] {
         writer.write(self).map_err(Into::into)
     }
 }
+
+/// Fixed-size `OCTET STRING` (`OCTET STRING (SIZE (N))`)
+///
+/// Parsing returns a `SizeConstraintFailed` error if the content length is not exactly `N`.
+impl<'a, const N: usize> TryFrom<Any<'a>> for [u8; N] {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<[u8; N]> {
+        any.tag().assert_eq(Self::TAG)?;
+        let s = OctetString::try_from(any)?;
+        <[u8; N]>::try_from(s.as_ref()).map_err(|_| Error::SizeConstraintFailed {
+            min: N,
+            max: N,
+            actual: s.as_ref().len(),
+        })
+    }
+}
+
+impl<const N: usize> CheckDerConstraints for [u8; N] {
+    fn check_constraints(any: &Any) -> Result<()> {
+        // X.690 section 10.2
+        any.header.assert_primitive()?;
+        Ok(())
+    }
+}
+
+impl<const N: usize> DerAutoDerive for [u8; N] {}
+
+impl<const N: usize> Tagged for [u8; N] {
+    const TAG: Tag = Tag::OctetString;
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> ToDer for [u8; N] {
+    fn to_der_len(&self) -> Result<usize> {
+        self.as_ref().to_der_len()
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.as_ref().write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(self).map_err(Into::into)
+    }
+}
@@ -12,6 +12,11 @@ use core::{
 use num_bigint::BigUint;
 use num_traits::Num;
 
+#[cfg(feature = "registry")]
+mod registry;
+#[cfg(feature = "registry")]
+pub use registry::*;
+
 /// An error for OID parsing functions.
 #[derive(Debug)]
 pub enum OidParseError {
@@ -21,6 +26,8 @@ pub enum OidParseError {
     /// The second component must be less than 40.
     FirstComponentsTooLarge,
     ParseIntError,
+    /// A relative OID was expected, but an absolute OID was provided.
+    RelativeOidExpected,
 }
 
 /// Object ID (OID) representation which can be relative or non-relative.
@@ -31,8 +38,10 @@ pub enum OidParseError {
 /// This library contains a procedural macro `oid` which can be used to
 /// create oids. For example `oid!(1.2.44.233)` or `oid!(rel 44.233)`
 /// for relative oids. See the [module documentation](index.html) for more information.
+///
+/// OIDs are ordered arc by arc (relative OIDs are ordered after absolute OIDs), so for example
+/// `1.2.9` is lower than `1.2.10`, and an OID is greater than all its prefixes.
 #[derive(Hash, PartialEq, Eq, Clone)]
-
 pub struct Oid<'a> {
     asn1: Cow<'a, [u8]>,
     relative: bool,
@@ -50,7 +59,7 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for Oid<'a> {
     type Error = Error;
 
     fn try_from(any: &'b Any<'a>) -> Result<Self> {
-        // check that any.data.last().unwrap() >> 7 == 0u8
+        check_sub_identifiers(any.data)?;
         let asn1 = Cow::Borrowed(any.data);
         Ok(Oid::new(asn1))
     }
@@ -60,12 +69,48 @@ impl<'a> CheckDerConstraints for Oid<'a> {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.assert_primitive()?;
         any.header.length.assert_definite()?;
-        Ok(())
+        check_sub_identifiers(any.data)
     }
 }
 
+/// Check the encoding of sub-identifiers
+///
+/// Content must not be empty, and each sub-identifier must be encoded using the minimum
+/// number of bytes (no leading `0x80`) and must be terminated.
+fn check_sub_identifiers(bytes: &[u8]) -> Result<()> {
+    if bytes.is_empty() {
+        return Err(Error::OidEmpty);
+    }
+    let mut first_byte = true;
+    for &b in bytes {
+        if first_byte && b == 0x80 {
+            return Err(Error::OidArcNotMinimal);
+        }
+        first_byte = (b >> 7) == 0u8;
+    }
+    if !first_byte {
+        return Err(Error::OidArcTruncated);
+    }
+    Ok(())
+}
+
 impl DerAutoDerive for Oid<'_> {}
 
+impl CheckCerConstraints for Oid<'_> {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for Oid<'_> {}
+
+#[cfg(feature = "std")]
+impl ToCer for Oid<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl<'a> Tagged for Oid<'a> {
     const TAG: Tag = Tag::Oid;
 }
@@ -242,6 +287,7 @@ impl<'a> Oid<'a> {
             oid: self,
             pos: 0,
             first: false,
+            second: None,
             n: PhantomData,
         }
     }
@@ -251,15 +297,10 @@ impl<'a> Oid<'a> {
     pub fn iter(
         &'_ self,
     ) -> Option<impl Iterator<Item = u64> + FusedIterator + ExactSizeIterator + '_> {
-        // Check that every arc fits into u64
-        let bytes = if self.relative {
-            &self.asn1
-        } else if self.asn1.is_empty() {
-            &[]
-        } else {
-            &self.asn1[1..]
-        };
-        let max_bits = bytes
+        // Check that every arc fits into u64 (the first sub-identifier of absolute OIDs is
+        // greater than its second arc, so checking it is enough)
+        let max_bits = self
+            .asn1
             .iter()
             .fold((0usize, 0usize), |(max, cur), c| {
                 let is_end = (c >> 7) == 0u8;
@@ -278,6 +319,7 @@ impl<'a> Oid<'a> {
             oid: self,
             pos: 0,
             first: false,
+            second: None,
             n: PhantomData,
         })
     }
@@ -286,6 +328,7 @@ impl<'a> Oid<'a> {
         let (rem, any) = Any::from_ber(bytes)?;
         any.header.assert_primitive()?;
         any.header.assert_tag(Tag::RelativeOid)?;
+        check_sub_identifiers(any.data)?;
         let asn1 = Cow::Borrowed(any.data);
         Ok((rem, Oid::new_relative(asn1)))
     }
@@ -297,41 +340,195 @@ impl<'a> Oid<'a> {
         let asn1 = Cow::Borrowed(any.data);
         Ok((rem, Oid::new_relative(asn1)))
     }
+
+    /// Parse a BER-encoded OID, without checking the encoding of sub-identifiers.
+    ///
+    /// Unlike [`FromBer::from_ber`], this function accepts empty OIDs, sub-identifiers with
+    /// leading `0x80` bytes, or a truncated last sub-identifier. This can be used to read data
+    /// produced by non-conforming encoders, but arcs may be decoded incorrectly.
+    pub fn from_ber_lenient(bytes: &'a [u8]) -> ParseResult<'a, Self> {
+        let (rem, any) = Any::from_ber(bytes)?;
+        any.header.assert_primitive()?;
+        any.header.assert_tag(Tag::Oid)?;
+        let asn1 = Cow::Borrowed(any.data);
+        Ok((rem, Oid::new(asn1)))
+    }
+
+    /// Return an iterator over the arcs (sub-identifiers).
+    ///
+    /// Contrary to [`Oid::iter`], this function always returns an iterator: arcs which do not
+    /// fit into `u64` are returned as `None`.
+    ///
+    /// ```rust
+    /// use asn1_rs::oid;
+    ///
+    /// let oid = oid!(1.3.6.1.4.1.311.21.20);
+    /// assert_eq!(oid.arcs().len(), 9);
+    /// assert_eq!(oid.arcs().nth(6), Some(Some(311)));
+    /// ```
+    pub fn arcs(
+        &self,
+    ) -> impl Iterator<Item = Option<u64>> + FusedIterator + ExactSizeIterator + '_ {
+        ArcIterator {
+            oid: self,
+            pos: 0,
+            first: false,
+            second: None,
+        }
+    }
+
+    /// Return the number of arcs (sub-identifiers) of the OID.
+    pub fn len(&self) -> usize {
+        let terminal_bytes = self.asn1.iter().filter(|o| (*o >> 7) == 0u8).count();
+        if self.relative || self.asn1.is_empty() {
+            terminal_bytes
+        } else if self.asn1.as_ref() == [0] {
+            // special case: `0`
+            1
+        } else {
+            // first sub-identifier encodes two arcs
+            terminal_bytes + 1
+        }
+    }
+
+    /// Return `true` if the OID has no arcs.
+    pub fn is_empty(&self) -> bool {
+        self.asn1.is_empty()
+    }
+
+    /// Return an iterator over the encoded sub-identifiers.
+    ///
+    /// For absolute OIDs, the first sub-identifier contains the first two arcs.
+    fn sub_identifiers(&self) -> impl Iterator<Item = &[u8]> {
+        self.asn1.split_inclusive(|o| (o >> 7) == 0u8)
+    }
+
+    /// Test if `prefix` is a prefix of this OID (arc-wise).
+    ///
+    /// An OID is a prefix of itself. Absolute and relative OIDs never match.
+    ///
+    /// ```rust
+    /// use asn1_rs::oid;
+    ///
+    /// let oid = oid!(1.3.6.1.4.1.311.21.20);
+    /// assert!(oid.starts_with(&oid!(1.3.6.1.4.1)));
+    /// assert!(!oid.starts_with(&oid!(1.3.6.1.4.13)));
+    /// ```
+    pub fn starts_with(&self, prefix: &Oid) -> bool {
+        // sub-identifiers are encoded independently and end with a terminal byte, so comparing
+        // the encoded forms is equivalent to comparing arcs
+        self.relative == prefix.relative && self.asn1.starts_with(&prefix.asn1)
+    }
+
+    /// Return the parent of this OID (the OID without its last arc), borrowing the data.
+    ///
+    /// Returns `None` if the OID has no parent that can be represented: a relative OID with one
+    /// arc, or an absolute OID with less than three arcs.
+    pub fn parent(&self) -> Option<Oid<'_>> {
+        // position of the last sub-identifier
+        let pos = self.asn1[..self.asn1.len().saturating_sub(1)]
+            .iter()
+            .rposition(|o| (o >> 7) == 0u8)?
+            + 1;
+        let asn1 = Cow::Borrowed(&self.asn1[..pos]);
+        Some(Oid {
+            asn1,
+            relative: self.relative,
+        })
+    }
+
+    /// Build a new OID by appending `arc` to this OID.
+    ///
+    /// This method allocates memory on the heap.
+    pub fn child(&self, arc: u64) -> Oid<'static> {
+        let asn1: Vec<u8> = self
+            .asn1
+            .iter()
+            .copied()
+            .chain(encode_relative(&[arc]))
+            .collect();
+        Oid {
+            asn1: Cow::from(asn1),
+            relative: self.relative,
+        }
+    }
+
+    /// Build a new OID by appending the arcs of the relative OID `suffix` to this OID.
+    ///
+    /// Returns an error if `suffix` is not a relative OID.
+    /// This method allocates memory on the heap.
+    pub fn join(&self, suffix: &Oid) -> core::result::Result<Oid<'static>, OidParseError> {
+        if !suffix.relative {
+            return Err(OidParseError::RelativeOidExpected);
+        }
+        let mut asn1 = Vec::with_capacity(self.asn1.len() + suffix.asn1.len());
+        asn1.extend_from_slice(&self.asn1);
+        asn1.extend_from_slice(&suffix.asn1);
+        Ok(Oid {
+            asn1: Cow::from(asn1),
+            relative: self.relative,
+        })
+    }
+
+    /// Remove `prefix` from this OID, and return the remaining arcs as a relative OID
+    /// (borrowing the data).
+    ///
+    /// Returns `None` if `prefix` is not a prefix of this OID, or if it is equal to this OID.
+    ///
+    /// ```rust
+    /// use asn1_rs::oid;
+    ///
+    /// let oid = oid!(1.3.6.1.4.1.311.21.20);
+    /// let suffix = oid.strip_prefix(&oid!(1.3.6.1.4.1)).unwrap();
+    /// assert_eq!(suffix, oid!(rel 311.21.20));
+    /// ```
+    pub fn strip_prefix(&self, prefix: &Oid) -> Option<Oid<'_>> {
+        if !self.starts_with(prefix) || self.asn1.len() == prefix.asn1.len() {
+            return None;
+        }
+        let asn1 = Cow::Borrowed(&self.asn1[prefix.asn1.len()..]);
+        Some(Oid::new_relative(asn1))
+    }
 }
 
-trait Repr: Num + Shl<usize, Output = Self> + From<u8> {}
-impl<N> Repr for N where N: Num + Shl<usize, Output = N> + From<u8> {}
+impl PartialOrd for Oid<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Oid<'_> {
+    /// Compare OIDs arc by arc
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        // sub-identifiers are encoded with the minimum number of bytes, so a longer encoding
+        // is a greater value, and values with the same length compare as bytes
+        fn key(o: &[u8]) -> (usize, &[u8]) {
+            (o.len(), o)
+        }
+        self.relative.cmp(&other.relative).then_with(|| {
+            self.sub_identifiers()
+                .map(key)
+                .cmp(other.sub_identifiers().map(key))
+        })
+    }
+}
+
+trait Repr: Num + Shl<usize, Output = Self> + From<u8> + PartialOrd {}
+impl<N> Repr for N where N: Num + Shl<usize, Output = N> + From<u8> + PartialOrd {}
 
 struct SubIdentifierIterator<'a, N: Repr> {
     oid: &'a Oid<'a>,
     pos: usize,
     first: bool,
+    second: Option<N>,
     n: PhantomData<&'a N>,
 }
 
-impl<'a, N: Repr> Iterator for SubIdentifierIterator<'a, N> {
-    type Item = N;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl<'a, N: Repr> SubIdentifierIterator<'a, N> {
+    // decode objet sub-identifier according to the asn.1 standard
+    fn decode(&mut self) -> N {
         use num_traits::identities::Zero;
 
-        if self.pos == self.oid.asn1.len() {
-            return None;
-        }
-        if !self.oid.relative {
-            if !self.first {
-                debug_assert!(self.pos == 0);
-                self.first = true;
-                return Some((self.oid.asn1[0] / 40).into());
-            } else if self.pos == 0 {
-                self.pos += 1;
-                if self.oid.asn1[0] == 0 && self.oid.asn1.len() == 1 {
-                    return None;
-                }
-                return Some((self.oid.asn1[0] % 40).into());
-            }
-        }
-        // decode objet sub-identifier according to the asn.1 standard
         let mut res = <N as Zero>::zero();
         for o in self.oid.asn1[self.pos..].iter() {
             self.pos += 1;
@@ -341,40 +538,141 @@ impl<'a, N: Repr> Iterator for SubIdentifierIterator<'a, N> {
                 break;
             }
         }
-        Some(res)
+        res
     }
 }
 
-impl<'a, N: Repr> FusedIterator for SubIdentifierIterator<'a, N> {}
+impl<'a, N: Repr> Iterator for SubIdentifierIterator<'a, N> {
+    type Item = N;
 
-impl<'a, N: Repr> ExactSizeIterator for SubIdentifierIterator<'a, N> {
-    fn len(&self) -> usize {
-        if self.oid.relative {
-            self.oid.asn1.iter().filter(|o| (*o >> 7) == 0u8).count()
-        } else if self.oid.asn1.len() == 0 {
-            0
-        } else if self.oid.asn1.len() == 1 {
-            if self.oid.asn1[0] == 0 {
-                1
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(second) = self.second.take() {
+            return Some(second);
+        }
+        if self.pos == self.oid.asn1.len() {
+            return None;
+        }
+        if !self.oid.relative && !self.first {
+            debug_assert!(self.pos == 0);
+            self.first = true;
+            // the first sub-identifier encodes the first two arcs as `40 * X + Y`, with
+            // `Y < 40` if `X < 2`
+            let res = self.decode();
+            let (x, y) = if res < N::from(40) {
+                (0, res)
+            } else if res < N::from(80) {
+                (1, res - N::from(40))
             } else {
-                2
+                (2, res - N::from(80))
+            };
+            // special case: `0`
+            if self.oid.asn1.as_ref() != [0] {
+                self.second = Some(y);
             }
-        } else {
-            2 + self.oid.asn1[2..]
-                .iter()
-                .filter(|o| (*o >> 7) == 0u8)
-                .count()
+            return Some(N::from(x));
         }
+        Some(self.decode())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = remaining_arcs(self.oid, self.pos, self.first) + self.second.is_some() as usize;
+        (n, Some(n))
     }
+}
+
+impl<'a, N: Repr> FusedIterator for SubIdentifierIterator<'a, N> {}
 
+impl<'a, N: Repr> ExactSizeIterator for SubIdentifierIterator<'a, N> {
     #[cfg(feature = "exact_size_is_empty")]
     fn is_empty(&self) -> bool {
-        self.oid.asn1.is_empty()
+        self.len() == 0
     }
 }
 
+/// Iterator over arcs, returning `None` for arcs which do not fit into `u64`
+struct ArcIterator<'a> {
+    oid: &'a Oid<'a>,
+    pos: usize,
+    first: bool,
+    second: Option<Option<u64>>,
+}
+
+impl<'a> ArcIterator<'a> {
+    fn decode(&mut self) -> Option<u64> {
+        let mut res = Some(0u64);
+        for o in self.oid.asn1[self.pos..].iter() {
+            self.pos += 1;
+            res = res
+                .filter(|r| r.leading_zeros() >= 7)
+                .map(|r| (r << 7) | u64::from(o & 0b111_1111));
+            if (o >> 7) == 0u8 {
+                break;
+            }
+        }
+        res
+    }
+}
+
+impl<'a> Iterator for ArcIterator<'a> {
+    type Item = Option<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(second) = self.second.take() {
+            return Some(second);
+        }
+        if self.pos == self.oid.asn1.len() {
+            return None;
+        }
+        if !self.oid.relative && !self.first {
+            self.first = true;
+            // the first sub-identifier encodes the first two arcs as `40 * X + Y`, with
+            // `Y < 40` if `X < 2`. If it does not fit into `u64`, `X` is 2.
+            let (x, y) = match self.decode() {
+                Some(res) if res < 80 => (res / 40, Some(res % 40)),
+                res => (2, res.map(|r| r - 80)),
+            };
+            // special case: `0`
+            if self.oid.asn1.as_ref() != [0] {
+                self.second = Some(y);
+            }
+            return Some(Some(x));
+        }
+        Some(self.decode())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = remaining_arcs(self.oid, self.pos, self.first) + self.second.is_some() as usize;
+        (n, Some(n))
+    }
+}
+
+impl<'a> FusedIterator for ArcIterator<'a> {}
+
+impl<'a> ExactSizeIterator for ArcIterator<'a> {}
+
+/// Return the number of arcs not yet decoded by an iterator at position `pos`
+///
+/// `first` is true if the first sub-identifier (encoding two arcs for absolute OIDs) has been
+/// decoded.
+fn remaining_arcs(oid: &Oid, pos: usize, first: bool) -> usize {
+    if oid.relative || first {
+        return oid.asn1[pos..].iter().filter(|o| (*o >> 7) == 0u8).count();
+    }
+    oid.len()
+}
+
+/// Display the OID using the dotted notation
+///
+/// When the `registry` feature is enabled, the alternate form (`{:#}`) also shows the name of
+/// the OID, if known (for ex. `commonName (2.5.4.3)`).
 impl<'a> fmt::Display for Oid<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(feature = "registry")]
+        if f.alternate() {
+            if let Some(name) = self.name() {
+                return write!(f, "{} ({})", name, self.to_id_string());
+            }
+        }
         if self.relative {
             f.write_str("rel. ")?;
         }
@@ -385,7 +683,10 @@ impl<'a> fmt::Display for Oid<'a> {
 impl<'a> fmt::Debug for Oid<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str("OID(")?;
-        <Oid as fmt::Display>::fmt(self, f)?;
+        if self.relative {
+            f.write_str("rel. ")?;
+        }
+        f.write_str(&self.to_id_string())?;
         f.write_str(")")
     }
 }
@@ -482,6 +783,107 @@ mod tests {
         }
     }
 
+    #[rustfmt::skip::macros(oid)]
+    #[test]
+    fn oid_arcs() {
+        assert_eq!(oid!(1.2.3).len(), 3);
+        assert_eq!(oid!(1.2.840.113549).len(), 4);
+        assert_eq!(oid!(rel 840.113549).len(), 2);
+        assert_eq!(oid!(1.2.3).iter().unwrap().len(), 3);
+        assert_eq!(oid!(0).iter().unwrap().len(), 1);
+        assert_eq!(oid!(1.2.3).arcs().len(), 3);
+        assert_eq!(oid!(rel 840.113549).arcs().len(), 2);
+        let oid = oid!(1.2.840.113549);
+        let mut arcs = oid.arcs();
+        assert_eq!(arcs.next(), Some(Some(1)));
+        assert_eq!(arcs.len(), 3);
+        assert_eq!(arcs.next(), Some(Some(2)));
+        assert_eq!(arcs.len(), 2);
+        assert_eq!(arcs.collect::<Vec<_>>(), [Some(840), Some(113_549)]);
+        // arcs which do not fit into u64
+        let oid = Oid::new_relative(std::borrow::Cow::Borrowed(&[
+            0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00, 0x05,
+        ]));
+        assert!(oid.iter().is_none());
+        assert_eq!(oid.arcs().collect::<Vec<_>>(), [None, Some(5)]);
+        let oid = Oid::new_relative(std::borrow::Cow::Borrowed(&[
+            0x81, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00,
+        ]));
+        assert_eq!(oid.arcs().next(), Some(Some(1 << 63)));
+        // first sub-identifier encoded on several bytes
+        let oid = Oid::new(std::borrow::Cow::Borrowed(&[0x81, 0x34, 0x03]));
+        assert_eq!(oid.len(), 3);
+        let mut arcs = oid.arcs();
+        assert_eq!(arcs.len(), 3);
+        assert_eq!(arcs.next(), Some(Some(2)));
+        assert_eq!(arcs.len(), 2);
+        assert_eq!(arcs.collect::<Vec<_>>(), [Some(100), Some(3)]);
+        let mut iter = oid.iter().unwrap();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.collect::<Vec<_>>(), [100, 3]);
+        assert_eq!(oid.to_id_string(), "2.100.3");
+        assert_eq!(oid!(0).arcs().collect::<Vec<_>>(), [Some(0)]);
+    }
+
+    #[rustfmt::skip::macros(oid)]
+    #[test]
+    fn oid_prefix() {
+        let oid = oid!(1.3.6.1.4.1.311.21.20);
+        assert!(oid.starts_with(&oid));
+        assert!(oid.starts_with(&oid!(1.3.6.1.4.1.311)));
+        assert!(!oid.starts_with(&oid!(1.3.6.1.4.1.3)));
+        assert!(!oid.starts_with(&oid!(rel 1.3)));
+        assert_eq!(
+            oid.strip_prefix(&oid!(1.3.6.1.4.1)),
+            Some(oid!(rel 311.21.20))
+        );
+        assert_eq!(oid.strip_prefix(&oid), None);
+        assert_eq!(oid.strip_prefix(&oid!(1.3.6.1.4.2)), None);
+    }
+
+    #[rustfmt::skip::macros(oid)]
+    #[test]
+    fn oid_parent_child() {
+        let oid = oid!(1.2.840.113549);
+        assert_eq!(oid.parent(), Some(oid!(1.2.840)));
+        assert_eq!(oid!(1.2.840).parent(), Some(oid!(1.2)));
+        assert_eq!(oid!(1.2).parent(), None);
+        assert_eq!(oid!(rel 5.6).parent(), Some(oid!(rel 5)));
+        assert_eq!(oid!(rel 5).parent(), None);
+        assert_eq!(oid!(1.2.840).child(113_549), oid);
+        assert_eq!(oid!(1.2).join(&oid!(rel 840.113549)).unwrap(), oid);
+        assert!(oid!(1.2).join(&oid!(1.2)).is_err());
+    }
+
+    #[rustfmt::skip::macros(oid)]
+    #[test]
+    fn oid_ord() {
+        let mut oids = vec![
+            oid!(rel 1.2),
+            oid!(1.2.10),
+            oid!(1.2.9),
+            oid!(1.2.840.113549),
+            oid!(2.5),
+            oid!(1.2),
+            oid!(1.2.128),
+        ];
+        oids.sort();
+        assert_eq!(
+            oids,
+            vec![
+                oid!(1.2),
+                oid!(1.2.9),
+                oid!(1.2.10),
+                oid!(1.2.128),
+                oid!(1.2.840.113549),
+                oid!(2.5),
+                oid!(rel 1.2),
+            ]
+        );
+    }
+
     #[rustfmt::skip::macros(oid)]
     #[test]
     fn test_compare_oid() {
@@ -0,0 +1,618 @@
+//! Registry of well-known OIDs
+
+use crate::{oid, Oid};
+
+/// An entry of the OID registry
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct OidEntry {
+    name: &'static str,
+    description: &'static str,
+    oid: Oid<'static>,
+}
+
+impl OidEntry {
+    const fn new(name: &'static str, description: &'static str, oid: Oid<'static>) -> Self {
+        OidEntry {
+            name,
+            description,
+            oid,
+        }
+    }
+
+    /// Return the short name (the ASN.1 identifier, for ex. `commonName`)
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Return a human-readable description
+    #[inline]
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Return the OID
+    #[inline]
+    pub const fn oid(&self) -> &Oid<'static> {
+        &self.oid
+    }
+}
+
+/// Static registry of well-known OIDs
+///
+/// The registry contains OIDs from PKIX (X.509 attributes and extensions), PKCS #1, #5, #7,
+/// #9 and #12, X9.62 and SECG (elliptic curves), NIST (hash and encryption algorithms),
+/// Kerberos and LDAP.
+///
+/// The table is built at compile-time, and entries are sorted by OID.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{oid, OidRegistry};
+///
+/// let entry = OidRegistry::get(&oid!(2.5.4.3)).expect("unknown OID");
+/// assert_eq!(entry.name(), "commonName");
+///
+/// let entry = OidRegistry::get_by_name("sha256WithRSAEncryption").expect("unknown name");
+/// assert_eq!(entry.oid(), &oid!(1.2.840.113549.1.1.11));
+///
+/// // the alternate form of `Display` shows the name
+/// assert_eq!(format!("{:#}", oid!(2.5.4.3)), "commonName (2.5.4.3)");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+#[derive(Debug)]
+pub struct OidRegistry;
+
+impl OidRegistry {
+    /// Look up an OID in the registry
+    pub fn get(oid: &Oid) -> Option<&'static OidEntry> {
+        REGISTRY
+            .binary_search_by(|entry| entry.oid.cmp(oid))
+            .ok()
+            .map(|idx| &REGISTRY[idx])
+    }
+
+    /// Look up an OID in the registry, using its short name (case-sensitive)
+    pub fn get_by_name(name: &str) -> Option<&'static OidEntry> {
+        REGISTRY.iter().find(|entry| entry.name == name)
+    }
+
+    /// Return an iterator over all registry entries, sorted by OID
+    pub fn iter() -> impl Iterator<Item = &'static OidEntry> {
+        REGISTRY.iter()
+    }
+}
+
+impl Oid<'_> {
+    /// Return the short name of the OID, if present in the [`OidRegistry`]
+    #[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+    pub fn name(&self) -> Option<&'static str> {
+        OidRegistry::get(self).map(OidEntry::name)
+    }
+}
+
+// Note: entries must be sorted by OID
+#[rustfmt::skip::macros(oid)]
+static REGISTRY: &[OidEntry] = &[
+    OidEntry::new("uid", "User ID", oid!(0.9.2342.19200300.100.1.1)),
+    OidEntry::new("mail", "RFC 822 Mailbox", oid!(0.9.2342.19200300.100.1.3)),
+    OidEntry::new(
+        "domainComponent",
+        "Domain Component",
+        oid!(0.9.2342.19200300.100.1.25),
+    ),
+    OidEntry::new("dsa", "DSA", oid!(1.2.840.10040.4.1)),
+    OidEntry::new("dsa-with-sha1", "DSA with SHA-1", oid!(1.2.840.10040.4.3)),
+    OidEntry::new(
+        "ecPublicKey",
+        "Elliptic curve public key",
+        oid!(1.2.840.10045.2.1),
+    ),
+    OidEntry::new("prime192v1", "NIST P-192 curve", oid!(1.2.840.10045.3.1.1)),
+    OidEntry::new("prime256v1", "NIST P-256 curve", oid!(1.2.840.10045.3.1.7)),
+    OidEntry::new(
+        "ecdsa-with-SHA1",
+        "ECDSA with SHA-1",
+        oid!(1.2.840.10045.4.1),
+    ),
+    OidEntry::new(
+        "ecdsa-with-SHA224",
+        "ECDSA with SHA-224",
+        oid!(1.2.840.10045.4.3.1),
+    ),
+    OidEntry::new(
+        "ecdsa-with-SHA256",
+        "ECDSA with SHA-256",
+        oid!(1.2.840.10045.4.3.2),
+    ),
+    OidEntry::new(
+        "ecdsa-with-SHA384",
+        "ECDSA with SHA-384",
+        oid!(1.2.840.10045.4.3.3),
+    ),
+    OidEntry::new(
+        "ecdsa-with-SHA512",
+        "ECDSA with SHA-512",
+        oid!(1.2.840.10045.4.3.4),
+    ),
+    OidEntry::new(
+        "msKerberosV5",
+        "Microsoft Kerberos V5 GSS-API mechanism",
+        oid!(1.2.840.48018.1.2.2),
+    ),
+    OidEntry::new("rsadsi", "RSA Data Security, Inc.", oid!(1.2.840.113549)),
+    OidEntry::new("pkcs", "PKCS", oid!(1.2.840.113549.1)),
+    OidEntry::new("pkcs-1", "PKCS #1", oid!(1.2.840.113549.1.1)),
+    OidEntry::new(
+        "rsaEncryption",
+        "RSA encryption",
+        oid!(1.2.840.113549.1.1.1),
+    ),
+    OidEntry::new(
+        "md2WithRSAEncryption",
+        "MD2 with RSA encryption",
+        oid!(1.2.840.113549.1.1.2),
+    ),
+    OidEntry::new(
+        "md5WithRSAEncryption",
+        "MD5 with RSA encryption",
+        oid!(1.2.840.113549.1.1.4),
+    ),
+    OidEntry::new(
+        "sha1WithRSAEncryption",
+        "SHA-1 with RSA encryption",
+        oid!(1.2.840.113549.1.1.5),
+    ),
+    OidEntry::new("id-RSAES-OAEP", "RSAES-OAEP", oid!(1.2.840.113549.1.1.7)),
+    OidEntry::new("id-mgf1", "MGF1", oid!(1.2.840.113549.1.1.8)),
+    OidEntry::new(
+        "id-pSpecified",
+        "OAEP encoding parameters",
+        oid!(1.2.840.113549.1.1.9),
+    ),
+    OidEntry::new("id-RSASSA-PSS", "RSASSA-PSS", oid!(1.2.840.113549.1.1.10)),
+    OidEntry::new(
+        "sha256WithRSAEncryption",
+        "SHA-256 with RSA encryption",
+        oid!(1.2.840.113549.1.1.11),
+    ),
+    OidEntry::new(
+        "sha384WithRSAEncryption",
+        "SHA-384 with RSA encryption",
+        oid!(1.2.840.113549.1.1.12),
+    ),
+    OidEntry::new(
+        "sha512WithRSAEncryption",
+        "SHA-512 with RSA encryption",
+        oid!(1.2.840.113549.1.1.13),
+    ),
+    OidEntry::new(
+        "sha224WithRSAEncryption",
+        "SHA-224 with RSA encryption",
+        oid!(1.2.840.113549.1.1.14),
+    ),
+    OidEntry::new(
+        "id-PBKDF2",
+        "PBKDF2 key derivation function",
+        oid!(1.2.840.113549.1.5.12),
+    ),
+    OidEntry::new(
+        "id-PBES2",
+        "PBES2 encryption scheme",
+        oid!(1.2.840.113549.1.5.13),
+    ),
+    OidEntry::new("pkcs-7", "PKCS #7", oid!(1.2.840.113549.1.7)),
+    OidEntry::new("data", "PKCS #7 data", oid!(1.2.840.113549.1.7.1)),
+    OidEntry::new(
+        "signedData",
+        "PKCS #7 signed data",
+        oid!(1.2.840.113549.1.7.2),
+    ),
+    OidEntry::new(
+        "envelopedData",
+        "PKCS #7 enveloped data",
+        oid!(1.2.840.113549.1.7.3),
+    ),
+    OidEntry::new(
+        "signedAndEnvelopedData",
+        "PKCS #7 signed and enveloped data",
+        oid!(1.2.840.113549.1.7.4),
+    ),
+    OidEntry::new(
+        "digestedData",
+        "PKCS #7 digested data",
+        oid!(1.2.840.113549.1.7.5),
+    ),
+    OidEntry::new(
+        "encryptedData",
+        "PKCS #7 encrypted data",
+        oid!(1.2.840.113549.1.7.6),
+    ),
+    OidEntry::new("pkcs-9", "PKCS #9", oid!(1.2.840.113549.1.9)),
+    OidEntry::new("emailAddress", "Email address", oid!(1.2.840.113549.1.9.1)),
+    OidEntry::new(
+        "unstructuredName",
+        "Unstructured name",
+        oid!(1.2.840.113549.1.9.2),
+    ),
+    OidEntry::new("contentType", "Content type", oid!(1.2.840.113549.1.9.3)),
+    OidEntry::new(
+        "messageDigest",
+        "Message digest",
+        oid!(1.2.840.113549.1.9.4),
+    ),
+    OidEntry::new("signingTime", "Signing time", oid!(1.2.840.113549.1.9.5)),
+    OidEntry::new(
+        "counterSignature",
+        "Countersignature",
+        oid!(1.2.840.113549.1.9.6),
+    ),
+    OidEntry::new(
+        "challengePassword",
+        "Challenge password",
+        oid!(1.2.840.113549.1.9.7),
+    ),
+    OidEntry::new(
+        "extensionRequest",
+        "Extension request",
+        oid!(1.2.840.113549.1.9.14),
+    ),
+    OidEntry::new(
+        "smimeCapabilities",
+        "S/MIME capabilities",
+        oid!(1.2.840.113549.1.9.15),
+    ),
+    OidEntry::new("friendlyName", "Friendly name", oid!(1.2.840.113549.1.9.20)),
+    OidEntry::new("localKeyID", "Local key ID", oid!(1.2.840.113549.1.9.21)),
+    OidEntry::new(
+        "x509Certificate",
+        "X.509 certificate (PKCS #12 certificate type)",
+        oid!(1.2.840.113549.1.9.22.1),
+    ),
+    OidEntry::new("pkcs-12", "PKCS #12", oid!(1.2.840.113549.1.12)),
+    OidEntry::new(
+        "pbeWithSHAAnd3-KeyTripleDES-CBC",
+        "PBE with SHA-1 and 3-key triple DES CBC",
+        oid!(1.2.840.113549.1.12.1.3),
+    ),
+    OidEntry::new(
+        "pbeWithSHAAnd40BitRC2-CBC",
+        "PBE with SHA-1 and 40-bit RC2 CBC",
+        oid!(1.2.840.113549.1.12.1.6),
+    ),
+    OidEntry::new(
+        "keyBag",
+        "PKCS #12 key bag",
+        oid!(1.2.840.113549.1.12.10.1.1),
+    ),
+    OidEntry::new(
+        "pkcs8ShroudedKeyBag",
+        "PKCS #12 shrouded key bag",
+        oid!(1.2.840.113549.1.12.10.1.2),
+    ),
+    OidEntry::new(
+        "certBag",
+        "PKCS #12 certificate bag",
+        oid!(1.2.840.113549.1.12.10.1.3),
+    ),
+    OidEntry::new(
+        "crlBag",
+        "PKCS #12 CRL bag",
+        oid!(1.2.840.113549.1.12.10.1.4),
+    ),
+    OidEntry::new(
+        "secretBag",
+        "PKCS #12 secret bag",
+        oid!(1.2.840.113549.1.12.10.1.5),
+    ),
+    OidEntry::new(
+        "safeContentsBag",
+        "PKCS #12 safe contents bag",
+        oid!(1.2.840.113549.1.12.10.1.6),
+    ),
+    OidEntry::new("md5", "MD5", oid!(1.2.840.113549.2.5)),
+    OidEntry::new(
+        "hmacWithSHA256",
+        "HMAC with SHA-256",
+        oid!(1.2.840.113549.2.9),
+    ),
+    OidEntry::new(
+        "krb5",
+        "Kerberos V5 GSS-API mechanism",
+        oid!(1.2.840.113554.1.2.2),
+    ),
+    OidEntry::new(
+        "pagedResultsControl",
+        "LDAP simple paged results control",
+        oid!(1.2.840.113556.1.4.319),
+    ),
+    OidEntry::new(
+        "startTLS",
+        "LDAP StartTLS extended operation",
+        oid!(1.3.6.1.4.1.1466.20037),
+    ),
+    OidEntry::new(
+        "passwdModify",
+        "LDAP password modify extended operation",
+        oid!(1.3.6.1.4.1.4203.1.11.1),
+    ),
+    OidEntry::new(
+        "whoami",
+        "LDAP \"Who am I?\" extended operation",
+        oid!(1.3.6.1.4.1.4203.1.11.3),
+    ),
+    OidEntry::new("kerberosv5", "Kerberos V5", oid!(1.3.6.1.5.2)),
+    OidEntry::new(
+        "id-pkinit-san",
+        "Kerberos principal name (PKINIT)",
+        oid!(1.3.6.1.5.2.2),
+    ),
+    OidEntry::new("id-pkinit", "PKINIT", oid!(1.3.6.1.5.2.3)),
+    OidEntry::new(
+        "id-pkinit-authData",
+        "PKINIT signed authentication data",
+        oid!(1.3.6.1.5.2.3.1),
+    ),
+    OidEntry::new(
+        "id-pkinit-DHKeyData",
+        "PKINIT Diffie-Hellman key data",
+        oid!(1.3.6.1.5.2.3.2),
+    ),
+    OidEntry::new(
+        "id-pkinit-rkeyData",
+        "PKINIT reply key data",
+        oid!(1.3.6.1.5.2.3.3),
+    ),
+    OidEntry::new(
+        "id-pkinit-KPClientAuth",
+        "PKINIT client authentication",
+        oid!(1.3.6.1.5.2.3.4),
+    ),
+    OidEntry::new("id-pkinit-KPKdc", "PKINIT KDC", oid!(1.3.6.1.5.2.3.5)),
+    OidEntry::new("id-pkix", "PKIX", oid!(1.3.6.1.5.5.7)),
+    OidEntry::new("id-pe", "PKIX private extensions", oid!(1.3.6.1.5.5.7.1)),
+    OidEntry::new(
+        "authorityInfoAccess",
+        "Authority information access",
+        oid!(1.3.6.1.5.5.7.1.1),
+    ),
+    OidEntry::new(
+        "subjectInfoAccess",
+        "Subject information access",
+        oid!(1.3.6.1.5.5.7.1.11),
+    ),
+    OidEntry::new(
+        "id-qt",
+        "PKIX policy qualifier types",
+        oid!(1.3.6.1.5.5.7.2),
+    ),
+    OidEntry::new(
+        "id-qt-cps",
+        "CPS pointer qualifier",
+        oid!(1.3.6.1.5.5.7.2.1),
+    ),
+    OidEntry::new(
+        "id-qt-unotice",
+        "User notice qualifier",
+        oid!(1.3.6.1.5.5.7.2.2),
+    ),
+    OidEntry::new("id-kp", "PKIX extended key purposes", oid!(1.3.6.1.5.5.7.3)),
+    OidEntry::new(
+        "serverAuth",
+        "TLS web server authentication",
+        oid!(1.3.6.1.5.5.7.3.1),
+    ),
+    OidEntry::new(
+        "clientAuth",
+        "TLS web client authentication",
+        oid!(1.3.6.1.5.5.7.3.2),
+    ),
+    OidEntry::new("codeSigning", "Code signing", oid!(1.3.6.1.5.5.7.3.3)),
+    OidEntry::new(
+        "emailProtection",
+        "Email protection",
+        oid!(1.3.6.1.5.5.7.3.4),
+    ),
+    OidEntry::new("timeStamping", "Time stamping", oid!(1.3.6.1.5.5.7.3.8)),
+    OidEntry::new("OCSPSigning", "OCSP signing", oid!(1.3.6.1.5.5.7.3.9)),
+    OidEntry::new("id-ad", "PKIX access descriptors", oid!(1.3.6.1.5.5.7.48)),
+    OidEntry::new("id-ad-ocsp", "OCSP", oid!(1.3.6.1.5.5.7.48.1)),
+    OidEntry::new(
+        "id-pkix-ocsp-basic",
+        "Basic OCSP response",
+        oid!(1.3.6.1.5.5.7.48.1.1),
+    ),
+    OidEntry::new(
+        "id-pkix-ocsp-nonce",
+        "OCSP nonce",
+        oid!(1.3.6.1.5.5.7.48.1.2),
+    ),
+    OidEntry::new(
+        "id-pkix-ocsp-nocheck",
+        "OCSP no check",
+        oid!(1.3.6.1.5.5.7.48.1.5),
+    ),
+    OidEntry::new("id-ad-caIssuers", "CA issuers", oid!(1.3.6.1.5.5.7.48.2)),
+    OidEntry::new(
+        "id-ad-timeStamping",
+        "Time stamping",
+        oid!(1.3.6.1.5.5.7.48.3),
+    ),
+    OidEntry::new(
+        "id-ad-caRepository",
+        "CA repository",
+        oid!(1.3.6.1.5.5.7.48.5),
+    ),
+    OidEntry::new("sha1", "SHA-1", oid!(1.3.14.3.2.26)),
+    OidEntry::new("X25519", "X25519", oid!(1.3.101.110)),
+    OidEntry::new("X448", "X448", oid!(1.3.101.111)),
+    OidEntry::new("Ed25519", "Ed25519", oid!(1.3.101.112)),
+    OidEntry::new("Ed448", "Ed448", oid!(1.3.101.113)),
+    OidEntry::new("secp256k1", "SECG secp256k1 curve", oid!(1.3.132.0.10)),
+    OidEntry::new("secp384r1", "NIST P-384 curve", oid!(1.3.132.0.34)),
+    OidEntry::new("secp521r1", "NIST P-521 curve", oid!(1.3.132.0.35)),
+    OidEntry::new("commonName", "Common name", oid!(2.5.4.3)),
+    OidEntry::new("surname", "Surname", oid!(2.5.4.4)),
+    OidEntry::new("serialNumber", "Serial number", oid!(2.5.4.5)),
+    OidEntry::new("countryName", "Country name", oid!(2.5.4.6)),
+    OidEntry::new("localityName", "Locality name", oid!(2.5.4.7)),
+    OidEntry::new(
+        "stateOrProvinceName",
+        "State or province name",
+        oid!(2.5.4.8),
+    ),
+    OidEntry::new("streetAddress", "Street address", oid!(2.5.4.9)),
+    OidEntry::new("organizationName", "Organization name", oid!(2.5.4.10)),
+    OidEntry::new(
+        "organizationalUnitName",
+        "Organizational unit name",
+        oid!(2.5.4.11),
+    ),
+    OidEntry::new("title", "Title", oid!(2.5.4.12)),
+    OidEntry::new("description", "Description", oid!(2.5.4.13)),
+    OidEntry::new("postalCode", "Postal code", oid!(2.5.4.17)),
+    OidEntry::new("name", "Name", oid!(2.5.4.41)),
+    OidEntry::new("givenName", "Given name", oid!(2.5.4.42)),
+    OidEntry::new("initials", "Initials", oid!(2.5.4.43)),
+    OidEntry::new(
+        "generationQualifier",
+        "Generation qualifier",
+        oid!(2.5.4.44),
+    ),
+    OidEntry::new("dnQualifier", "DN qualifier", oid!(2.5.4.46)),
+    OidEntry::new("pseudonym", "Pseudonym", oid!(2.5.4.65)),
+    OidEntry::new(
+        "organizationIdentifier",
+        "Organization identifier",
+        oid!(2.5.4.97),
+    ),
+    OidEntry::new(
+        "subjectDirectoryAttributes",
+        "Subject directory attributes",
+        oid!(2.5.29.9),
+    ),
+    OidEntry::new(
+        "subjectKeyIdentifier",
+        "Subject key identifier",
+        oid!(2.5.29.14),
+    ),
+    OidEntry::new("keyUsage", "Key usage", oid!(2.5.29.15)),
+    OidEntry::new(
+        "privateKeyUsagePeriod",
+        "Private key usage period",
+        oid!(2.5.29.16),
+    ),
+    OidEntry::new(
+        "subjectAltName",
+        "Subject alternative name",
+        oid!(2.5.29.17),
+    ),
+    OidEntry::new("issuerAltName", "Issuer alternative name", oid!(2.5.29.18)),
+    OidEntry::new("basicConstraints", "Basic constraints", oid!(2.5.29.19)),
+    OidEntry::new("cRLNumber", "CRL number", oid!(2.5.29.20)),
+    OidEntry::new("cRLReason", "CRL reason code", oid!(2.5.29.21)),
+    OidEntry::new("invalidityDate", "Invalidity date", oid!(2.5.29.24)),
+    OidEntry::new("deltaCRLIndicator", "Delta CRL indicator", oid!(2.5.29.27)),
+    OidEntry::new(
+        "issuingDistributionPoint",
+        "Issuing distribution point",
+        oid!(2.5.29.28),
+    ),
+    OidEntry::new("certificateIssuer", "Certificate issuer", oid!(2.5.29.29)),
+    OidEntry::new("nameConstraints", "Name constraints", oid!(2.5.29.30)),
+    OidEntry::new(
+        "cRLDistributionPoints",
+        "CRL distribution points",
+        oid!(2.5.29.31),
+    ),
+    OidEntry::new(
+        "certificatePolicies",
+        "Certificate policies",
+        oid!(2.5.29.32),
+    ),
+    OidEntry::new("anyPolicy", "Any policy", oid!(2.5.29.32.0)),
+    OidEntry::new("policyMappings", "Policy mappings", oid!(2.5.29.33)),
+    OidEntry::new(
+        "authorityKeyIdentifier",
+        "Authority key identifier",
+        oid!(2.5.29.35),
+    ),
+    OidEntry::new("policyConstraints", "Policy constraints", oid!(2.5.29.36)),
+    OidEntry::new("extKeyUsage", "Extended key usage", oid!(2.5.29.37)),
+    OidEntry::new(
+        "anyExtendedKeyUsage",
+        "Any extended key usage",
+        oid!(2.5.29.37.0),
+    ),
+    OidEntry::new("freshestCRL", "Freshest CRL", oid!(2.5.29.46)),
+    OidEntry::new("inhibitAnyPolicy", "Inhibit any policy", oid!(2.5.29.54)),
+    OidEntry::new(
+        "aes128-CBC",
+        "AES-128 in CBC mode",
+        oid!(2.16.840.1.101.3.4.1.2),
+    ),
+    OidEntry::new(
+        "aes128-GCM",
+        "AES-128 in GCM mode",
+        oid!(2.16.840.1.101.3.4.1.6),
+    ),
+    OidEntry::new(
+        "aes192-CBC",
+        "AES-192 in CBC mode",
+        oid!(2.16.840.1.101.3.4.1.22),
+    ),
+    OidEntry::new(
+        "aes192-GCM",
+        "AES-192 in GCM mode",
+        oid!(2.16.840.1.101.3.4.1.26),
+    ),
+    OidEntry::new(
+        "aes256-CBC",
+        "AES-256 in CBC mode",
+        oid!(2.16.840.1.101.3.4.1.42),
+    ),
+    OidEntry::new(
+        "aes256-GCM",
+        "AES-256 in GCM mode",
+        oid!(2.16.840.1.101.3.4.1.46),
+    ),
+    OidEntry::new("sha256", "SHA-256", oid!(2.16.840.1.101.3.4.2.1)),
+    OidEntry::new("sha384", "SHA-384", oid!(2.16.840.1.101.3.4.2.2)),
+    OidEntry::new("sha512", "SHA-512", oid!(2.16.840.1.101.3.4.2.3)),
+    OidEntry::new("sha224", "SHA-224", oid!(2.16.840.1.101.3.4.2.4)),
+    OidEntry::new("sha3-256", "SHA3-256", oid!(2.16.840.1.101.3.4.2.8)),
+    OidEntry::new("sha3-384", "SHA3-384", oid!(2.16.840.1.101.3.4.2.9)),
+    OidEntry::new("sha3-512", "SHA3-512", oid!(2.16.840.1.101.3.4.2.10)),
+    OidEntry::new(
+        "dsa-with-sha256",
+        "DSA with SHA-256",
+        oid!(2.16.840.1.101.3.4.3.2),
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_sorted() {
+        assert!(REGISTRY.windows(2).all(|w| w[0].oid < w[1].oid));
+        for (idx, entry) in REGISTRY.iter().enumerate() {
+            assert!(REGISTRY[idx + 1..].iter().all(|e| e.name != entry.name));
+        }
+    }
+
+    #[rustfmt::skip::macros(oid)]
+    #[test]
+    fn registry_lookup() {
+        assert_eq!(oid!(1.2.840.10045.2.1).name(), Some("ecPublicKey"));
+        assert_eq!(oid!(2.16.840.1.101.3.4.2.1).name(), Some("sha256"));
+        assert_eq!(oid!(1.2.3.4).name(), None);
+        assert_eq!(oid!(rel 2.5.4.3).name(), None);
+        let entry = OidRegistry::get_by_name("id-pkinit-KPKdc").unwrap();
+        assert_eq!(entry.oid(), &oid!(1.3.6.1.5.2.3.5));
+        assert!(OidRegistry::get_by_name("unknown").is_none());
+    }
+}
@@ -46,6 +46,31 @@ where
     }
 }
 
+impl<'a, T> FromCer<'a> for Option<T>
+where
+    T: FromCer<'a>,
+{
+    fn from_cer(bytes: &'a [u8]) -> ParseResult<Self> {
+        if bytes.is_empty() {
+            return Ok((bytes, None));
+        }
+        match T::from_cer(bytes) {
+            Ok((rem, t)) => Ok((rem, Some(t))),
+            Err(Err::Error(Error::UnexpectedTag { .. })) => Ok((bytes, None)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<T> CheckCerConstraints for Option<T>
+where
+    T: CheckCerConstraints,
+{
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        T::check_cer_constraints(any)
+    }
+}
+
 impl<T> DynTagged for Option<T>
 where
     T: DynTagged,
@@ -85,3 +110,16 @@ where
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl<T> ToCer for Option<T>
+where
+    T: ToCer,
+{
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        match self {
+            None => Ok(0),
+            Some(t) => t.write_cer(writer),
+        }
+    }
+}
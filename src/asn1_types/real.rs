@@ -1,7 +1,8 @@
 use crate::*;
 use alloc::format;
+use alloc::string::String;
 use core::convert::TryFrom;
-use nom::Needed;
+use core::fmt;
 
 mod f32;
 mod f64;
@@ -10,104 +11,230 @@ pub use self::f64::*;
 
 /// ASN.1 `REAL` type
 ///
-/// # Limitations
+/// The value keeps the representation it was decoded from (or built with): base, scaling factor
+/// and exponent for binary encodings, and the character string for decimal encodings.
+/// Encoding a value gives back the same representation, so decoding and encoding is an exact
+/// round-trip. Use [`Real::canonical`] to get the representation required by DER and CER
+/// (X.690 section 11.3).
 ///
-/// When encoding binary values, only base 2 is supported
-#[derive(Debug, PartialEq)]
+/// Values are compared exactly: two binary values are equal if they represent the same number
+/// (regardless of the base and scaling factor), and two decimal values are equal if they
+/// represent the same number (regardless of the form). Binary and decimal values are never equal.
+///
+/// `Display` uses the ASN.1 value notation.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{DecimalForm, Real};
+///
+/// let r = Real::binary(3.25, 2, 0)
+///     .and_then(|r| r.with_enc_base(8))
+///     .expect("invalid value");
+/// assert_eq!(r.f64(), 3.25);
+/// assert_eq!(r.to_string(), "{ mantissa 26, base 2, exponent -3 }");
+///
+/// let r = Real::decimal(DecimalForm::NR2, "-1,50").expect("invalid value");
+/// assert_eq!(r.f64(), -1.5);
+/// assert_eq!(r.to_string(), "-1.50");
+/// assert_eq!(r.canonical().unwrap(), Real::decimal(DecimalForm::NR3, "-15.E-1").unwrap());
+/// ```
+#[derive(Debug, Clone)]
 pub enum Real {
-    /// Non-special values
+    /// Binary encoding (X.690 section 8.5.7)
+    ///
+    /// The value is `mantissa × 2^scaling × base^exponent`, negated if `negative` is true.
     Binary {
-        mantissa: f64,
-        base: u32,
+        negative: bool,
+        /// Mantissa (`N`)
+        mantissa: u64,
+        /// Base (`B`): 2, 8 or 16
+        base: u8,
+        /// Binary scaling factor (`F`), between 0 and 3
+        scaling: u8,
+        /// Exponent (`E`)
         exponent: i32,
-        enc_base: u8,
     },
+    /// Decimal encoding (X.690 section 8.5.8), as an ISO 6093 string
+    Decimal { form: DecimalForm, value: String },
     /// Infinity (∞).
     Infinity,
     /// Negative infinity (−∞).
     NegInfinity,
+    /// Not a number
+    NotANumber,
     /// Zero
     Zero,
+    /// Negative zero
+    MinusZero,
+}
+
+/// Numerical representation of decimal `REAL` values (ISO 6093)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalForm {
+    /// Integer, for ex. `-123`
+    NR1 = 1,
+    /// Number with a decimal mark, for ex. `1.5` or `-0,25`
+    NR2 = 2,
+    /// Number with a decimal mark and an exponent, for ex. `15.E-1`
+    NR3 = 3,
+}
+
+/// Decimal value, decomposed as `digits × 10^exponent`
+struct Decimal {
+    negative: bool,
+    /// Digits without leading or trailing zeroes (empty for 0)
+    digits: String,
+    exponent: i64,
 }
 
 impl Real {
     /// Create a new `REAL` from the `f64` value.
+    ///
+    /// Finite values are stored as decimal values (`NR3` form) using the shortest string
+    /// which converts back to the same `f64`.
     pub fn new(f: f64) -> Self {
-        if f.is_infinite() {
+        if f.is_nan() {
+            Self::NotANumber
+        } else if f.is_infinite() {
             if f.is_sign_positive() {
                 Self::Infinity
             } else {
                 Self::NegInfinity
             }
-        } else if f.abs() == 0.0 {
-            Self::Zero
+        } else if f == 0.0 {
+            if f.is_sign_positive() {
+                Self::Zero
+            } else {
+                Self::MinusZero
+            }
         } else {
-            let mut e = 0;
-            let mut f = f;
-            while f.fract() != 0.0 {
-                f *= 10.0_f64;
-                e -= 1;
+            // `{:e}` gives the shortest representation, for ex. `1.2345e0`
+            let s = format!("{:e}", f);
+            match parse_decimal(DecimalForm::NR3, &s) {
+                Some(d) => d.into_canonical(),
+                None => unreachable!("invalid float formatting"),
             }
-            Real::Binary {
-                mantissa: f,
-                base: 10,
-                exponent: e,
-                enc_base: 10,
+        }
+    }
+
+    /// Create a new binary `REAL`, with value `mantissa × base^exponent`
+    ///
+    /// If `base` is 2, 8 or 16, it is used for encoding, otherwise the value is encoded using
+    /// base 2. The value is exact if `base` is a power of 2, otherwise it is computed using `f64`
+    /// and may be rounded.
+    ///
+    /// Returns an error if the exponent does not fit into `i32` when using the encoding base.
+    pub fn binary(mantissa: f64, base: u32, exponent: i32) -> Result<Self> {
+        let enc_base = match base {
+            2 | 8 | 16 => base as u8,
+            _ => 2,
+        };
+        if base < 2 || !base.is_power_of_two() {
+            let f = mantissa * f64::from(base).powi(exponent);
+            return match decompose_f64(f) {
+                Ok((negative, n, e)) => Real::from_normalized(negative, n, e, enc_base),
+                Err(special) => Ok(special),
+            };
+        }
+        let shift = i64::from(base.trailing_zeros());
+        let (negative, n, e) = match decompose_f64(mantissa) {
+            Ok(t) => t,
+            Err(special) => return Ok(special),
+        };
+        Real::from_normalized(negative, n, e + i64::from(exponent) * shift, enc_base)
+    }
+
+    /// Create a new decimal `REAL` from its character string
+    ///
+    /// Leading spaces, a plus sign and a comma as decimal mark are accepted, as defined by
+    /// ISO 6093. The decimal mark is optional in the `NR3` form. The string is stored unmodified.
+    pub fn decimal(form: DecimalForm, value: &str) -> Result<Self> {
+        match parse_decimal(form, value) {
+            Some(_) => Ok(Real::Decimal {
+                form,
+                value: value.into(),
+            }),
+            None => Err(Self::TAG.invalid_value("Invalid decimal REAL string")),
+        }
+    }
+
+    /// Change the base used for encoding (2, 8 or 16 for binary encoding, 10 for decimal encoding)
+    ///
+    /// Conversions between binary and decimal values are done using `f64`, and may lose precision.
+    /// Other values are unchanged.
+    ///
+    /// Returns an error if the exponent does not fit into `i32` when using the new base.
+    pub fn with_enc_base(self, enc_base: u8) -> Result<Self> {
+        match (&self, enc_base) {
+            (Real::Binary { .. }, 2 | 8 | 16) => {
+                let (negative, n, e) = self.binary_normalized().unwrap_or_default();
+                Real::from_normalized(negative, n, e, enc_base)
             }
-            .normalize_base10()
+            (Real::Decimal { .. }, 2 | 8 | 16) => Real::binary(self.f64(), enc_base as u32, 0),
+            (Real::Binary { .. }, 10) => Ok(Real::new(self.f64())),
+            _ => Ok(self),
         }
     }
 
-    pub const fn with_enc_base(self, enc_base: u8) -> Self {
+    /// Returns the representation required by DER and CER (X.690 section 11.3)
+    ///
+    /// Binary values use base 2, no scaling factor and an odd mantissa. Decimal values use
+    /// the `NR3` form, with no leading or trailing zeroes in the mantissa.
+    ///
+    /// Returns an error if the exponent of a binary value does not fit into `i32` when using
+    /// base 2.
+    pub fn canonical(&self) -> Result<Self> {
         match self {
-            Real::Binary {
-                mantissa,
-                base,
-                exponent,
-                ..
-            } => Real::Binary {
-                mantissa,
-                base,
-                exponent,
-                enc_base,
+            Real::Binary { .. } => match self.binary_normalized() {
+                Some((negative, n, e)) => Real::from_normalized(negative, n, e, 2),
+                None => Ok(Real::Zero),
+            },
+            Real::Decimal { form, value } => match parse_decimal(*form, value) {
+                Some(d) => Ok(d.into_canonical()),
+                None => Ok(self.clone()),
             },
-            e => e,
+            _ => Ok(self.clone()),
         }
     }
 
-    fn normalize_base10(self) -> Self {
-        match self {
+    /// Build a binary value from `(-1)^negative × n × 2^e`
+    fn from_normalized(negative: bool, n: u64, e: i64, base: u8) -> Result<Self> {
+        if n == 0 {
+            return Ok(Real::Zero);
+        }
+        let shift = base_shift(base);
+        let exponent = e.div_euclid(shift);
+        let scaling = (e - exponent * shift) as u8;
+        let exponent = i32::try_from(exponent)
+            .map_err(|_| Self::TAG.invalid_value("Exponent does not fit into i32"))?;
+        Ok(Real::Binary {
+            negative,
+            mantissa: n,
+            base,
+            scaling,
+            exponent,
+        })
+    }
+
+    /// Returns `(negative, n, e)` so that the value is `(-1)^negative × n × 2^e`, and `n` is odd
+    fn binary_normalized(&self) -> Option<(bool, u64, i64)> {
+        match *self {
             Real::Binary {
+                negative,
                 mantissa,
-                base: 10,
+                base,
+                scaling,
                 exponent,
-                enc_base: _enc_base,
             } => {
-                let mut m = mantissa;
-                let mut e = exponent;
-                while m.abs() > f64::EPSILON && m.rem_euclid(10.0).abs() < f64::EPSILON {
-                    m /= 10.0;
-                    e += 1;
-                }
-                Real::Binary {
-                    mantissa: m,
-                    base: 10,
-                    exponent: e,
-                    enc_base: _enc_base,
+                if mantissa == 0 {
+                    return None;
                 }
+                let tz = mantissa.trailing_zeros();
+                let e = (exponent as i64) * base_shift(base) + (scaling as i64) + (tz as i64);
+                Some((negative, mantissa >> tz, e))
             }
-            _ => self,
-        }
-    }
-
-    /// Create a new binary `REAL`
-    #[inline]
-    pub const fn binary(mantissa: f64, base: u32, exponent: i32) -> Self {
-        Self::Binary {
-            mantissa,
-            base,
-            exponent,
-            enc_base: 2,
+            _ => None,
         }
     }
 
@@ -118,10 +245,13 @@ impl Real {
         matches!(self, Real::Infinity | Real::NegInfinity)
     }
 
-    /// Returns `true` if this number is not infinite.
+    /// Returns `true` if this number is neither infinite nor `NaN`.
     #[inline]
     pub fn is_finite(&self) -> bool {
-        matches!(self, Real::Zero | Real::Binary { .. })
+        matches!(
+            self,
+            Real::Zero | Real::MinusZero | Real::Binary { .. } | Real::Decimal { .. }
+        )
     }
 
     /// Returns the 'f64' value of this `REAL`.
@@ -129,19 +259,24 @@ impl Real {
     /// Returned value is a float, and may be infinite.
     pub fn f64(&self) -> f64 {
         match self {
-            Real::Binary {
-                mantissa,
-                base,
-                exponent,
-                ..
-            } => {
-                let f = *mantissa as f64;
-                let exp = (*base as f64).powi(*exponent);
-                f * exp
+            Real::Binary { negative, .. } => {
+                let (_, n, e) = self.binary_normalized().unwrap_or_default();
+                let f = ldexp(n as f64, e);
+                if *negative {
+                    -f
+                } else {
+                    f
+                }
             }
+            Real::Decimal { form, value } => match parse_decimal(*form, value) {
+                Some(d) => d.f64(),
+                None => f64::NAN,
+            },
             Real::Zero => 0.0_f64,
+            Real::MinusZero => -0.0_f64,
             Real::Infinity => f64::INFINITY,
             Real::NegInfinity => f64::NEG_INFINITY,
+            Real::NotANumber => f64::NAN,
         }
     }
 
@@ -153,6 +288,98 @@ impl Real {
     }
 }
 
+impl PartialEq for Real {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Real::Binary { .. }, Real::Binary { .. }) => {
+                self.binary_normalized() == other.binary_normalized()
+            }
+            (
+                Real::Decimal {
+                    form: f1,
+                    value: v1,
+                },
+                Real::Decimal {
+                    form: f2,
+                    value: v2,
+                },
+            ) => match (parse_decimal(*f1, v1), parse_decimal(*f2, v2)) {
+                (Some(d1), Some(d2)) => {
+                    d1.negative == d2.negative
+                        && d1.digits == d2.digits
+                        && d1.exponent == d2.exponent
+                }
+                _ => f1 == f2 && v1 == v2,
+            },
+            // compare as values, for ex. a binary value with mantissa 0 is zero
+            (Real::Binary { .. }, Real::Zero) | (Real::Zero, Real::Binary { .. }) => {
+                self.binary_normalized().is_none() && other.binary_normalized().is_none()
+            }
+            (Real::Binary { .. }, _) | (_, Real::Binary { .. }) => false,
+            (Real::Decimal { .. }, _) | (_, Real::Decimal { .. }) => false,
+            _ => core::mem::discriminant(self) == core::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Real {}
+
+impl fmt::Display for Real {
+    /// Format the value using the ASN.1 value notation (X.680 section 21.6)
+    ///
+    /// Binary values are written using the `SEQUENCE` notation with base 2, and decimal values
+    /// as a `realnumber`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Real::Binary {
+                negative,
+                mantissa,
+                base,
+                scaling,
+                exponent,
+            } => {
+                let m = (*mantissa as i128) << *scaling;
+                let m = if *negative { -m } else { m };
+                let e = (*exponent as i64) * base_shift(*base);
+                write!(f, "{{ mantissa {}, base 2, exponent {} }}", m, e)
+            }
+            Real::Decimal { value, .. } => {
+                let s = value.trim_start_matches(' ');
+                let s = s.strip_prefix('+').unwrap_or(s);
+                let s = match s.strip_prefix('-') {
+                    Some(s) => {
+                        f.write_str("-")?;
+                        s
+                    }
+                    None => s,
+                };
+                // a realnumber starts with a digit
+                if s.starts_with(|c| c == '.' || c == ',') {
+                    f.write_str("0")?;
+                }
+                let mut after_exponent = false;
+                for c in s.chars() {
+                    match c {
+                        ',' => f.write_str(".")?,
+                        'e' | 'E' => {
+                            after_exponent = true;
+                            f.write_str("E")?;
+                        }
+                        '+' if after_exponent => (),
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                Ok(())
+            }
+            Real::Infinity => f.write_str("PLUS-INFINITY"),
+            Real::NegInfinity => f.write_str("MINUS-INFINITY"),
+            Real::NotANumber => f.write_str("NOT-A-NUMBER"),
+            Real::Zero => f.write_str("0"),
+            Real::MinusZero => f.write_str("-0"),
+        }
+    }
+}
+
 impl<'a> TryFrom<Any<'a>> for Real {
     type Error = Error;
 
@@ -163,111 +390,68 @@ impl<'a> TryFrom<Any<'a>> for Real {
         if data.is_empty() {
             return Ok(Real::Zero);
         }
-        // code inspired from pyasn1
         let first = data[0];
         let rem = &data[1..];
         if first & 0x80 != 0 {
-            // binary encoding (X.690 section 8.5.6)
-            let rem = rem;
-            // format of exponent
-            let (n, rem) = match first & 0x03 {
-                4 => {
-                    let (b, rem) = rem
-                        .split_first()
-                        .ok_or_else(|| Error::Incomplete(Needed::new(1)))?;
-                    (*b as usize, rem)
-                }
-                b => (b as usize + 1, rem),
+            // binary encoding (X.690 section 8.5.7)
+            let negative = first & 0x40 != 0;
+            let base = match (first >> 4) & 0x03 {
+                0 => 2,
+                1 => 8,
+                2 => 16,
+                _ => return Err(any.tag().invalid_value("Illegal REAL encoding base")),
             };
-            if n >= rem.len() {
-                return Err(any.tag().invalid_value("Invalid float value(exponent)"));
-            }
-            // n cannot be 0 (see the +1 above)
-            let (eo, rem) = rem.split_at(n);
-            // so 'eo' cannot be empty
-            let mut e = if eo[0] & 0x80 != 0 { -1 } else { 0 };
+            let scaling = (first >> 2) & 0x03;
+            let (eo, mo) = split_exponent(first, rem)
+                .ok_or_else(|| any.tag().invalid_value("Invalid float value(exponent)"))?;
             // safety check: 'eo' length must be <= container type for 'e'
             if eo.len() > 4 {
                 return Err(any.tag().invalid_value("Exponent too large (REAL)"));
             }
+            let mut exponent = if eo[0] & 0x80 != 0 { -1 } else { 0 };
             for b in eo {
-                e = (e << 8) | (*b as i32);
+                exponent = (exponent << 8) | (*b as i32);
             }
-            // base bits
-            let b = (first >> 4) & 0x03;
-            let _enc_base = match b {
-                0 => 2,
-                1 => 8,
-                2 => 16,
-                _ => return Err(any.tag().invalid_value("Illegal REAL encoding base")),
-            };
-            let e = match b {
-                // base 2
-                0 => e,
-                // base 8
-                1 => e * 3,
-                // base 16
-                2 => e * 4,
-                _ => return Err(any.tag().invalid_value("Illegal REAL base")),
-            };
-            if rem.len() > 8 {
+            let mo = &mo[mo.iter().take_while(|&&b| b == 0).count()..];
+            if mo.len() > 8 {
                 return Err(any.tag().invalid_value("Mantissa too large (REAL)"));
             }
-            let mut p = 0;
-            for b in rem {
-                p = (p << 8) | (*b as i64);
+            let mut mantissa = 0;
+            for b in mo {
+                mantissa = (mantissa << 8) | (*b as u64);
             }
-            // sign bit
-            let p = if first & 0x40 != 0 { -p } else { p };
-            // scale bits
-            let sf = (first >> 2) & 0x03;
-            let p = match sf {
-                0 => p as f64,
-                sf => {
-                    // 2^sf: cannot overflow, sf is between 0 and 3
-                    let scale = 2_f64.powi(sf as _);
-                    (p as f64) * scale
-                }
-            };
             Ok(Real::Binary {
-                mantissa: p,
-                base: 2,
-                exponent: e,
-                enc_base: _enc_base,
+                negative,
+                mantissa,
+                base,
+                scaling,
+                exponent,
             })
         } else if first & 0x40 != 0 {
-            // special real value (X.690 section 8.5.8)
+            // special real value (X.690 section 8.5.9)
             // there shall be only one contents octet,
-            if any.header.length != Length::Definite(1) {
+            if data.len() != 1 {
                 return Err(Error::InvalidLength);
             }
             // with values as follows
             match first {
                 0x40 => Ok(Real::Infinity),
                 0x41 => Ok(Real::NegInfinity),
+                0x42 => Ok(Real::NotANumber),
+                0x43 => Ok(Real::MinusZero),
                 _ => Err(any.tag().invalid_value("Invalid float special value")),
             }
         } else {
-            // decimal encoding (X.690 section 8.5.7)
+            // decimal encoding (X.690 section 8.5.8)
+            let form = match first {
+                0x01 => DecimalForm::NR1,
+                0x02 => DecimalForm::NR2,
+                0x03 => DecimalForm::NR3,
+                c => return Err(any.tag().invalid_value(&format!("Invalid NR ({})", c))),
+            };
             let s = alloc::str::from_utf8(rem)?;
-            match first & 0x03 {
-                0x1 => {
-                    // NR1
-                    match s.parse::<u32>() {
-                        Err(_) => Err(any.tag().invalid_value("Invalid float string encoding")),
-                        Ok(v) => Ok(Real::new(v.into())),
-                    }
-                }
-                0x2 /* NR2 */ | 0x3 /* NR3 */=> {
-                    match s.parse::<f64>() {
-                        Err(_) => Err(any.tag().invalid_value("Invalid float string encoding")),
-                        Ok(v) => Ok(Real::new(v)),
-                    }
-                        }
-                c => {
-                    return Err(any.tag().invalid_value(&format!("Invalid NR ({})", c)));
-                }
-            }
+            Real::decimal(form, s)
+                .map_err(|_| any.tag().invalid_value("Invalid float string encoding"))
         }
     }
 }
@@ -276,13 +460,53 @@ impl<'a> CheckDerConstraints for Real {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.assert_primitive()?;
         any.header.length.assert_definite()?;
-        // XXX more checks
+        // X.690 section 11.3
+        let data = &any.data;
+        let first = match data.first() {
+            Some(b) => *b,
+            None => return Ok(()),
+        };
+        let canonical = if first & 0x80 != 0 {
+            // base 2, no scaling factor, exponent and mantissa in the fewest octets, odd mantissa
+            match split_exponent(first, &data[1..]) {
+                Some((eo, mo)) => {
+                    first & 0x3c == 0
+                        && !(eo.len() > 1
+                            && ((eo[0] == 0 && eo[1] < 0x80) || (eo[0] == 0xff && eo[1] >= 0x80)))
+                        && mo[0] != 0
+                        && mo[mo.len() - 1] & 1 == 1
+                }
+                None => false,
+            }
+        } else if first & 0x40 != 0 {
+            true
+        } else {
+            first == DecimalForm::NR3 as u8 && is_canonical_nr3(&data[1..])
+        };
+        if !canonical {
+            return Err(Error::DerConstraintFailed(DerConstraint::RealNotCanonical));
+        }
         Ok(())
     }
 }
 
 impl DerAutoDerive for Real {}
 
+impl CheckCerConstraints for Real {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for Real {}
+
+#[cfg(feature = "std")]
+impl ToCer for Real {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl Tagged for Real {
     const TAG: Tag = Tag::RealType;
 }
@@ -290,27 +514,19 @@ impl Tagged for Real {
 #[cfg(feature = "std")]
 impl ToDer for Real {
     fn to_der_len(&self) -> Result<usize> {
-        match self {
-            Real::Zero => Ok(0),
-            Real::Infinity | Real::NegInfinity => Ok(1),
-            Real::Binary { .. } => {
-                let mut sink = std::io::sink();
-                let n = self
-                    .write_der_content(&mut sink)
-                    .map_err(|_| Self::TAG.invalid_value("Serialization of REAL failed"))?;
-                Ok(n)
-            }
-        }
+        let mut sink = std::io::sink();
+        let n = self
+            .write_der_content(&mut sink)
+            .map_err(|_| Self::TAG.invalid_value("Serialization of REAL failed"))?;
+        let header = Header::new(Class::Universal, false, Self::TAG, Length::Definite(n));
+        Ok(header.to_der_len()? + n)
     }
 
     fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        let header = Header::new(
-            Class::Universal,
-            false,
-            Self::TAG,
-            Length::Definite(self.to_der_len()?),
-        );
-        header.write_der_header(writer).map_err(Into::into)
+        let mut sink = std::io::sink();
+        let n = self.write_der_content(&mut sink)?;
+        let header = Header::new(Class::Universal, false, Self::TAG, Length::Definite(n));
+        header.write_der_header(writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
@@ -318,93 +534,53 @@ impl ToDer for Real {
             Real::Zero => Ok(0),
             Real::Infinity => writer.write(&[0x40]).map_err(Into::into),
             Real::NegInfinity => writer.write(&[0x41]).map_err(Into::into),
+            Real::NotANumber => writer.write(&[0x42]).map_err(Into::into),
+            Real::MinusZero => writer.write(&[0x43]).map_err(Into::into),
+            Real::Decimal { form, value } => {
+                let n = writer.write(&[*form as u8])?;
+                Ok(n + writer.write(value.as_bytes())?)
+            }
             Real::Binary {
+                negative,
                 mantissa,
                 base,
+                scaling,
                 exponent,
-                enc_base: _enc_base,
             } => {
-                if *base == 10 {
-                    // using character form
-                    let sign = if *exponent == 0 { "+" } else { "" };
-                    let s = format!("\x03{}E{}{}", mantissa, sign, exponent);
-                    return writer.write(s.as_bytes()).map_err(Into::into);
-                }
-                if *base != 2 {
-                    return Err(Self::TAG.invalid_value("Invalid base for REAL").into());
-                }
                 let mut first: u8 = 0x80;
-                // choose encoding base
-                let enc_base = *_enc_base;
-                let (ms, mut m, enc_base, mut e) =
-                    drop_floating_point(*mantissa, enc_base, *exponent);
-                assert!(m != 0);
-                if ms < 0 {
+                if *negative {
                     first |= 0x40
                 };
-                // exponent & mantissa normalization
-                match enc_base {
-                    2 => {
-                        while m & 0x1 == 0 {
-                            m >>= 1;
-                            e += 1;
-                        }
-                    }
-                    8 => {
-                        while m & 0x7 == 0 {
-                            m >>= 3;
-                            e += 1;
-                        }
-                        first |= 0x10;
-                    }
-                    _ /* 16 */ => {
-                        while m & 0xf == 0 {
-                            m >>= 4;
-                            e += 1;
-                        }
-                        first |= 0x20;
-                    }
-                }
-                // scale factor
-                // XXX in DER, sf is always 0 (11.3.1)
-                let mut sf = 0;
-                while m & 0x1 == 0 && sf < 4 {
-                    m >>= 1;
-                    sf += 1;
-                }
-                first |= sf << 2;
-                // exponent length and bytes
-                let len_e = match e.abs() {
-                    0..=0xff => 1,
-                    0x100..=0xffff => 2,
-                    0x1_0000..=0xff_ffff => 3,
-                    // e is an `i32` so it can't be longer than 4 bytes
-                    // use 4, so `first` is ORed with 3
-                    _ => 4,
+                first |= match base {
+                    2 => 0x00,
+                    8 => 0x10,
+                    16 => 0x20,
+                    _ => return Err(Self::TAG.invalid_value("Invalid base for REAL").into()),
                 };
-                first |= (len_e - 1) & 0x3;
-                // write first byte
-                let mut n = writer.write(&[first])?;
-                // write exponent
-                // special case: number of bytes from exponent is > 3 and cannot fit in 2 bits
-                #[allow(clippy::identity_op)]
-                if len_e == 4 {
-                    let b = len_e & 0xff;
-                    n += writer.write(&[b])?;
+                if *scaling > 3 {
+                    return Err(Self::TAG
+                        .invalid_value("Invalid scaling factor for REAL")
+                        .into());
                 }
-                // we only need to write e.len() bytes
-                let bytes = e.to_be_bytes();
-                n += writer.write(&bytes[(4 - len_e) as usize..])?;
+                first |= scaling << 2;
+                // exponent bytes, skipping leading bytes if they are only sign extension
+                let bytes = exponent.to_be_bytes();
+                let skip = bytes
+                    .windows(2)
+                    .take_while(|w| (w[0] == 0x00 && w[1] < 0x80) || (w[0] == 0xff && w[1] >= 0x80))
+                    .count();
+                let eo = &bytes[skip..];
+                // special case: number of bytes from exponent is > 3 and cannot fit in 2 bits
+                let mut n = if eo.len() > 3 {
+                    writer.write(&[first | 0x03, eo.len() as u8])?
+                } else {
+                    writer.write(&[first | (eo.len() as u8 - 1)])?
+                };
+                n += writer.write(eo)?;
                 // write mantissa
-                let bytes = m.to_be_bytes();
-                let mut idx = 0;
-                for &b in bytes.iter() {
-                    if b != 0 {
-                        break;
-                    }
-                    idx += 1;
-                }
-                n += writer.write(&bytes[idx..])?;
+                let bytes = mantissa.to_be_bytes();
+                let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+                n += writer.write(&bytes[skip..])?;
                 Ok(n)
             }
         }
@@ -435,28 +611,181 @@ impl From<Real> for f64 {
     }
 }
 
-#[cfg(feature = "std")]
-fn drop_floating_point(m: f64, b: u8, e: i32) -> (i8, u64, u8, i32) {
-    let ms = if m.is_sign_positive() { 1 } else { -1 };
-    let es = if e.is_positive() { 1 } else { -1 };
-    let mut m = m.abs();
-    let mut e = e;
-    //
-    if b == 8 {
-        m *= 2_f64.powi((e.abs() / 3) * es);
-        e = (e.abs() / 3) * es;
-    } else if b == 16 {
-        m *= 2_f64.powi((e.abs() / 4) * es);
-        e = (e.abs() / 4) * es;
-    }
-    //
-    while m.abs() > f64::EPSILON {
-        if m.fract() != 0.0 {
-            m *= b as f64;
-            e -= 1;
+impl Decimal {
+    fn f64(&self) -> f64 {
+        if self.digits.is_empty() {
+            return if self.negative { -0.0 } else { 0.0 };
+        }
+        let sign = if self.negative { "-" } else { "" };
+        let s = format!("{}{}e{}", sign, self.digits, self.exponent);
+        s.parse().unwrap_or(f64::NAN)
+    }
+
+    /// Build the `NR3` canonical form (X.690 section 11.3.2)
+    fn into_canonical(self) -> Real {
+        if self.digits.is_empty() {
+            return Real::Zero;
+        }
+        let sign = if self.negative { "-" } else { "" };
+        let value = if self.exponent == 0 {
+            format!("{}{}.E+0", sign, self.digits)
         } else {
-            break;
+            format!("{}{}.E{}", sign, self.digits, self.exponent)
+        };
+        Real::Decimal {
+            form: DecimalForm::NR3,
+            value,
         }
     }
-    (ms, m as u64, b, e)
+}
+
+/// Parse a decimal string in the given ISO 6093 form
+fn parse_decimal(form: DecimalForm, s: &str) -> Option<Decimal> {
+    let s = s.trim_start_matches(' ');
+    let (negative, s) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (significand, exponent) = match form {
+        DecimalForm::NR3 => {
+            let pos = s.find(|c| c == 'E' || c == 'e')?;
+            (&s[..pos], Some(&s[pos + 1..]))
+        }
+        _ => (s, None),
+    };
+    let (int, frac) = match significand.find(|c| c == '.' || c == ',') {
+        Some(pos) => (&significand[..pos], Some(&significand[pos + 1..])),
+        None => (significand, None),
+    };
+    // NR1 has no decimal mark, and NR2 requires one
+    match (form, frac) {
+        (DecimalForm::NR1, Some(_)) | (DecimalForm::NR2, None) => return None,
+        _ => (),
+    }
+    let frac = frac.unwrap_or("");
+    if int.len() + frac.len() == 0 || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let exponent = match exponent {
+        Some(e) => {
+            let (exp_negative, digits) = match e.as_bytes().first() {
+                Some(b'-') => (true, &e[1..]),
+                Some(b'+') => (false, &e[1..]),
+                _ => (false, e),
+            };
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let e = digits.parse::<i64>().ok()?;
+            if exp_negative {
+                -e
+            } else {
+                e
+            }
+        }
+        None => 0,
+    };
+    let mut exponent = exponent.checked_sub(i64::try_from(frac.len()).ok()?)?;
+    let mut digits: String = int
+        .chars()
+        .chain(frac.chars())
+        .skip_while(|&c| c == '0')
+        .collect();
+    while digits.ends_with('0') {
+        digits.pop();
+        exponent = exponent.checked_add(1)?;
+    }
+    if digits.is_empty() {
+        exponent = 0;
+    }
+    Some(Decimal {
+        negative,
+        digits,
+        exponent,
+    })
+}
+
+/// Test if the string is the canonical `NR3` form (X.690 section 11.3.2)
+fn is_canonical_nr3(s: &[u8]) -> bool {
+    let s = s.strip_prefix(b"-").unwrap_or(s);
+    let pos = match s.iter().position(|&b| b == b'.') {
+        Some(pos) => pos,
+        None => return false,
+    };
+    let (mantissa, exponent) = (&s[..pos], &s[pos + 1..]);
+    let exponent = match exponent.strip_prefix(b"E") {
+        Some(e) => e,
+        None => return false,
+    };
+    let is_digits = |s: &[u8]| !s.is_empty() && s.iter().all(u8::is_ascii_digit);
+    let exponent_ok = match exponent {
+        b"+0" => true,
+        [b'-', rest @ ..] | rest => is_digits(rest) && rest[0] != b'0',
+    };
+    is_digits(mantissa)
+        && mantissa[0] != b'0'
+        && mantissa[mantissa.len() - 1] != b'0'
+        && exponent_ok
+}
+
+/// Split the exponent and mantissa octets of a binary encoding
+///
+/// Returns `None` if the exponent or the mantissa is empty.
+fn split_exponent(first: u8, rem: &[u8]) -> Option<(&[u8], &[u8])> {
+    // format of exponent
+    let (n, rem) = match first & 0x03 {
+        3 => {
+            let (b, rem) = rem.split_first()?;
+            (*b as usize, rem)
+        }
+        b => (b as usize + 1, rem),
+    };
+    if n == 0 || n >= rem.len() {
+        return None;
+    }
+    Some(rem.split_at(n))
+}
+
+/// Number of bits of one digit in `base`
+fn base_shift(base: u8) -> i64 {
+    match base {
+        8 => 3,
+        16 => 4,
+        _ => 1,
+    }
+}
+
+/// Decompose a float in `(negative, n, e)` with `n` odd so that `f = (-1)^negative × n × 2^e`,
+/// or return the special value
+fn decompose_f64(f: f64) -> core::result::Result<(bool, u64, i64), Real> {
+    if !f.is_finite() || f == 0.0 {
+        return Err(Real::new(f));
+    }
+    let bits = f.to_bits();
+    let negative = bits >> 63 != 0;
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i64;
+    let fraction = bits & ((1 << 52) - 1);
+    let (n, e) = if biased_exponent == 0 {
+        // subnormal
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+    let tz = n.trailing_zeros();
+    Ok((negative, n >> tz, e + tz as i64))
+}
+
+/// Compute `x × 2^e`
+fn ldexp(mut x: f64, mut e: i64) -> f64 {
+    const STEP: i32 = 1000;
+    while e > STEP as i64 && x.is_finite() {
+        x *= 2_f64.powi(STEP);
+        e -= STEP as i64;
+    }
+    while e < -(STEP as i64) && x != 0.0 {
+        x *= 2_f64.powi(-STEP);
+        e += STEP as i64;
+    }
+    x * 2_f64.powi(e as i32)
 }
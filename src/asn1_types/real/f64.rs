@@ -14,9 +14,7 @@ impl<'a> TryFrom<Any<'a>> for f64 {
 
 impl<'a> CheckDerConstraints for f64 {
     fn check_constraints(any: &Any) -> Result<()> {
-        any.header.assert_primitive()?;
-        any.header.length.assert_definite()?;
-        Ok(())
+        Real::check_constraints(any)
     }
 }
 
@@ -1,12 +1,18 @@
+#[cfg(feature = "std")]
+use crate::cer::write_cer_constructed;
 use crate::*;
 use alloc::borrow::Cow;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
 
+mod array;
+#[cfg(feature = "heapless")]
+mod heapless_vec;
 mod iterator;
 mod sequence_of;
 mod vec;
 
+pub use array::*;
 pub use iterator::*;
 pub use sequence_of::*;
 pub use vec::*;
@@ -204,6 +210,16 @@ impl<'a> Sequence<'a> {
         SequenceIterator::new(&self.content)
     }
 
+    /// Return an iterator over the sequence content, attempting to decode objects as CER
+    ///
+    /// This method can be used when all objects from the sequence have the same type.
+    pub fn cer_iter<T, E>(&'a self) -> SequenceIterator<'a, T, CerParser, E>
+    where
+        T: FromCer<'a, E>,
+    {
+        SequenceIterator::new(&self.content)
+    }
+
     /// Attempt to parse the sequence as a `SEQUENCE OF` items (BER), and return the parsed items as a `Vec`.
     pub fn ber_sequence_of<T, E>(&'a self) -> Result<Vec<T>, E>
     where
@@ -334,6 +350,14 @@ impl<'a> CheckDerConstraints for Sequence<'a> {
 
 impl<'a> DerAutoDerive for Sequence<'a> {}
 
+impl<'a> CheckCerConstraints for Sequence<'a> {
+    fn check_cer_constraints(_any: &Any) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> CerAutoDerive for Sequence<'a> {}
+
 impl<'a> Tagged for Sequence<'a> {
     const TAG: Tag = Tag::Sequence;
 }
@@ -367,6 +391,16 @@ impl ToDer for Sequence<'_> {
     }
 }
 
+/// The content is written unchanged, so it must already be encoded using CER
+#[cfg(feature = "std")]
+impl ToCer for Sequence<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        write_cer_constructed(writer, Class::Universal, Self::TAG, |writer| {
+            writer.write(&self.content).map_err(Into::into)
+        })
+    }
+}
+
 #[cfg(feature = "std")]
 impl<'a> Sequence<'a> {
     /// Attempt to create a `Sequence` from an iterator over serializable objects (to DER)
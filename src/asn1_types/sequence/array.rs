@@ -0,0 +1,143 @@
+use crate::*;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// The `SEQUENCE SIZE (N) OF` object, an ordered list of exactly `N` homogeneous types
+///
+/// Parsing returns a `SizeConstraintFailed` error if the number of items is not exactly `N`.
+///
+/// Note: arrays of bytes (`[u8; N]`) are parsed as `OCTET STRING`, so this wrapper is
+/// required to parse arrays as `SEQUENCE OF`.
+///
+/// # Examples
+///
+/// ```
+/// use asn1_rs::{FixedSequenceOf, FromDer};
+///
+/// let input = &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02];
+/// let (_, seq) = FixedSequenceOf::<u32, 2>::from_der(input).expect("parsing failed");
+/// assert_eq!(seq.into_inner(), [1, 2]);
+///
+/// // wrong number of items
+/// assert!(FixedSequenceOf::<u32, 3>::from_der(input).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedSequenceOf<T, const N: usize> {
+    pub(crate) items: [T; N],
+}
+
+impl<T, const N: usize> FixedSequenceOf<T, N> {
+    /// Builds a `SEQUENCE OF` from the provided content
+    #[inline]
+    pub const fn new(items: [T; N]) -> Self {
+        FixedSequenceOf { items }
+    }
+
+    /// Returns the length of this `SEQUENCE` (the number of items).
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if this `SEQUENCE` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns an iterator over the items of the `SEQUENCE`.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+
+    /// Returns the items of the `SEQUENCE`.
+    #[inline]
+    pub fn into_inner(self) -> [T; N] {
+        self.items
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for FixedSequenceOf<T, N> {
+    fn as_ref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for FixedSequenceOf<T, N> {
+    fn from(items: [T; N]) -> Self {
+        FixedSequenceOf { items }
+    }
+}
+
+impl<'a, T, const N: usize> TryFrom<Any<'a>> for FixedSequenceOf<T, N>
+where
+    T: FromBer<'a>,
+{
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Self> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        let items = SequenceIterator::<T, BerParser>::new(any.data).collect::<Result<Vec<T>>>()?;
+        let items = <[T; N]>::try_from(items).map_err(|v| Error::SizeConstraintFailed {
+            min: N,
+            max: N,
+            actual: v.len(),
+        })?;
+        Ok(FixedSequenceOf { items })
+    }
+}
+
+impl<T, const N: usize> CheckDerConstraints for FixedSequenceOf<T, N>
+where
+    T: CheckDerConstraints,
+{
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        for item in SequenceIterator::<Any, DerParser>::new(any.data) {
+            let item = item?;
+            T::check_constraints(&item)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> DerAutoDerive for FixedSequenceOf<T, N> {}
+
+impl<T, const N: usize> Tagged for FixedSequenceOf<T, N> {
+    const TAG: Tag = Tag::Sequence;
+}
+
+#[cfg(feature = "std")]
+impl<T, const N: usize> ToDer for FixedSequenceOf<T, N>
+where
+    T: ToDer,
+{
+    fn to_der_len(&self) -> Result<usize> {
+        let mut len = 0;
+        for t in self.items.iter() {
+            len += t.to_der_len()?;
+        }
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
+        Ok(header.to_der_len()? + len)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let mut len = 0;
+        for t in self.items.iter() {
+            len += t.to_der_len().map_err(|_| SerializeError::InvalidLength)?;
+        }
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let mut sz = 0;
+        for t in self.items.iter() {
+            sz += t.write_der(writer)?;
+        }
+        Ok(sz)
+    }
+}
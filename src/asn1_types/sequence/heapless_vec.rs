@@ -0,0 +1,92 @@
+use crate::*;
+use core::convert::TryFrom;
+use heapless::Vec;
+
+/// `SEQUENCE SIZE (0..N) OF`, using a fixed-capacity vector (no allocation)
+///
+/// Parsing returns a `SizeConstraintFailed` error if the number of items exceeds the capacity.
+impl<'a, T, const N: usize> TryFrom<Any<'a>> for Vec<T, N>
+where
+    T: FromBer<'a>,
+{
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Self> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        let mut items = Vec::new();
+        let mut count = 0;
+        for item in SequenceIterator::<T, BerParser>::new(any.data) {
+            // ignore push error here, so the total count can be reported
+            let _ = items.push(item?);
+            count += 1;
+        }
+        if count > N {
+            return Err(Error::SizeConstraintFailed {
+                min: 0,
+                max: N,
+                actual: count,
+            });
+        }
+        Ok(items)
+    }
+}
+
+impl<T, const N: usize> CheckDerConstraints for Vec<T, N>
+where
+    T: CheckDerConstraints,
+{
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        for item in SequenceIterator::<Any, DerParser>::new(any.data) {
+            let item = item?;
+            T::check_constraints(&item)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> DerAutoDerive for Vec<T, N> {}
+
+impl<T, const N: usize> Tagged for Vec<T, N> {
+    const TAG: Tag = Tag::Sequence;
+}
+
+impl<T, const N: usize> Asn1Size for Vec<T, N> {
+    fn asn1_size(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, const N: usize> ToDer for Vec<T, N>
+where
+    T: ToDer,
+{
+    fn to_der_len(&self) -> Result<usize> {
+        let mut len = 0;
+        for t in self.iter() {
+            len += t.to_der_len()?;
+        }
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
+        Ok(header.to_der_len()? + len)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let mut len = 0;
+        for t in self.iter() {
+            len += t.to_der_len().map_err(|_| SerializeError::InvalidLength)?;
+        }
+        let header = Header::new(Class::Universal, true, Self::TAG, Length::Definite(len));
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let mut sz = 0;
+        for t in self.iter() {
+            sz += t.write_der(writer)?;
+        }
+        Ok(sz)
+    }
+}
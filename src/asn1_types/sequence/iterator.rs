@@ -1,10 +1,10 @@
-use crate::{ASN1Parser, BerParser, DerParser, Error, FromBer, FromDer};
+use crate::{ASN1Parser, BerParser, CerParser, DerParser, Error, FromBer, FromCer, FromDer};
 use core::marker::PhantomData;
 
 /// An Iterator over binary data, parsing elements of type `T`
 ///
 /// This helps parsing `SEQUENCE OF` items of type `T`. The type of parser
-/// (BER/DER) is specified using the generic parameter `F` of this struct.
+/// (BER/DER/CER) is specified using the generic parameter `F` of this struct.
 ///
 /// Note: the iterator must start on the sequence *contents*, not the sequence itself.
 ///
@@ -104,3 +104,32 @@ where
         }
     }
 }
+
+impl<'a, T, E> Iterator for SequenceIterator<'a, T, CerParser, E>
+where
+    T: FromCer<'a, E>,
+    E: From<Error>,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.has_error || self.data.is_empty() {
+            return None;
+        }
+        match T::from_cer(self.data) {
+            Ok((rem, obj)) => {
+                self.data = rem;
+                Some(Ok(obj))
+            }
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+                self.has_error = true;
+                Some(Err(e))
+            }
+
+            Err(nom::Err::Incomplete(n)) => {
+                self.has_error = true;
+                Some(Err(Error::Incomplete(n).into()))
+            }
+        }
+    }
+}
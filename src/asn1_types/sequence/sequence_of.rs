@@ -127,6 +127,23 @@ where
 
 impl<T> DerAutoDerive for SequenceOf<T> {}
 
+impl<T> CheckCerConstraints for SequenceOf<T>
+where
+    T: CheckCerConstraints,
+{
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        for item in SequenceIterator::<Any, CerParser>::new(any.data) {
+            let item = item?;
+            T::check_cer_constraints(&item)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> CerAutoDerive for SequenceOf<T> {}
+
 impl<T> Tagged for SequenceOf<T> {
     const TAG: Tag = Tag::Sequence;
 }
@@ -148,3 +165,13 @@ where
         self.items.write_der_content(writer)
     }
 }
+
+#[cfg(feature = "std")]
+impl<T> ToCer for SequenceOf<T>
+where
+    T: ToCer,
+{
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.items.write_cer(writer)
+    }
+}
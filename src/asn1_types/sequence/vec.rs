@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::cer::write_cer_constructed;
 use crate::*;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
@@ -71,6 +73,21 @@ where
     }
 }
 
+impl<T> CheckCerConstraints for Vec<T>
+where
+    T: CheckCerConstraints,
+{
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        for item in SequenceIterator::<Any, CerParser>::new(any.data) {
+            let item = item?;
+            <T as CheckCerConstraints>::check_cer_constraints(&item)?;
+        }
+        Ok(())
+    }
+}
+
 impl<T> Tagged for Vec<T> {
     const TAG: Tag = Tag::Sequence;
 }
@@ -105,6 +122,24 @@ where
     }
 }
 
+/// manual impl of FromCer, so we do not need to require TryFrom<Any> + CheckCerConstraints
+impl<'a, T, E> FromCer<'a, E> for Vec<T>
+where
+    T: FromCer<'a, E>,
+    E: From<Error>,
+{
+    fn from_cer(bytes: &'a [u8]) -> ParseResult<Self, E> {
+        let (rem, any) = Any::from_cer(bytes).map_err(Err::convert)?;
+        any.header
+            .assert_tag(Self::TAG)
+            .map_err(|e| Err::Error(e.into()))?;
+        let v = SequenceIterator::<T, CerParser, E>::new(any.data)
+            .collect::<Result<Vec<T>, E>>()
+            .map_err(Err::Error)?;
+        Ok((rem, v))
+    }
+}
+
 #[cfg(feature = "std")]
 impl<T> ToDer for Vec<T>
 where
@@ -136,3 +171,19 @@ where
         Ok(sz)
     }
 }
+
+#[cfg(feature = "std")]
+impl<T> ToCer for Vec<T>
+where
+    T: ToCer,
+{
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        write_cer_constructed(writer, Class::Universal, Self::TAG, |writer| {
+            let mut sz = 0;
+            for t in self.iter() {
+                sz += t.write_cer(writer)?;
+            }
+            Ok(sz)
+        })
+    }
+}
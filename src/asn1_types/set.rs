@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::cer::write_cer_constructed;
 use crate::*;
 use alloc::borrow::Cow;
 use alloc::vec::Vec;
@@ -207,6 +209,16 @@ impl<'a> Set<'a> {
         SetIterator::new(&self.content)
     }
 
+    /// Return an iterator over the set content, attempting to decode objects as CER
+    ///
+    /// This method can be used when all objects from the set have the same type.
+    pub fn cer_iter<T, E>(&'a self) -> SetIterator<'a, T, CerParser, E>
+    where
+        T: FromCer<'a, E>,
+    {
+        SetIterator::new(&self.content)
+    }
+
     /// Attempt to parse the set as a `SET OF` items (BER), and return the parsed items as a `Vec`.
     pub fn ber_set_of<T, E>(&'a self) -> Result<Vec<T>, E>
     where
@@ -323,6 +335,14 @@ impl<'a> CheckDerConstraints for Set<'a> {
 
 impl<'a> DerAutoDerive for Set<'a> {}
 
+impl<'a> CheckCerConstraints for Set<'a> {
+    fn check_cer_constraints(_any: &Any) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> CerAutoDerive for Set<'a> {}
+
 impl<'a> Tagged for Set<'a> {
     const TAG: Tag = Tag::Set;
 }
@@ -356,6 +376,16 @@ impl ToDer for Set<'_> {
     }
 }
 
+/// The content is written unchanged, so it must already be encoded using CER
+#[cfg(feature = "std")]
+impl ToCer for Set<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        write_cer_constructed(writer, Class::Universal, Self::TAG, |writer| {
+            writer.write(&self.content).map_err(Into::into)
+        })
+    }
+}
+
 #[cfg(feature = "std")]
 impl<'a> Set<'a> {
     /// Attempt to create a `Set` from an iterator over serializable objects (to DER)
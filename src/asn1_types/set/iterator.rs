@@ -3,7 +3,7 @@ pub use crate::{Error, SequenceIterator};
 /// An Iterator over binary data, parsing elements of type `T`
 ///
 /// This helps parsing `SET OF` items of type `T`. The type of parser
-/// (BER/DER) is specified using the generic parameter `F` of this struct.
+/// (BER/DER/CER) is specified using the generic parameter `F` of this struct.
 ///
 /// Note: the iterator must start on the set *contents*, not the set itself.
 ///
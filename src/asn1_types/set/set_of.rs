@@ -1,3 +1,4 @@
+use crate::cer::*;
 use crate::*;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
@@ -127,6 +128,19 @@ where
 
 impl<T> DerAutoDerive for SetOf<T> {}
 
+impl<T> CheckCerConstraints for SetOf<T>
+where
+    T: CheckCerConstraints,
+{
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        any.tag().assert_eq(Self::TAG)?;
+        any.header.assert_constructed()?;
+        check_cer_set_of(any, T::check_cer_constraints)
+    }
+}
+
+impl<T> CerAutoDerive for SetOf<T> {}
+
 impl<T> Tagged for SetOf<T> {
     const TAG: Tag = Tag::Set;
 }
@@ -148,3 +162,26 @@ where
         self.items.write_der_content(writer)
     }
 }
+
+#[cfg(feature = "std")]
+impl<T> ToCer for SetOf<T>
+where
+    T: ToCer,
+{
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        // X.690 section 9.3: elements are sorted by their encoding
+        let mut encodings = self
+            .items
+            .iter()
+            .map(|item| item.to_cer_vec())
+            .collect::<SerializeResult<Vec<_>>>()?;
+        encodings.sort_by(|a, b| cmp_set_of_encodings(a, b));
+        write_cer_constructed(writer, Class::Universal, Self::TAG, |writer| {
+            let mut sz = 0;
+            for encoded in &encodings {
+                sz += writer.write(encoded)?;
+            }
+            Ok(sz)
+        })
+    }
+}
@@ -0,0 +1,237 @@
+use crate::*;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::Deref;
+
+/// Size of an object, as used by ASN.1 `SIZE` constraints
+///
+/// The size is the number of bytes for `OCTET STRING`, the number of bits for `BIT STRING`,
+/// the number of characters for strings, and the number of elements for `SEQUENCE OF` and
+/// `SET OF`.
+pub trait Asn1Size {
+    /// Return the size of the object
+    fn asn1_size(&self) -> usize;
+}
+
+impl Asn1Size for &'_ [u8] {
+    fn asn1_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<const N: usize> Asn1Size for [u8; N] {
+    fn asn1_size(&self) -> usize {
+        N
+    }
+}
+
+impl Asn1Size for OctetString<'_> {
+    fn asn1_size(&self) -> usize {
+        self.as_ref().len()
+    }
+}
+
+impl Asn1Size for BitString<'_> {
+    fn asn1_size(&self) -> usize {
+        (self.data.len() * 8).saturating_sub(self.unused_bits as usize)
+    }
+}
+
+impl Asn1Size for &'_ str {
+    fn asn1_size(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Asn1Size for String {
+    fn asn1_size(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+macro_rules! impl_asn1_size_string {
+    ($($name:ident),*) => {
+        $(
+            impl Asn1Size for $name<'_> {
+                fn asn1_size(&self) -> usize {
+                    <Self as AsRef<str>>::as_ref(self).chars().count()
+                }
+            }
+        )*
+    };
+}
+
+impl_asn1_size_string!(
+    BmpString,
+    GeneralString,
+    GraphicString,
+    Ia5String,
+    NumericString,
+    PrintableString,
+    TeletexString,
+    UniversalString,
+    Utf8String,
+    VideotexString,
+    VisibleString
+);
+
+impl<T> Asn1Size for Vec<T> {
+    fn asn1_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Asn1Size for SequenceOf<T> {
+    fn asn1_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const N: usize> Asn1Size for FixedSequenceOf<T, N> {
+    fn asn1_size(&self) -> usize {
+        N
+    }
+}
+
+impl<T> Asn1Size for SetOf<T> {
+    fn asn1_size(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Wrapper for objects with a size constraint (`T (SIZE (MIN..MAX))`)
+///
+/// The size of the inner object (see [`Asn1Size`]) is checked when parsing, or when building
+/// the object, and a `SizeConstraintFailed` error is returned if it is out of range.
+/// Since the inner object cannot be modified, encoding always produces a valid object.
+///
+/// Use `usize::MAX` for an unbounded range, and the same value for `MIN` and `MAX` for
+/// fixed sizes.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{FromDer, Integer, SizeConstrained};
+///
+/// // OCTET STRING (SIZE (4))
+/// type Digest<'a> = SizeConstrained<&'a [u8], 4, 4>;
+/// let (_, d) = Digest::from_der(&[0x04, 0x04, 0x01, 0x02, 0x03, 0x04]).expect("parsing failed");
+/// assert_eq!(d.len(), 4);
+/// assert!(Digest::from_der(&[0x04, 0x01, 0x01]).is_err());
+///
+/// // SEQUENCE SIZE (1..MAX) OF INTEGER
+/// type Integers<'a> = SizeConstrained<Vec<Integer<'a>>, 1, { usize::MAX }>;
+/// assert!(Integers::from_der(&[0x30, 0x00]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SizeConstrained<T, const MIN: usize, const MAX: usize>(T);
+
+impl<T, const MIN: usize, const MAX: usize> SizeConstrained<T, MIN, MAX>
+where
+    T: Asn1Size,
+{
+    /// Build a new `SizeConstrained` object, checking that the size of `inner` is in range
+    pub fn new(inner: T) -> Result<Self> {
+        Self::check_size(&inner)?;
+        Ok(SizeConstrained(inner))
+    }
+
+    fn check_size(inner: &T) -> Result<()> {
+        let actual = inner.asn1_size();
+        if actual < MIN || actual > MAX {
+            return Err(Error::SizeConstraintFailed {
+                min: MIN,
+                max: MAX,
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> SizeConstrained<T, MIN, MAX> {
+    /// Return a reference to the inner object
+    #[inline]
+    pub const fn inner(&self) -> &T {
+        &self.0
+    }
+
+    /// Return the inner object
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> Deref for SizeConstrained<T, MIN, MAX> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> AsRef<T> for SizeConstrained<T, MIN, MAX> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'a, T, const MIN: usize, const MAX: usize> TryFrom<Any<'a>> for SizeConstrained<T, MIN, MAX>
+where
+    T: TryFrom<Any<'a>, Error = Error>,
+    T: Asn1Size,
+{
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Self> {
+        let inner = T::try_from(any)?;
+        Self::new(inner)
+    }
+}
+
+/// manual impl of FromDer, so we do not need to require TryFrom<Any> + CheckDerConstraints
+impl<'a, T, E, const MIN: usize, const MAX: usize> FromDer<'a, E> for SizeConstrained<T, MIN, MAX>
+where
+    T: FromDer<'a, E>,
+    T: Asn1Size,
+    E: From<Error>,
+{
+    fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self, E> {
+        let (rem, inner) = T::from_der(bytes)?;
+        let obj = Self::new(inner).map_err(|e| Err::Error(e.into()))?;
+        Ok((rem, obj))
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> DynTagged for SizeConstrained<T, MIN, MAX>
+where
+    T: DynTagged,
+{
+    fn tag(&self) -> Tag {
+        self.0.tag()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, const MIN: usize, const MAX: usize> ToDer for SizeConstrained<T, MIN, MAX>
+where
+    T: ToDer,
+{
+    fn to_der_len(&self) -> Result<usize> {
+        self.0.to_der_len()
+    }
+
+    fn write_der(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.0.write_der(writer)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.0.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.0.write_der_content(writer)
+    }
+}
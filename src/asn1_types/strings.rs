@@ -1,4 +1,7 @@
 mod bmpstring;
+#[macro_use]
+mod charsets;
+mod directorystring;
 mod generalstring;
 mod graphicstring;
 mod ia5string;
@@ -14,6 +17,7 @@ mod visiblestring;
 
 pub use self::str::*;
 pub use bmpstring::*;
+pub use directorystring::*;
 pub use generalstring::*;
 pub use graphicstring::*;
 pub use ia5string::*;
@@ -108,10 +112,17 @@ macro_rules! asn1_string {
                 use crate::traits::Tagged;
                 use alloc::borrow::Cow;
                 any.tag().assert_eq(Self::TAG)?;
-                <$name>::test_valid_charset(any.data)?;
-
-                let s = alloc::str::from_utf8(any.data)?;
-                let data = Cow::Borrowed(s);
+                // X.690 section 8.23.6: constructed strings are made of OCTET STRING segments
+                let data = match any.string_content($crate::Tag::OctetString)? {
+                    Cow::Borrowed(b) => {
+                        <$name>::test_valid_charset(b)?;
+                        Cow::Borrowed(alloc::str::from_utf8(b)?)
+                    }
+                    Cow::Owned(v) => {
+                        <$name>::test_valid_charset(&v)?;
+                        Cow::Owned(alloc::string::String::from_utf8(v)?)
+                    }
+                };
                 Ok($name { data })
             }
         }
@@ -125,6 +136,16 @@ macro_rules! asn1_string {
 
         impl $crate::DerAutoDerive for $name<'_> {}
 
+        impl<'a> $crate::CheckCerConstraints for $name<'a> {
+            fn check_cer_constraints(any: &$crate::Any) -> $crate::Result<()> {
+                // X.690 section 9.2
+                $crate::cer::cer_string_segments(any, $crate::Tag::OctetString)?;
+                Ok(())
+            }
+        }
+
+        impl $crate::CerAutoDerive for $name<'_> {}
+
         impl<'a> $crate::Tagged for $name<'a> {
             const TAG: $crate::Tag = $crate::Tag::$name;
         }
@@ -164,6 +185,17 @@ macro_rules! asn1_string {
                 writer.write(self.data.as_bytes()).map_err(Into::into)
             }
         }
+
+        #[cfg(feature = "std")]
+        impl $crate::ToCer for $name<'_> {
+            fn write_cer(
+                &self,
+                writer: &mut dyn std::io::Write,
+            ) -> $crate::SerializeResult<usize> {
+                use $crate::Tagged;
+                $crate::cer::write_cer_string(writer, Self::TAG, self.data.as_bytes())
+            }
+        }
     };
     ($name:ident) => {
         asn1_string!(IMPL $name, stringify!($name));
@@ -1,6 +1,7 @@
 // do not use the `asn1_string` macro, since types are not the same
 // X.680 section 37.15
 
+use crate::cer::*;
 use crate::*;
 use alloc::borrow::Cow;
 use alloc::string::{String, ToString};
@@ -8,8 +9,8 @@ use alloc::vec::Vec;
 
 /// ASN.1 `BMPSTRING` type
 ///
-/// Note: parsing a `BmpString` allocates memory since the UTF-16 to UTF-8 conversion requires a memory allocation.
-/// (see `String::from_utf16` method).
+/// Note: parsing a `BmpString` allocates memory since the UCS-2 to UTF-8 conversion requires a memory allocation.
+/// See [`RawBmpString`] for a type borrowing the encoded bytes.
 #[derive(Debug, PartialEq)]
 pub struct BmpString<'a> {
     pub(crate) data: Cow<'a, str>,
@@ -52,19 +53,11 @@ impl<'a> core::convert::TryFrom<Any<'a>> for BmpString<'a> {
 
     fn try_from(any: Any<'a>) -> Result<BmpString<'a>> {
         any.tag().assert_eq(Self::TAG)?;
+        // X.690 section 8.23.6: constructed strings are made of OCTET STRING segments
+        let content = any.string_content(Tag::OctetString)?;
 
-        // read slice as big-endian UTF-16 string
-        let v = &any
-            .data
-            .chunks(2)
-            .map(|s| match s {
-                [a, b] => ((*a as u16) << 8) | (*b as u16),
-                [a] => *a as u16,
-                _ => unreachable!(),
-            })
-            .collect::<Vec<_>>();
-
-        let s = String::from_utf16(v)?;
+        // read slice as big-endian UCS-2 string
+        let s = decode_ucs2(&content).collect::<Result<String>>()?;
         let data = Cow::Owned(s);
 
         Ok(BmpString { data })
@@ -80,29 +73,198 @@ impl<'a> CheckDerConstraints for BmpString<'a> {
 
 impl DerAutoDerive for BmpString<'_> {}
 
+impl<'a> CheckCerConstraints for BmpString<'a> {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        // X.690 section 9.2
+        cer_string_segments(any, Tag::OctetString)?;
+        Ok(())
+    }
+}
+
+impl CerAutoDerive for BmpString<'_> {}
+
 impl<'a> Tagged for BmpString<'a> {
     const TAG: Tag = Tag::BmpString;
 }
 
 impl<'a> TestValidCharset for BmpString<'a> {
     fn test_valid_charset(i: &[u8]) -> Result<()> {
-        if i.len() % 2 != 0 {
-            return Err(Error::StringInvalidCharset);
+        decode_ucs2(i).try_for_each(|c| c.map(|_| ()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToDer for BmpString<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = ucs2_len(&self.data)?;
+        if sz < 127 {
+            // 1 (class+tag) + 1 (length) + len
+            Ok(2 + sz)
+        } else {
+            // 1 (class+tag) + n (length) + len
+            let n = Length::Definite(sz).to_der_len()?;
+            Ok(1 + n + sz)
         }
-        let iter = i.chunks(2).map(|s| ((s[0] as u16) << 8) | (s[1] as u16));
-        for c in char::decode_utf16(iter) {
-            if c.is_err() {
-                return Err(Error::StringInvalidCharset);
-            }
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let header = Header::new(
+            Class::Universal,
+            false,
+            Self::TAG,
+            Length::Definite(ucs2_len(&self.data)?),
+        );
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let v = encode_ucs2(&self.data)?;
+        writer.write(&v).map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToCer for BmpString<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        write_cer_string(writer, Self::TAG, &encode_ucs2(&self.data)?)
+    }
+}
+
+/// Return the length of the string encoded as UCS-2
+fn ucs2_len(s: &str) -> Result<usize> {
+    s.chars().try_fold(0, |len, c| {
+        ucs2_char(c)?;
+        Ok(len + 2)
+    })
+}
+
+/// Encode string as big-endian UCS-2
+///
+/// Characters outside the Basic Multilingual Plane cannot be represented (UTF-16 would use
+/// surrogate pairs, which are not valid in a `BMPString`), and are rejected.
+fn encode_ucs2(s: &str) -> Result<Vec<u8>> {
+    let mut v = Vec::with_capacity(2 * s.len());
+    for c in s.chars() {
+        v.extend_from_slice(&ucs2_char(c)?.to_be_bytes());
+    }
+    Ok(v)
+}
+
+/// Decode big-endian UCS-2 bytes
+///
+/// Surrogates (0xD800-0xDFFF) are not characters in UCS-2, and are rejected, as well as a
+/// trailing odd byte.
+fn decode_ucs2(i: &[u8]) -> impl Iterator<Item = Result<char>> + '_ {
+    i.chunks(2).map(|s| match s {
+        [a, b] => {
+            char::from_u32(u32::from(*a) << 8 | u32::from(*b)).ok_or(Error::StringInvalidCharset)
         }
+        _ => Err(Error::StringInvalidCharset),
+    })
+}
+
+fn ucs2_char(c: char) -> Result<u16> {
+    let c = u32::from(c);
+    if c > 0xffff {
+        return Err(Error::StringInvalidCharset);
+    }
+    Ok(c as u16)
+}
+
+/// ASN.1 `BMPSTRING` type, keeping the encoded (UCS-2) bytes
+///
+/// Contrary to [`BmpString`], parsing this object does not allocate memory: the content is
+/// validated and borrowed, and characters are decoded only when iterating (see
+/// [`RawBmpString::chars`]).
+///
+/// The original bytes are kept unchanged, so comparing or encoding this object uses the exact
+/// bytes that were parsed.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{FromDer, RawBmpString, ToDer};
+///
+/// let bytes = &[0x1e, 0x04, 0x00, 0x55, 0x00, 0x73];
+/// let (_, s) = RawBmpString::from_der(bytes).expect("parsing failed");
+///
+/// assert_eq!(s.as_bytes(), &bytes[2..]);
+/// assert!(s.chars().eq("Us".chars()));
+/// # #[cfg(feature = "std")]
+/// assert_eq!(s.to_der_vec().unwrap(), bytes);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawBmpString<'a> {
+    pub(crate) data: Cow<'a, [u8]>,
+}
+
+impl<'a> RawBmpString<'a> {
+    /// Build a `RawBmpString` from encoded (big-endian UCS-2) bytes
+    ///
+    /// Returns an error if `bytes` is not valid UCS-2 (odd length, or surrogate code points).
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        BmpString::test_valid_charset(bytes)?;
+        Ok(RawBmpString {
+            data: Cow::Borrowed(bytes),
+        })
+    }
+
+    /// Return the encoded (big-endian UCS-2) bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return an iterator over the decoded characters
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        // content was validated when building object, so decoding cannot fail
+        decode_ucs2(&self.data).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Decode the string (this allocates memory)
+    pub fn string(&self) -> String {
+        self.chars().collect()
+    }
+}
+
+impl core::convert::TryFrom<&str> for RawBmpString<'_> {
+    type Error = Error;
+
+    /// Encode the string
+    ///
+    /// Returns an error if the string contains characters outside the Basic Multilingual Plane.
+    fn try_from(s: &str) -> Result<Self> {
+        Ok(RawBmpString {
+            data: Cow::Owned(encode_ucs2(s)?),
+        })
+    }
+}
+
+impl<'a> core::convert::TryFrom<Any<'a>> for RawBmpString<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<RawBmpString<'a>> {
+        any.tag().assert_eq(Self::TAG)?;
+        RawBmpString::new(any.data)
+    }
+}
+
+impl<'a> CheckDerConstraints for RawBmpString<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.header.assert_primitive()?;
         Ok(())
     }
 }
 
+impl DerAutoDerive for RawBmpString<'_> {}
+
+impl<'a> Tagged for RawBmpString<'a> {
+    const TAG: Tag = Tag::BmpString;
+}
+
 #[cfg(feature = "std")]
-impl ToDer for BmpString<'_> {
+impl ToDer for RawBmpString<'_> {
     fn to_der_len(&self) -> Result<usize> {
-        let sz = self.data.as_bytes().len();
+        let sz = self.data.len();
         if sz < 127 {
             // 1 (class+tag) + 1 (length) + len
             Ok(2 + sz)
@@ -118,12 +280,12 @@ impl ToDer for BmpString<'_> {
             Class::Universal,
             false,
             Self::TAG,
-            Length::Definite(self.data.as_bytes().len()),
+            Length::Definite(self.data.len()),
         );
-        header.write_der_header(writer).map_err(Into::into)
+        header.write_der_header(writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        writer.write(self.data.as_bytes()).map_err(Into::into)
+        writer.write(&self.data).map_err(Into::into)
     }
 }
@@ -0,0 +1,441 @@
+//! Character set conversions for string types that are not a subset of Unicode encodings
+//!
+//! - `TeletexString` (T.61) and `VideotexString` (T.100/T.101) are decoded using the
+//!   ISO 6937 repertoire: the primary set is ASCII (including C0 controls), and the
+//!   supplementary set (0xA0-0xFF) contains symbols, special letters and non-spacing
+//!   diacritical marks.
+//! - `GeneralString` is decoded as ISO 2022, with ASCII as initial G0 set. Escape sequences
+//!   designating ISO 8859-1 as G1 (`ESC - A`) and switching to/from UTF-8 (`ESC % G`, `ESC % @`)
+//!   are supported. Returning from UTF-8 restores the previously designated sets.
+
+use crate::{Error, Result};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Declare a string type, storing both the decoded (UTF-8) value and the original bytes
+///
+/// `$decode` converts the encoded bytes to a string, and `$encode` is the reverse operation
+/// (used only when the object was not built from encoded bytes).
+macro_rules! asn1_decoded_string {
+    (IMPL $name:ident, $sname:expr, $decode:path, $encode:path) => {
+        #[doc="ASN.1 restricted character string type (`"]
+        #[doc = $sname]
+        #[doc = "`)"]
+        ///
+        /// The string is decoded to Unicode when parsing. The original bytes are kept, so the
+        /// object is encoded back exactly as it was read.
+        ///
+        /// Note: the decoded string is allocated only if the original bytes are not ASCII.
+        #[derive(Debug)]
+        pub struct $name<'a> {
+            pub(crate) data: alloc::borrow::Cow<'a, str>,
+            pub(crate) raw: Option<alloc::borrow::Cow<'a, [u8]>>,
+        }
+
+        impl<'a> $name<'a> {
+            pub const fn new(s: &'a str) -> Self {
+                $name {
+                    data: alloc::borrow::Cow::Borrowed(s),
+                    raw: None,
+                }
+            }
+
+            pub fn string(&self) -> String {
+                use alloc::string::ToString;
+                self.data.to_string()
+            }
+
+            /// Return the original (encoded) bytes, if this object was parsed
+            ///
+            /// Returns `None` if the object was built from a string.
+            pub fn raw_bytes(&self) -> Option<&[u8]> {
+                self.raw.as_deref()
+            }
+
+            /// Get the encoded bytes: the original bytes if present, or the encoded string
+            fn encoded(&self) -> $crate::Result<alloc::borrow::Cow<'_, [u8]>> {
+                match &self.raw {
+                    Some(raw) => Ok(alloc::borrow::Cow::Borrowed(raw.as_ref())),
+                    None => $encode(&self.data).map(alloc::borrow::Cow::Owned),
+                }
+            }
+        }
+
+        /// Objects are equal if their decoded values are equal
+        impl PartialEq for $name<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                self.data == other.data
+            }
+        }
+
+        impl<'a> AsRef<str> for $name<'a> {
+            fn as_ref(&self) -> &str {
+                &self.data
+            }
+        }
+
+        impl<'a> From<&'a str> for $name<'a> {
+            fn from(s: &'a str) -> Self {
+                Self::new(s)
+            }
+        }
+
+        impl From<String> for $name<'_> {
+            fn from(s: String) -> Self {
+                Self {
+                    data: alloc::borrow::Cow::Owned(s),
+                    raw: None,
+                }
+            }
+        }
+
+        impl<'a> core::convert::TryFrom<$crate::Any<'a>> for $name<'a> {
+            type Error = $crate::Error;
+
+            fn try_from(any: $crate::Any<'a>) -> $crate::Result<$name<'a>> {
+                use core::convert::TryFrom;
+                TryFrom::try_from(&any)
+            }
+        }
+
+        impl<'a, 'b> core::convert::TryFrom<&'b $crate::Any<'a>> for $name<'a> {
+            type Error = $crate::Error;
+
+            fn try_from(any: &'b $crate::Any<'a>) -> $crate::Result<$name<'a>> {
+                use crate::traits::Tagged;
+                use alloc::borrow::Cow;
+                any.tag().assert_eq(Self::TAG)?;
+
+                // X.690 section 8.23.6: constructed strings are made of OCTET STRING segments
+                match any.string_content($crate::Tag::OctetString)? {
+                    Cow::Borrowed(b) => {
+                        let data = $decode(b)?;
+                        let raw = Some(Cow::Borrowed(b));
+                        Ok($name { data, raw })
+                    }
+                    Cow::Owned(v) => {
+                        let data = Cow::Owned($decode(&v)?.into_owned());
+                        let raw = Some(Cow::Owned(v));
+                        Ok($name { data, raw })
+                    }
+                }
+            }
+        }
+
+        impl<'a> $crate::CheckDerConstraints for $name<'a> {
+            fn check_constraints(any: &$crate::Any) -> $crate::Result<()> {
+                any.header.assert_primitive()?;
+                Ok(())
+            }
+        }
+
+        impl $crate::DerAutoDerive for $name<'_> {}
+
+        impl<'a> $crate::CheckCerConstraints for $name<'a> {
+            fn check_cer_constraints(any: &$crate::Any) -> $crate::Result<()> {
+                // X.690 section 9.2
+                $crate::cer::cer_string_segments(any, $crate::Tag::OctetString)?;
+                Ok(())
+            }
+        }
+
+        impl $crate::CerAutoDerive for $name<'_> {}
+
+        impl<'a> $crate::Tagged for $name<'a> {
+            const TAG: $crate::Tag = $crate::Tag::$name;
+        }
+
+        impl<'a> $crate::TestValidCharset for $name<'a> {
+            fn test_valid_charset(i: &[u8]) -> $crate::Result<()> {
+                $decode(i).map(|_| ())
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl $crate::ToDer for $name<'_> {
+            fn to_der_len(&self) -> Result<usize> {
+                let sz = self.encoded()?.len();
+                if sz < 127 {
+                    // 1 (class+tag) + 1 (length) + len
+                    Ok(2 + sz)
+                } else {
+                    // 1 (class+tag) + n (length) + len
+                    let n = $crate::Length::Definite(sz).to_der_len()?;
+                    Ok(1 + n + sz)
+                }
+            }
+
+            fn write_der_header(
+                &self,
+                writer: &mut dyn std::io::Write,
+            ) -> $crate::SerializeResult<usize> {
+                use $crate::Tagged;
+                let header = $crate::Header::new(
+                    $crate::Class::Universal,
+                    false,
+                    Self::TAG,
+                    $crate::Length::Definite(self.encoded()?.len()),
+                );
+                header.write_der_header(writer)
+            }
+
+            fn write_der_content(
+                &self,
+                writer: &mut dyn std::io::Write,
+            ) -> $crate::SerializeResult<usize> {
+                writer.write(&self.encoded()?).map_err(Into::into)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl $crate::ToCer for $name<'_> {
+            fn write_cer(
+                &self,
+                writer: &mut dyn std::io::Write,
+            ) -> $crate::SerializeResult<usize> {
+                use $crate::Tagged;
+                $crate::cer::write_cer_string(writer, Self::TAG, &self.encoded()?)
+            }
+        }
+    };
+    ($name:ident, $decode:path, $encode:path) => {
+        asn1_decoded_string!(IMPL $name, stringify!($name), $decode, $encode);
+    };
+}
+
+/// ISO 6937 supplementary set (0xA0-0xFF)
+///
+/// Non-spacing diacritical marks (0xC1-0xCF) and unassigned positions are `'\0'`.
+/// Positions 0xA4 and 0xA6 hold the T.61 `$` and `#` characters.
+#[rustfmt::skip]
+const T61_SUPPLEMENTARY: [char; 96] = [
+    // 0xA0
+    '\u{a0}', '¡', '¢', '£', '$', '¥', '#', '§',
+    '¤', '\u{2018}', '\u{201c}', '«', '\u{2190}', '\u{2191}', '\u{2192}', '\u{2193}',
+    // 0xB0
+    '°', '±', '²', '³', '×', 'µ', '¶', '·',
+    '÷', '\u{2019}', '\u{201d}', '»', '¼', '½', '¾', '¿',
+    // 0xC0 (diacritical marks)
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    // 0xD0
+    '\u{2015}', '¹', '®', '©', '\u{2122}', '\u{266a}', '¬', '¦',
+    '\0', '\0', '\0', '\0', '\u{215b}', '\u{215c}', '\u{215d}', '\u{215e}',
+    // 0xE0
+    '\u{2126}', 'Æ', 'Đ', 'ª', 'Ħ', '\0', 'Ĳ', 'Ŀ',
+    'Ł', 'Ø', 'Œ', 'º', 'Þ', 'Ŧ', 'Ŋ', 'ŉ',
+    // 0xF0
+    'ĸ', 'æ', 'đ', 'ð', 'ħ', 'ı', 'ĳ', 'ŀ',
+    'ł', 'ø', 'œ', 'ß', 'þ', 'ŧ', 'ŋ', '\u{ad}',
+];
+
+/// A T.61 non-spacing diacritical mark
+struct Diacritic {
+    /// Encoded value
+    code: u8,
+    /// Unicode combining character
+    combining: char,
+    /// Spacing form (diacritical mark followed by a space)
+    spacing: char,
+    /// Base letters having a precomposed Unicode character
+    bases: &'static str,
+    /// Precomposed characters, in the same order as `bases`
+    composed: &'static str,
+}
+
+#[rustfmt::skip]
+const T61_DIACRITICS: &[Diacritic] = &[
+    Diacritic { code: 0xc1, combining: '\u{300}', spacing: '`', bases: "AEIOUaeiou", composed: "ÀÈÌÒÙàèìòù" },
+    Diacritic { code: 0xc2, combining: '\u{301}', spacing: '´', bases: "ACEILNORSUYZaceilnorsuyz", composed: "ÁĆÉÍĹŃÓŔŚÚÝŹáćéíĺńóŕśúýź" },
+    Diacritic { code: 0xc3, combining: '\u{302}', spacing: '^', bases: "ACEGHIJOSUWYaceghijosuwy", composed: "ÂĈÊĜĤÎĴÔŜÛŴŶâĉêĝĥîĵôŝûŵŷ" },
+    Diacritic { code: 0xc4, combining: '\u{303}', spacing: '~', bases: "AINOUainou", composed: "ÃĨÑÕŨãĩñõũ" },
+    Diacritic { code: 0xc5, combining: '\u{304}', spacing: '¯', bases: "AEIOUaeiou", composed: "ĀĒĪŌŪāēīōū" },
+    Diacritic { code: 0xc6, combining: '\u{306}', spacing: '˘', bases: "AGUagu", composed: "ĂĞŬăğŭ" },
+    Diacritic { code: 0xc7, combining: '\u{307}', spacing: '˙', bases: "CEGIZcegz", composed: "ĊĖĠİŻċėġż" },
+    Diacritic { code: 0xc8, combining: '\u{308}', spacing: '¨', bases: "AEIOUYaeiouy", composed: "ÄËÏÖÜŸäëïöüÿ" },
+    // T.61 umlaut (same rendering as diaeresis)
+    Diacritic { code: 0xc9, combining: '\u{308}', spacing: '¨', bases: "AEIOUYaeiouy", composed: "ÄËÏÖÜŸäëïöüÿ" },
+    Diacritic { code: 0xca, combining: '\u{30a}', spacing: '˚', bases: "AUau", composed: "ÅŮåů" },
+    Diacritic { code: 0xcb, combining: '\u{327}', spacing: '¸', bases: "CGKLNRSTcgklnrst", composed: "ÇĢĶĻŅŖŞŢçģķļņŗşţ" },
+    // T.61 non-spacing underline
+    Diacritic { code: 0xcc, combining: '\u{332}', spacing: '_', bases: "", composed: "" },
+    Diacritic { code: 0xcd, combining: '\u{30b}', spacing: '˝', bases: "OUou", composed: "ŐŰőű" },
+    Diacritic { code: 0xce, combining: '\u{328}', spacing: '˛', bases: "AEIUaeiu", composed: "ĄĘĮŲąęįų" },
+    Diacritic { code: 0xcf, combining: '\u{30c}', spacing: 'ˇ', bases: "CDELNRSTZcdelnrstz", composed: "ČĎĚĽŇŘŠŤŽčďěľňřšťž" },
+];
+
+/// Return true if `b` is a C0 control character or a character of the primary set
+#[inline]
+const fn is_t61_primary(b: u8) -> bool {
+    // the T.61 primary set has the same code points as ASCII
+    b.is_ascii()
+}
+
+/// Decode T.61 (ISO 6937) bytes to a string
+///
+/// A diacritical mark applies to the following character. If the combination has a precomposed
+/// Unicode character it is used, otherwise the base character is followed by the combining mark.
+pub(crate) fn decode_t61(i: &[u8]) -> Result<Cow<'_, str>> {
+    if i.iter().all(|&b| is_t61_primary(b)) {
+        // ASCII, no conversion required
+        let s = core::str::from_utf8(i)?;
+        return Ok(Cow::Borrowed(s));
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut iter = i.iter();
+    while let Some(&b) = iter.next() {
+        match b {
+            b if is_t61_primary(b) => s.push(b as char),
+            0xc1..=0xcf => {
+                let diacritic = T61_DIACRITICS
+                    .iter()
+                    .find(|d| d.code == b)
+                    .ok_or(Error::StringInvalidCharset)?;
+                match iter.next() {
+                    Some(b' ') => s.push(diacritic.spacing),
+                    Some(&base) if base.is_ascii_graphic() => {
+                        let base = base as char;
+                        match diacritic.bases.chars().position(|c| c == base) {
+                            Some(idx) => s.extend(diacritic.composed.chars().nth(idx)),
+                            None => {
+                                s.push(base);
+                                s.push(diacritic.combining);
+                            }
+                        }
+                    }
+                    _ => return Err(Error::StringInvalidCharset),
+                }
+            }
+            0xa0..=0xff => match T61_SUPPLEMENTARY[(b - 0xa0) as usize] {
+                '\0' => return Err(Error::StringInvalidCharset),
+                c => s.push(c),
+            },
+            _ => return Err(Error::StringInvalidCharset),
+        }
+    }
+    Ok(Cow::Owned(s))
+}
+
+/// Encode a string to T.61 (ISO 6937) bytes
+///
+/// Returns an error if a character is not part of the T.61 repertoire. Combining characters
+/// are not recognized, only precomposed characters are encoded using diacritical marks.
+pub(crate) fn encode_t61(s: &str) -> Result<Vec<u8>> {
+    let mut v = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii() {
+            v.push(c as u8);
+        } else if let Some(idx) = T61_SUPPLEMENTARY.iter().position(|&x| x == c && x != '\0') {
+            v.push(0xa0 + idx as u8);
+        } else if let Some((code, base)) = T61_DIACRITICS.iter().find_map(|d| {
+            d.composed
+                .chars()
+                .position(|x| x == c)
+                .and_then(|idx| d.bases.chars().nth(idx))
+                .map(|base| (d.code, base as u8))
+        }) {
+            v.push(code);
+            v.push(base);
+        } else if let Some(d) = T61_DIACRITICS.iter().find(|d| d.spacing == c) {
+            v.push(d.code);
+            v.push(b' ');
+        } else {
+            return Err(Error::StringInvalidCharset);
+        }
+    }
+    Ok(v)
+}
+
+const ESC: u8 = 0x1b;
+
+/// ISO 2022 state when decoding a `GeneralString`
+#[derive(Clone, Copy, PartialEq)]
+enum Iso2022Mode {
+    /// G0 is ASCII, G1 is not designated
+    Ascii,
+    /// G0 is ASCII, G1 is ISO 8859-1 (right part)
+    Latin1,
+    /// UTF-8 (ISO 2022 "other coding system")
+    Utf8,
+}
+
+/// Decode ISO 2022 bytes (`GeneralString`) to a string
+///
+/// The initial state has ASCII as G0, and no G1 set: bytes above 0x7f are invalid unless
+/// a G1 set is designated.
+pub(crate) fn decode_iso2022(i: &[u8]) -> Result<Cow<'_, str>> {
+    if i.iter().all(|&b| b.is_ascii() && b != ESC) {
+        // ASCII, no conversion required
+        let s = core::str::from_utf8(i)?;
+        return Ok(Cow::Borrowed(s));
+    }
+    let mut s = String::with_capacity(i.len());
+    let mut mode = Iso2022Mode::Ascii;
+    // mode restored when returning from UTF-8
+    let mut designated = Iso2022Mode::Ascii;
+    let mut rem = i;
+    while !rem.is_empty() {
+        if mode == Iso2022Mode::Utf8 {
+            // copy everything until ESC % @
+            let end = rem.windows(3).position(|w| w == [ESC, b'%', b'@']);
+            let (utf8, next) = match end {
+                Some(idx) => (&rem[..idx], &rem[idx + 3..]),
+                None => (rem, &rem[rem.len()..]),
+            };
+            s.push_str(core::str::from_utf8(utf8)?);
+            mode = designated;
+            rem = next;
+            continue;
+        }
+        match rem {
+            [ESC, b'(', b'B', r @ ..] => {
+                // G0: ASCII (already designated)
+                rem = r;
+            }
+            [ESC, b'-', b'A', r @ ..] => {
+                mode = Iso2022Mode::Latin1;
+                designated = mode;
+                rem = r;
+            }
+            [ESC, b'%', b'G', r @ ..] => {
+                mode = Iso2022Mode::Utf8;
+                rem = r;
+            }
+            [ESC, ..] => return Err(Error::StringInvalidCharset),
+            [b, r @ ..] if b.is_ascii() => {
+                s.push(*b as char);
+                rem = r;
+            }
+            [b, r @ ..] if mode == Iso2022Mode::Latin1 && *b >= 0xa0 => {
+                // ISO 8859-1 has the same code points as Unicode
+                s.push(*b as char);
+                rem = r;
+            }
+            _ => return Err(Error::StringInvalidCharset),
+        }
+    }
+    Ok(Cow::Owned(s))
+}
+
+/// Encode a string to ISO 2022 bytes (`GeneralString`)
+///
+/// ASCII strings are encoded unchanged. If the string contains characters from ISO 8859-1,
+/// ISO 8859-1 is designated as G1. Otherwise, the string is encoded as UTF-8.
+pub(crate) fn encode_iso2022(s: &str) -> Result<Vec<u8>> {
+    if s.is_ascii() {
+        return Ok(s.as_bytes().to_vec());
+    }
+    let mut v = Vec::with_capacity(s.len() + 3);
+    if s.chars()
+        .all(|c| c.is_ascii() || ('\u{a0}'..='\u{ff}').contains(&c))
+    {
+        v.extend_from_slice(&[ESC, b'-', b'A']);
+        v.extend(s.chars().map(|c| c as u8));
+    } else {
+        v.extend_from_slice(&[ESC, b'%', b'G']);
+        v.extend_from_slice(s.as_bytes());
+        v.extend_from_slice(&[ESC, b'%', b'@']);
+    }
+    Ok(v)
+}
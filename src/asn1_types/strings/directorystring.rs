@@ -0,0 +1,257 @@
+// RFC 5280 section 4.1.2.4
+//
+// DirectoryString ::= CHOICE {
+//       teletexString           TeletexString (SIZE (1..MAX)),
+//       printableString         PrintableString (SIZE (1..MAX)),
+//       universalString         UniversalString (SIZE (1..MAX)),
+//       utf8String              UTF8String (SIZE (1..MAX)),
+//       bmpString               BMPString (SIZE (1..MAX)) }
+
+use crate::*;
+use alloc::string::{String, ToString};
+use core::convert::{TryFrom, TryInto};
+
+/// The `DirectoryString` type (`CHOICE` of string types), used in X.509 and LDAP
+///
+/// When the `stringprep` feature is enabled, values can be prepared using [RFC 4518] rules
+/// (see [`DirectoryString::normalized`]), and compared (see [`DirectoryString::matches`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{DirectoryString, FromDer};
+///
+/// let bytes = &[0x13, 0x04, 0x54, 0x65, 0x73, 0x74];
+/// let (_, s) = DirectoryString::from_der(bytes).expect("parsing failed");
+///
+/// assert!(matches!(s, DirectoryString::PrintableString(_)));
+/// assert_eq!(s.as_ref(), "Test");
+/// ```
+///
+/// [RFC 4518]: https://datatracker.ietf.org/doc/html/rfc4518
+#[derive(Debug, PartialEq)]
+pub enum DirectoryString<'a> {
+    TeletexString(TeletexString<'a>),
+    PrintableString(PrintableString<'a>),
+    UniversalString(UniversalString<'a>),
+    Utf8String(Utf8String<'a>),
+    BmpString(BmpString<'a>),
+}
+
+impl<'a> DirectoryString<'a> {
+    /// Return the decoded value as a `String`
+    pub fn string(&self) -> String {
+        self.as_ref().to_string()
+    }
+}
+
+impl<'a> AsRef<str> for DirectoryString<'a> {
+    fn as_ref(&self) -> &str {
+        match self {
+            DirectoryString::TeletexString(s) => s.as_ref(),
+            DirectoryString::PrintableString(s) => s.as_ref(),
+            DirectoryString::UniversalString(s) => s.as_ref(),
+            DirectoryString::Utf8String(s) => s.as_ref(),
+            DirectoryString::BmpString(s) => s.as_ref(),
+        }
+    }
+}
+
+impl<'a> Choice for DirectoryString<'a> {
+    fn can_decode(tag: Tag) -> bool {
+        matches!(
+            tag,
+            Tag::TeletexString
+                | Tag::PrintableString
+                | Tag::UniversalString
+                | Tag::Utf8String
+                | Tag::BmpString
+        )
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for DirectoryString<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<DirectoryString<'a>> {
+        let s = match any.tag() {
+            Tag::TeletexString => DirectoryString::TeletexString(any.try_into()?),
+            Tag::PrintableString => DirectoryString::PrintableString(any.try_into()?),
+            Tag::UniversalString => DirectoryString::UniversalString(any.try_into()?),
+            Tag::Utf8String => DirectoryString::Utf8String(any.try_into()?),
+            Tag::BmpString => DirectoryString::BmpString(any.try_into()?),
+            tag => return Err(Error::unexpected_tag(None, tag)),
+        };
+        if s.as_ref().is_empty() {
+            // SIZE (1..MAX)
+            return Err(Error::InvalidLength);
+        }
+        Ok(s)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for DirectoryString<'a> {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<DirectoryString<'a>> {
+        TryFrom::try_from(any.clone())
+    }
+}
+
+impl CheckDerConstraints for DirectoryString<'_> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.header.assert_primitive()?;
+        Ok(())
+    }
+}
+
+impl DerAutoDerive for DirectoryString<'_> {}
+
+impl DynTagged for DirectoryString<'_> {
+    fn tag(&self) -> Tag {
+        match self {
+            DirectoryString::TeletexString(_) => Tag::TeletexString,
+            DirectoryString::PrintableString(_) => Tag::PrintableString,
+            DirectoryString::UniversalString(_) => Tag::UniversalString,
+            DirectoryString::Utf8String(_) => Tag::Utf8String,
+            DirectoryString::BmpString(_) => Tag::BmpString,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToDer for DirectoryString<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        match self {
+            DirectoryString::TeletexString(s) => s.to_der_len(),
+            DirectoryString::PrintableString(s) => s.to_der_len(),
+            DirectoryString::UniversalString(s) => s.to_der_len(),
+            DirectoryString::Utf8String(s) => s.to_der_len(),
+            DirectoryString::BmpString(s) => s.to_der_len(),
+        }
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        match self {
+            DirectoryString::TeletexString(s) => s.write_der_header(writer),
+            DirectoryString::PrintableString(s) => s.write_der_header(writer),
+            DirectoryString::UniversalString(s) => s.write_der_header(writer),
+            DirectoryString::Utf8String(s) => s.write_der_header(writer),
+            DirectoryString::BmpString(s) => s.write_der_header(writer),
+        }
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        match self {
+            DirectoryString::TeletexString(s) => s.write_der_content(writer),
+            DirectoryString::PrintableString(s) => s.write_der_content(writer),
+            DirectoryString::UniversalString(s) => s.write_der_content(writer),
+            DirectoryString::Utf8String(s) => s.write_der_content(writer),
+            DirectoryString::BmpString(s) => s.write_der_content(writer),
+        }
+    }
+}
+
+#[cfg(feature = "stringprep")]
+impl<'a> DirectoryString<'a> {
+    /// Prepare the string for comparison, using the [RFC 4518] rules for case-insensitive matching
+    ///
+    /// The steps are:
+    /// - Transcode: the value is decoded to Unicode
+    /// - Map: soft hyphens, control and formatting characters are removed, other spaces
+    ///   (tabulations, line breaks, no-break spaces, etc.) are replaced with `SPACE`, and
+    ///   characters are case folded (using the Unicode lowercase mapping)
+    /// - Normalize: the string is normalized to Unicode form KC
+    /// - Prohibit: return an error if the string contains private use characters,
+    ///   non-characters or `REPLACEMENT CHARACTER`
+    /// - Insignificant space handling: leading and trailing spaces are removed, and inner
+    ///   sequences of spaces are replaced by one space
+    ///
+    /// Note: insignificant space handling uses the simplified form from [RFC 5280] section 7.1,
+    /// which is equivalent for equality matching.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::{DirectoryString, Utf8String};
+    ///
+    /// let s = DirectoryString::Utf8String(Utf8String::new("  Ma\u{00AD}RIE\tCurie "));
+    /// assert_eq!(s.normalized().unwrap(), "marie curie");
+    /// ```
+    ///
+    /// [RFC 4518]: https://datatracker.ietf.org/doc/html/rfc4518
+    /// [RFC 5280]: https://datatracker.ietf.org/doc/html/rfc5280#section-7.1
+    pub fn normalized(&self) -> Result<String> {
+        stringprep::prepare(self.as_ref())
+    }
+
+    /// Test if two values match, using case-insensitive comparison of the prepared strings
+    ///
+    /// Values match if they are equal after being prepared using [`DirectoryString::normalized`],
+    /// regardless of the string types used to encode them.
+    /// Values containing prohibited characters never match.
+    pub fn matches(&self, other: &DirectoryString) -> bool {
+        match (self.normalized(), other.normalized()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "stringprep")]
+mod stringprep {
+    use crate::{Error, Result};
+    use alloc::string::String;
+    use unicode_normalization::UnicodeNormalization;
+
+    /// RFC 4518 section 2.2: characters mapped to nothing
+    fn is_mapped_to_nothing(c: char) -> bool {
+        matches!(c,
+            '\u{00AD}' | '\u{1806}' | '\u{034F}' | '\u{180B}'..='\u{180D}'
+            | '\u{FE00}'..='\u{FE0F}' | '\u{FFFC}' | '\u{200B}'
+            // control characters (Cf)
+            | '\u{200C}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2064}'
+            | '\u{FEFF}'
+        ) || (c.is_control() && !c.is_whitespace())
+    }
+
+    /// RFC 4518 section 2.4: prohibited characters
+    fn is_prohibited(c: char) -> bool {
+        matches!(c,
+            '\u{FFFD}'
+            // private use
+            | '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..='\u{FFFFD}' | '\u{100000}'..='\u{10FFFD}'
+            // non-characters
+            | '\u{FDD0}'..='\u{FDEF}'
+        ) || (c as u32 & 0xfffe) == 0xfffe
+    }
+
+    pub(super) fn prepare(s: &str) -> Result<String> {
+        // map
+        let mapped = s
+            .chars()
+            .filter(|&c| !is_mapped_to_nothing(c))
+            .map(|c| if c.is_whitespace() { ' ' } else { c })
+            .flat_map(char::to_lowercase);
+        // normalize
+        let normalized = mapped.nfkc();
+        // prohibit, and insignificant space handling
+        let mut output = String::with_capacity(s.len());
+        let mut pending_space = false;
+        for c in normalized {
+            if is_prohibited(c) {
+                return Err(Error::StringInvalidCharset);
+            }
+            if c == ' ' {
+                pending_space = !output.is_empty();
+                continue;
+            }
+            if pending_space {
+                output.push(' ');
+                pending_space = false;
+            }
+            output.push(c);
+        }
+        Ok(output)
+    }
+}
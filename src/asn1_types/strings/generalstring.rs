@@ -1,14 +1,7 @@
-use crate::{asn1_string, TestValidCharset};
-use crate::{Error, Result};
+use super::charsets::{decode_iso2022, encode_iso2022};
+use crate::Result;
 use alloc::string::String;
 
-asn1_string!(GeneralString);
+// X.690 section 8.23.5: ISO 2022 encoding, ASCII is designated as G0 initially
 
-impl<'a> TestValidCharset for GeneralString<'a> {
-    fn test_valid_charset(i: &[u8]) -> Result<()> {
-        if !i.iter().all(u8::is_ascii) {
-            return Err(Error::StringInvalidCharset);
-        }
-        Ok(())
-    }
-}
+asn1_decoded_string!(GeneralString, decode_iso2022, encode_iso2022);
@@ -1,18 +1,8 @@
-use crate::{asn1_string, TestValidCharset};
-use crate::{Error, Result};
+use super::charsets::{decode_t61, encode_t61};
+use crate::Result;
 use alloc::string::String;
 
-asn1_string!(TeletexString);
+// X.680 section 41.4: T.61 (Teletex) character set
+// Decoding uses the ISO 6937 repertoire (see `charsets` module)
 
-impl<'a> TestValidCharset for TeletexString<'a> {
-    fn test_valid_charset(i: &[u8]) -> Result<()> {
-        #[allow(clippy::trivially_copy_pass_by_ref)]
-        fn is_visible(b: &u8) -> bool {
-            0x20 <= *b && *b <= 0x7f
-        }
-        if !i.iter().all(is_visible) {
-            return Err(Error::StringInvalidCharset);
-        }
-        Ok(())
-    }
-}
+asn1_decoded_string!(TeletexString, decode_t61, encode_t61);
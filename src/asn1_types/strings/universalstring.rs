@@ -1,6 +1,7 @@
 // do not use the `asn1_string` macro, since types are not the same
 // X.680 section 37.6 and X.690 section 8.21.7
 
+use crate::cer::*;
 use crate::*;
 use alloc::borrow::Cow;
 use alloc::string::{String, ToString};
@@ -61,25 +62,15 @@ impl<'a, 'b> TryFrom<&'b Any<'a>> for UniversalString<'a> {
 
     fn try_from(any: &'b Any<'a>) -> Result<UniversalString<'a>> {
         any.tag().assert_eq(Self::TAG)?;
+        // X.690 section 8.23.6: constructed strings are made of OCTET STRING segments
+        let content = any.string_content(Tag::OctetString)?;
 
-        if any.data.len() % 4 != 0 {
+        if content.len() % 4 != 0 {
             return Err(Error::StringInvalidCharset);
         }
 
         // read slice as big-endian UCS-4 string
-        let v = &any
-            .data
-            .chunks(4)
-            .map(|s| match s {
-                [a, b, c, d] => {
-                    let u32_val = ((*a as u32) << 24)
-                        | ((*b as u32) << 16)
-                        | ((*c as u32) << 8)
-                        | (*d as u32);
-                    char::from_u32(u32_val)
-                }
-                _ => unreachable!(),
-            })
+        let v = decode_ucs4(&content)
             .collect::<Option<Vec<_>>>()
             .ok_or(Error::StringInvalidCharset)?;
 
@@ -99,15 +90,175 @@ impl<'a> CheckDerConstraints for UniversalString<'a> {
 
 impl DerAutoDerive for UniversalString<'_> {}
 
+impl<'a> CheckCerConstraints for UniversalString<'a> {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        // X.690 section 9.2
+        cer_string_segments(any, Tag::OctetString)?;
+        Ok(())
+    }
+}
+
+impl CerAutoDerive for UniversalString<'_> {}
+
 impl<'a> Tagged for UniversalString<'a> {
     const TAG: Tag = Tag::UniversalString;
 }
 
+impl<'a> TestValidCharset for UniversalString<'a> {
+    fn test_valid_charset(i: &[u8]) -> Result<()> {
+        if i.len() % 4 != 0 {
+            return Err(Error::StringInvalidCharset);
+        }
+        if !decode_ucs4(i).all(|c| c.is_some()) {
+            return Err(Error::StringInvalidCharset);
+        }
+        Ok(())
+    }
+}
+
 #[cfg(feature = "std")]
 impl ToDer for UniversalString<'_> {
     fn to_der_len(&self) -> Result<usize> {
         // UCS-4: 4 bytes per character
-        let sz = self.data.as_bytes().len() * 4;
+        let sz = self.data.chars().count() * 4;
+        if sz < 127 {
+            // 1 (class+tag) + 1 (length) + len
+            Ok(2 + sz)
+        } else {
+            // 1 (class+tag) + n (length) + len
+            let n = Length::Definite(sz).to_der_len()?;
+            Ok(1 + n + sz)
+        }
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let header = Header::new(
+            Class::Universal,
+            false,
+            Self::TAG,
+            Length::Definite(self.data.chars().count() * 4),
+        );
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let v = encode_ucs4(&self.data);
+        writer.write(&v).map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToCer for UniversalString<'_> {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        write_cer_string(writer, Self::TAG, &encode_ucs4(&self.data))
+    }
+}
+
+/// Read slice as big-endian UCS-4 characters (`None` if a value is not a valid character)
+///
+/// Trailing bytes (if length is not a multiple of 4) are ignored.
+fn decode_ucs4(i: &[u8]) -> impl Iterator<Item = Option<char>> + '_ {
+    i.chunks_exact(4).map(|s| {
+        let u32_val =
+            ((s[0] as u32) << 24) | ((s[1] as u32) << 16) | ((s[2] as u32) << 8) | (s[3] as u32);
+        char::from_u32(u32_val)
+    })
+}
+
+/// Encode string as big-endian UCS-4
+fn encode_ucs4(s: &str) -> Vec<u8> {
+    s.chars().flat_map(|c| (c as u32).to_be_bytes()).collect()
+}
+
+/// ASN.1 `UniversalString` type, keeping the encoded (UCS-4) bytes
+///
+/// Contrary to [`UniversalString`], parsing this object does not allocate memory: the content is
+/// validated and borrowed, and characters are decoded only when iterating (see
+/// [`RawUniversalString::chars`]).
+///
+/// The original bytes are kept unchanged, so comparing or encoding this object uses the exact
+/// bytes that were parsed.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{FromDer, RawUniversalString, ToDer};
+///
+/// let bytes = &[0x1c, 0x08, 0x00, 0x00, 0x00, 0x55, 0x00, 0x00, 0x00, 0x73];
+/// let (_, s) = RawUniversalString::from_der(bytes).expect("parsing failed");
+///
+/// assert_eq!(s.as_bytes(), &bytes[2..]);
+/// assert!(s.chars().eq("Us".chars()));
+/// # #[cfg(feature = "std")]
+/// assert_eq!(s.to_der_vec().unwrap(), bytes);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawUniversalString<'a> {
+    pub(crate) data: Cow<'a, [u8]>,
+}
+
+impl<'a> RawUniversalString<'a> {
+    /// Build a `RawUniversalString` from encoded (big-endian UCS-4) bytes
+    ///
+    /// Returns an error if `bytes` is not valid UCS-4.
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        UniversalString::test_valid_charset(bytes)?;
+        Ok(RawUniversalString {
+            data: Cow::Borrowed(bytes),
+        })
+    }
+
+    /// Return the encoded (big-endian UCS-4) bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return an iterator over the decoded characters
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        // content was validated when building object, so decoding cannot fail
+        decode_ucs4(&self.data).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Decode the string (this allocates memory)
+    pub fn string(&self) -> String {
+        self.chars().collect()
+    }
+}
+
+impl From<&str> for RawUniversalString<'_> {
+    fn from(s: &str) -> Self {
+        RawUniversalString {
+            data: Cow::Owned(encode_ucs4(s)),
+        }
+    }
+}
+
+impl<'a> TryFrom<Any<'a>> for RawUniversalString<'a> {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<RawUniversalString<'a>> {
+        any.tag().assert_eq(Self::TAG)?;
+        RawUniversalString::new(any.data)
+    }
+}
+
+impl<'a> CheckDerConstraints for RawUniversalString<'a> {
+    fn check_constraints(any: &Any) -> Result<()> {
+        any.header.assert_primitive()?;
+        Ok(())
+    }
+}
+
+impl DerAutoDerive for RawUniversalString<'_> {}
+
+impl<'a> Tagged for RawUniversalString<'a> {
+    const TAG: Tag = Tag::UniversalString;
+}
+
+#[cfg(feature = "std")]
+impl ToDer for RawUniversalString<'_> {
+    fn to_der_len(&self) -> Result<usize> {
+        let sz = self.data.len();
         if sz < 127 {
             // 1 (class+tag) + 1 (length) + len
             Ok(2 + sz)
@@ -123,15 +274,12 @@ impl ToDer for UniversalString<'_> {
             Class::Universal,
             false,
             Self::TAG,
-            Length::Definite(self.data.as_bytes().len() * 4),
+            Length::Definite(self.data.len()),
         );
-        header.write_der_header(writer).map_err(Into::into)
+        header.write_der_header(writer)
     }
 
     fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
-        self.data
-            .chars()
-            .try_for_each(|c| writer.write(&(c as u32).to_be_bytes()[..]).map(|_| ()))?;
-        Ok(self.data.as_bytes().len() * 4)
+        writer.write(&self.data).map_err(Into::into)
     }
 }
@@ -1,19 +1,8 @@
-use crate::{asn1_string, TestValidCharset};
-use crate::{Error, Result};
+use super::charsets::{decode_t61, encode_t61};
+use crate::Result;
 use alloc::string::String;
 
-asn1_string!(VideotexString);
+// X.680 section 41.4: T.100 and T.101 (Videotex) character sets
+// Only the primary and supplementary sets shared with T.61 are supported
 
-impl<'a> TestValidCharset for VideotexString<'a> {
-    fn test_valid_charset(i: &[u8]) -> Result<()> {
-        #[allow(clippy::trivially_copy_pass_by_ref)]
-        fn is_visible(b: &u8) -> bool {
-            // XXX
-            0x20 <= *b && *b <= 0x7f
-        }
-        if !i.iter().all(is_visible) {
-            return Err(Error::StringInvalidCharset);
-        }
-        Ok(())
-    }
-}
+asn1_decoded_string!(VideotexString, decode_t61, encode_t61);
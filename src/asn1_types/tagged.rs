@@ -1,4 +1,4 @@
-use crate::{Class, DerAutoDerive, Error, Tag, Tagged};
+use crate::{CerAutoDerive, Class, DerAutoDerive, Error, Tag, Tagged};
 use core::marker::PhantomData;
 
 mod builder;
@@ -127,3 +127,8 @@ impl<'a, T, E, TagKind, const CLASS: u8, const TAG: u32> DerAutoDerive
     for TaggedValue<T, E, TagKind, CLASS, TAG>
 {
 }
+
+impl<T, E, TagKind, const CLASS: u8, const TAG: u32> CerAutoDerive
+    for TaggedValue<T, E, TagKind, CLASS, TAG>
+{
+}
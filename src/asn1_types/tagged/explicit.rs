@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::cer::write_cer_constructed;
 use crate::*;
 use core::convert::TryFrom;
 use core::marker::PhantomData;
@@ -93,6 +95,32 @@ where
     }
 }
 
+impl<T, E, const CLASS: u8, const TAG: u32> CheckCerConstraints
+    for TaggedValue<T, E, Explicit, CLASS, TAG>
+where
+    T: CheckCerConstraints,
+{
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        let (_, inner) = Any::from_cer(any.data)?;
+        T::check_cer_constraints(&inner)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E, const CLASS: u8, const TAG: u32> ToCer for TaggedValue<T, E, Explicit, CLASS, TAG>
+where
+    T: ToCer,
+{
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let class =
+            Class::try_from(CLASS).map_err(|_| SerializeError::InvalidClass { class: CLASS })?;
+        write_cer_constructed(writer, class, self.tag(), |writer| {
+            self.inner.write_cer(writer)
+        })
+    }
+}
+
 /// A helper object to parse `[ n ] EXPLICIT T`
 ///
 /// A helper object implementing [`FromBer`] and [`FromDer`], to parse tagged
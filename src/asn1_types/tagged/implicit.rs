@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::cer::write_cer_implicit;
 use crate::*;
 use core::convert::TryFrom;
 use core::marker::PhantomData;
@@ -102,6 +104,33 @@ where
     }
 }
 
+impl<T, E, const CLASS: u8, const TAG: u32> CheckCerConstraints
+    for TaggedValue<T, E, Implicit, CLASS, TAG>
+where
+    T: CheckCerConstraints,
+    T: Tagged,
+{
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        let header = any.header.clone().with_tag(T::TAG);
+        let inner = Any::new(header, any.data);
+        T::check_cer_constraints(&inner)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E, const CLASS: u8, const TAG: u32> ToCer for TaggedValue<T, E, Implicit, CLASS, TAG>
+where
+    T: ToCer,
+{
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        let class =
+            Class::try_from(CLASS).map_err(|_| SerializeError::InvalidClass { class: CLASS })?;
+        let v = self.inner.to_cer_vec()?;
+        write_cer_implicit(writer, class, self.tag(), &v)
+    }
+}
+
 /// A helper object to parse `[ n ] IMPLICIT T`
 ///
 /// A helper object implementing [`FromBer`] and [`FromDer`], to parse tagged
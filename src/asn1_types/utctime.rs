@@ -5,7 +5,7 @@ use core::fmt;
 #[cfg(feature = "datetime")]
 use time::OffsetDateTime;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct UtcTime(pub ASN1DateTime);
 
 impl UtcTime {
@@ -185,6 +185,21 @@ impl CheckDerConstraints for UtcTime {
 
 impl DerAutoDerive for UtcTime {}
 
+impl CheckCerConstraints for UtcTime {
+    fn check_cer_constraints(any: &Any) -> Result<()> {
+        Self::check_constraints(any)
+    }
+}
+
+impl CerAutoDerive for UtcTime {}
+
+#[cfg(feature = "std")]
+impl ToCer for UtcTime {
+    fn write_cer(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        self.write_der(writer)
+    }
+}
+
 impl Tagged for UtcTime {
     const TAG: Tag = Tag::UtcTime;
 }
@@ -1,6 +1,6 @@
 use crate::error::*;
 use crate::header::*;
-use crate::{FromDer, Length, Tag};
+use crate::{FromBer, Length, Tag};
 use nom::bytes::streaming::take;
 use nom::{Err, Needed, Offset};
 use rusticata_macros::custom_check;
@@ -34,7 +34,7 @@ pub(crate) fn ber_skip_object_content<'a>(
             // this is recursive
             let mut i = i;
             loop {
-                let (i2, header2) = Header::from_der(i)?;
+                let (i2, header2) = Header::from_ber(i)?;
                 let (i3, eoc) = ber_skip_object_content(i2, &header2, max_depth - 1)?;
                 if eoc {
                     // return false, since top object was not EndOfContent
@@ -96,9 +96,9 @@ pub(crate) fn parse_identifier(i: &[u8]) -> ParseResult<(u8, u8, u32, &[u8])> {
                 // Make sure we don't read past the end of our data.
                 custom_check!(i, tag_byte_count >= i.len(), Error::InvalidTag)?;
 
-                // With tag defined as u32 the most we can fit in is four tag bytes.
-                // (X.690 doesn't actually specify maximum tag width.)
-                custom_check!(i, tag_byte_count > 5, Error::InvalidTag)?;
+                // X.690 doesn't actually specify maximum tag width, tag numbers are limited to
+                // `Tag::MAX`
+                custom_check!(i, c > Tag::MAX.0 >> 7, Error::TagTooLarge)?;
 
                 c = (c << 7) | (u32::from(i[tag_byte_count]) & 0x7f);
                 let done = i[tag_byte_count] & 0x80 == 0;
@@ -115,6 +115,22 @@ pub(crate) fn parse_identifier(i: &[u8]) -> ParseResult<(u8, u8, u32, &[u8])> {
     }
 }
 
+/// Return true if the tag number is encoded with the minimum number of octets
+///
+/// The high-tag-number form must be used only for tag numbers >= 31, and the first subsequent
+/// octet must not be `0x80` (X.690 section 8.1.2.4.2).
+pub(crate) fn is_minimal_tag_encoding(raw_tag: &[u8], tag: u32) -> bool {
+    raw_tag.len() <= 1 || (tag >= 0x1f && raw_tag[1] != 0x80)
+}
+
+/// Return true if the length octets are encoded with the minimum number of octets
+///
+/// `raw_length` contains all length octets. The long form must be used only for lengths >= 128,
+/// and without leading zeroes (X.690 section 10.1).
+pub(crate) fn is_minimal_length_encoding(raw_length: &[u8], length: usize) -> bool {
+    raw_length.len() <= 1 || (length >= 0x80 && raw_length[1] != 0)
+}
+
 /// Return the MSB and the rest of the first byte, or an error
 pub(crate) fn parse_ber_length_byte(i: &[u8]) -> ParseResult<(u8, u8)> {
     if i.is_empty() {
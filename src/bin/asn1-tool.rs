@@ -0,0 +1,523 @@
+//! Command-line tool to inspect and convert ASN.1 BER/DER objects
+//!
+//! Run `asn1-tool --help` for the list of commands.
+
+use asn1_rs::{
+    base64_decode, base64_encode, validate_der, Any, Asn1Value, Class, FromBer, Header, Length,
+    Pem, PemParser, Tag, ToDer, TreeFormatter,
+};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::io::{self, Read, Write};
+use std::{env, fs, process};
+
+/// Maximum nesting of constructed objects when converting
+const MAX_DEPTH: usize = 50;
+
+const USAGE: &str = "\
+Usage: asn1-tool <COMMAND> [OPTIONS] [FILE]
+
+Read ASN.1 objects from FILE (or from the standard input if absent or `-`). The input can be
+binary (BER/DER), PEM, base64 or hexadecimal, and is detected automatically.
+
+Commands:
+  dump                  Display objects as a tree, with offsets and lengths
+  parse                 Display objects using the ASN.1 value notation
+  validate              Check that objects are valid BER (or DER with --der)
+  extract <PATH>        Write the object at PATH: indexes of elements, separated by dots (for
+                        ex. `0.2`). The content of OCTET STRING and BIT STRING values is parsed
+                        as encapsulated objects
+  convert               Encode objects again, using DER (default) or BER
+
+Options:
+  --inform <FORMAT>     Input format: der, pem, base64 or hex
+  --block <N>           Use only the N-th PEM block (starting at 0)
+  --strict              Use the strict PEM rules from RFC 7468
+  --no-offsets          dump: do not show offsets and lengths
+  --max-bytes <N>       dump: maximum number of bytes displayed for binary values
+  --der                 validate: check DER rules
+  --to <ENCODING>       convert: der, or ber (constructed objects use the indefinite form)
+  --outform <FORMAT>    extract, convert: der, pem, base64 or hex (default: der)
+  --label <LABEL>       extract, convert: label of the PEM output
+  -o, --output <FILE>   extract, convert: write to FILE instead of the standard output
+  -h, --help            Print this help
+";
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Command {
+    Dump,
+    Parse,
+    Validate,
+    Extract,
+    Convert,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Der,
+    Pem,
+    Base64,
+    Hex,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Result<Format> {
+        match name {
+            "der" | "ber" => Ok(Format::Der),
+            "pem" => Ok(Format::Pem),
+            "base64" => Ok(Format::Base64),
+            "hex" => Ok(Format::Hex),
+            _ => Err(format!("unknown format '{}'", name).into()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Options {
+    command: Command,
+    path: Vec<usize>,
+    file: Option<String>,
+    inform: Option<Format>,
+    block: Option<usize>,
+    strict: bool,
+    offsets: bool,
+    max_bytes: usize,
+    der: bool,
+    to_ber: bool,
+    outform: Format,
+    label: Option<String>,
+    output: Option<String>,
+}
+
+/// A block of input data, with its PEM label
+struct Block {
+    label: Option<String>,
+    data: Vec<u8>,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|a| a == "-h" || a == "--help") {
+        print!("{}", USAGE);
+        return;
+    }
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("asn1-tool: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    match run(&options) {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("asn1-tool: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options> {
+    let mut args = args.iter();
+    let command = match args.next().map(String::as_str) {
+        Some("dump") => Command::Dump,
+        Some("parse") => Command::Parse,
+        Some("validate") => Command::Validate,
+        Some("extract") => Command::Extract,
+        Some("convert") => Command::Convert,
+        Some(c) => return Err(format!("unknown command '{}'", c).into()),
+        None => return Err("missing command".into()),
+    };
+    let mut options = Options {
+        command,
+        path: Vec::new(),
+        file: None,
+        inform: None,
+        block: None,
+        strict: false,
+        offsets: true,
+        max_bytes: 64,
+        der: false,
+        to_ber: false,
+        outform: Format::Der,
+        label: None,
+        output: None,
+    };
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{}'", arg))
+        };
+        match arg.as_str() {
+            "--inform" => options.inform = Some(Format::from_name(value()?)?),
+            "--block" => options.block = Some(value()?.parse()?),
+            "--strict" => options.strict = true,
+            "--no-offsets" => options.offsets = false,
+            "--max-bytes" => options.max_bytes = value()?.parse()?,
+            "--der" => options.der = true,
+            "--to" => {
+                options.to_ber = match value()?.as_str() {
+                    "der" => false,
+                    "ber" => true,
+                    e => return Err(format!("unknown encoding '{}'", e).into()),
+                }
+            }
+            "--outform" => options.outform = Format::from_name(value()?)?,
+            "--label" => options.label = Some(value()?.clone()),
+            "-o" | "--output" => options.output = Some(value()?.clone()),
+            "-" => positional.push(arg),
+            a if a.starts_with('-') => return Err(format!("unknown option '{}'", a).into()),
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+    if command == Command::Extract {
+        let path = positional.next().ok_or("missing path")?;
+        options.path = parse_path(path)?;
+    }
+    options.file = positional.next().filter(|f| *f != "-").cloned();
+    if let Some(arg) = positional.next() {
+        return Err(format!("unexpected argument '{}'", arg).into());
+    }
+    if options.outform == Format::Pem {
+        match &options.label {
+            Some(label) if !Pem::is_valid_label(label) => {
+                return Err(format!("invalid PEM label '{}'", label).into())
+            }
+            Some(_) => (),
+            None => return Err("PEM output requires a label (--label)".into()),
+        }
+    }
+    Ok(options)
+}
+
+/// Parse a path of element indexes, for ex. `1.0.2` (the empty path selects the object itself)
+fn parse_path(path: &str) -> Result<Vec<usize>> {
+    path.split('.')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse()
+                .map_err(|_| format!("invalid path '{}'", path).into())
+        })
+        .collect()
+}
+
+/// Run the command, and return false if the input is not valid
+fn run(options: &Options) -> Result<bool> {
+    let mut input = Vec::new();
+    match &options.file {
+        Some(file) => input = fs::read(file).map_err(|e| format!("{}: {}", file, e))?,
+        None => {
+            io::stdin().read_to_end(&mut input)?;
+        }
+    }
+    let format = options.inform.unwrap_or_else(|| detect_format(&input));
+    let mut blocks = decode_input(&input, format, options.strict)?;
+    if let Some(n) = options.block {
+        if n >= blocks.len() {
+            return Err(format!("block {} not found ({} blocks)", n, blocks.len()).into());
+        }
+        blocks = vec![blocks.swap_remove(n)];
+    }
+    match options.command {
+        Command::Dump | Command::Parse | Command::Validate => {
+            let mut valid = true;
+            for (idx, block) in blocks.iter().enumerate() {
+                if let Some(label) = &block.label {
+                    let idx = options.block.unwrap_or(idx);
+                    eprintln!("PEM block {} [{}]", idx, label);
+                }
+                valid &= match options.command {
+                    Command::Dump => {
+                        let formatter = TreeFormatter::new()
+                            .with_offsets(options.offsets)
+                            .with_max_bytes(options.max_bytes);
+                        print!("{}", formatter.display(&block.data));
+                        true
+                    }
+                    Command::Parse => {
+                        let formatter = TreeFormatter::new().with_value_notation(true);
+                        print!("{}", formatter.display(&block.data));
+                        true
+                    }
+                    _ => validate(&block.data, options.der),
+                };
+            }
+            Ok(valid)
+        }
+        Command::Extract | Command::Convert => {
+            if blocks.len() != 1 {
+                return Err(format!(
+                    "input contains {} PEM blocks, select one with --block",
+                    blocks.len()
+                )
+                .into());
+            }
+            let data = &blocks[0].data;
+            let output = if options.command == Command::Extract {
+                extract(data, &options.path)?.to_vec()
+            } else {
+                let mut output = Vec::new();
+                let mut i = &data[..];
+                while !i.is_empty() {
+                    let (rem, any) = Any::from_ber(i)?;
+                    if options.to_ber {
+                        to_ber(&any, &mut output, MAX_DEPTH)?;
+                    } else {
+                        to_der(&any, &mut output, MAX_DEPTH)?;
+                    }
+                    i = rem;
+                }
+                output
+            };
+            write_output(&output, options)?;
+            Ok(true)
+        }
+    }
+}
+
+fn detect_format(input: &[u8]) -> Format {
+    let start = input.iter().position(|b| !b.is_ascii_whitespace());
+    if start.map_or(false, |start| input[start..].starts_with(b"-----BEGIN ")) {
+        return Format::Pem;
+    }
+    let text: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    if text.is_empty() {
+        Format::Der
+    } else if text.iter().all(|b| b.is_ascii_hexdigit() || *b == b':') {
+        Format::Hex
+    } else if text
+        .iter()
+        .all(|b| b.is_ascii_alphanumeric() || b"+/=".contains(b))
+    {
+        Format::Base64
+    } else {
+        Format::Der
+    }
+}
+
+fn decode_input(input: &[u8], format: Format, strict: bool) -> Result<Vec<Block>> {
+    let text =
+        || std::str::from_utf8(input).map_err(|_| "input is not text, check the input format");
+    let data = match format {
+        Format::Der => input.to_vec(),
+        Format::Pem => {
+            let pems = PemParser::new().with_strict(strict).parse_all(text()?)?;
+            let blocks = pems
+                .into_iter()
+                .map(|pem| Block {
+                    label: Some(pem.label),
+                    data: pem.contents,
+                })
+                .collect();
+            return Ok(blocks);
+        }
+        Format::Base64 => {
+            let s: String = text()?.split_ascii_whitespace().collect();
+            base64_decode(&s)?
+        }
+        Format::Hex => {
+            let digits: Vec<u8> = input
+                .iter()
+                .copied()
+                .filter(|b| !b.is_ascii_whitespace() && *b != b':')
+                .collect();
+            if digits.len() % 2 != 0 {
+                return Err("odd number of hexadecimal digits".into());
+            }
+            digits
+                .chunks(2)
+                .map(|c| {
+                    let s = std::str::from_utf8(c).map_err(|_| "invalid hexadecimal digit")?;
+                    u8::from_str_radix(s, 16).map_err(|_| "invalid hexadecimal digit".into())
+                })
+                .collect::<Result<_>>()?
+        }
+    };
+    Ok(vec![Block { label: None, data }])
+}
+
+fn write_output(data: &[u8], options: &Options) -> Result<()> {
+    let data = match options.outform {
+        Format::Hex => {
+            let mut s: String = data.iter().map(|b| format!("{:02x}", b)).collect();
+            s.push('\n');
+            s.into_bytes()
+        }
+        Format::Base64 => {
+            let mut s = base64_encode(data);
+            s.push('\n');
+            s.into_bytes()
+        }
+        Format::Pem => {
+            let label = options.label.as_deref().unwrap_or_default();
+            Pem::new(label, data.to_vec()).to_string().into_bytes()
+        }
+        Format::Der => data.to_vec(),
+    };
+    match &options.output {
+        Some(file) => fs::write(file, data).map_err(|e| format!("{}: {}", file, e))?,
+        None => io::stdout().write_all(&data)?,
+    }
+    Ok(())
+}
+
+/// Check all objects and print the errors, and return true if the input is valid
+fn validate(data: &[u8], der: bool) -> bool {
+    if der {
+        let violations = validate_der(data);
+        for violation in &violations {
+            println!("{}", violation);
+        }
+        println!("{} DER violation(s)", violations.len());
+        return violations.is_empty();
+    }
+    let mut i = data;
+    while !i.is_empty() {
+        let offset = data.len() - i.len();
+        let result = Any::from_ber(i)
+            .map_err(asn1_rs::Error::from)
+            .and_then(|(rem, any)| Asn1Value::try_from(&any).map(|_| rem));
+        match result {
+            Ok(rem) => i = rem,
+            Err(e) => {
+                println!("offset {}: {}", offset, e);
+                return false;
+            }
+        }
+    }
+    println!("valid BER");
+    true
+}
+
+/// Return the encoding of the object at `path`
+fn extract<'a>(data: &'a [u8], path: &[usize]) -> Result<&'a [u8]> {
+    let mut current = first_object(data)?;
+    for (depth, &idx) in path.iter().enumerate() {
+        let (_, any) = Any::from_ber(current)?;
+        let content = match any.tag() {
+            _ if any.header.is_constructed() => any.data,
+            // encapsulated objects
+            Tag::OctetString if any.class() == Class::Universal => any.data,
+            Tag::BitString if any.class() == Class::Universal && any.data.first() == Some(&0) => {
+                &any.data[1..]
+            }
+            _ => return Err(format!("object at path {:?} has no elements", &path[..depth]).into()),
+        };
+        let mut i = content;
+        for _ in 0..idx {
+            let (rem, _) = Any::from_ber(i)?;
+            i = rem;
+        }
+        if i.is_empty() {
+            return Err(format!("no element at path {:?}", &path[..=depth]).into());
+        }
+        current = first_object(i)?;
+    }
+    Ok(current)
+}
+
+/// Return the encoding of the first object of `data`
+fn first_object(data: &[u8]) -> Result<&[u8]> {
+    let (rem, _) = Any::from_ber(data)?;
+    Ok(&data[..data.len() - rem.len()])
+}
+
+/// Return true if the constructed form of this universal type is a segmented string
+fn is_string_type(tag: Tag) -> bool {
+    !matches!(
+        tag,
+        Tag::Sequence | Tag::Set | Tag::External | Tag::EmbeddedPdv
+    ) && tag.0 < 31
+}
+
+/// Encode an object using DER
+fn to_der(any: &Any, output: &mut Vec<u8>, max_depth: usize) -> Result<()> {
+    if max_depth == 0 {
+        return Err(asn1_rs::Error::BerMaxDepth.into());
+    }
+    let universal = any.class() == Class::Universal;
+    let integer = matches!(any.tag(), Tag::Integer | Tag::Enumerated);
+    // primitive values and segmented strings are decoded, and encoded again
+    let decoded = !any.header.is_constructed() || is_string_type(any.tag());
+    if universal && decoded && !integer {
+        Asn1Value::try_from(any)?.write_der(output)?;
+        return Ok(());
+    }
+    let content = if any.header.is_constructed() {
+        let mut elements = Vec::new();
+        let mut i = any.data;
+        while !i.is_empty() {
+            let (rem, element) = Any::from_ber(i)?;
+            let mut v = Vec::new();
+            to_der(&element, &mut v, max_depth - 1)?;
+            elements.push(v);
+            i = rem;
+        }
+        if universal && any.tag() == Tag::Set {
+            elements.sort();
+        }
+        elements.concat()
+    } else if universal && integer {
+        // check the value, and remove the redundant leading octets allowed in BER
+        Asn1Value::try_from(any)?;
+        trim_integer(any.data).to_vec()
+    } else {
+        any.data.to_vec()
+    };
+    let header = Header::new(
+        any.class(),
+        any.header.is_constructed(),
+        any.tag(),
+        Length::Definite(content.len()),
+    );
+    header.write_der(output)?;
+    output.extend_from_slice(&content);
+    Ok(())
+}
+
+/// Remove the redundant leading octets of an integer (X.690 section 8.3.2)
+fn trim_integer(mut data: &[u8]) -> &[u8] {
+    while data.len() > 1
+        && ((data[0] == 0 && data[1] & 0x80 == 0) || (data[0] == 0xff && data[1] & 0x80 != 0))
+    {
+        data = &data[1..];
+    }
+    data
+}
+
+/// Encode an object using BER, with the indefinite form for constructed objects
+fn to_ber(any: &Any, output: &mut Vec<u8>, max_depth: usize) -> Result<()> {
+    if max_depth == 0 {
+        return Err(asn1_rs::Error::BerMaxDepth.into());
+    }
+    if !any.header.is_constructed() {
+        let header = Header::new(
+            any.class(),
+            false,
+            any.tag(),
+            Length::Definite(any.data.len()),
+        );
+        header.write_der(output)?;
+        output.extend_from_slice(any.data);
+        return Ok(());
+    }
+    let header = Header::new(any.class(), true, any.tag(), Length::Indefinite);
+    header.write_der(output)?;
+    let mut i = any.data;
+    while !i.is_empty() {
+        let (rem, element) = Any::from_ber(i)?;
+        to_ber(&element, output, max_depth - 1)?;
+        i = rem;
+    }
+    output.extend_from_slice(&[0, 0]);
+    Ok(())
+}
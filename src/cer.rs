@@ -0,0 +1,170 @@
+//! Helper functions for the Canonical Encoding Rules (X.690 section 9)
+//!
+//! CER differs from DER mostly in two ways: constructed objects use the indefinite length form,
+//! and strings longer than 1000 octets are encoded as constructed strings, split into segments.
+
+#[cfg(feature = "std")]
+use crate::{ber::parse_identifier, Class, Header, Length, SerializeResult, ToDer};
+use crate::{Any, CerConstraint, Error, FromCer, Result, Tag};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+/// Maximum length of a primitive string, and length of the segments of a constructed string
+/// (X.690 section 9.2)
+pub(crate) const CER_SEGMENT_LEN: usize = 1000;
+
+/// Check the CER constraints for a string object, and return its segments
+///
+/// The segment tag is `BIT STRING` for bit strings, and `OCTET STRING` for all other string
+/// types. If the object is primitive, the only segment is the object content.
+pub(crate) fn cer_string_segments<'a>(any: &Any<'a>, segment_tag: Tag) -> Result<Vec<&'a [u8]>> {
+    if any.header.is_primitive() {
+        if any.data.len() > CER_SEGMENT_LEN {
+            return Err(Error::CerConstraintFailed(CerConstraint::StringTooLong));
+        }
+        return Ok(vec![any.data]);
+    }
+    let mut segments = Vec::new();
+    let mut i = any.data;
+    while !i.is_empty() {
+        let (rem, segment) = Any::from_cer(i)?;
+        // segments must be primitive (segments cannot be nested in CER)
+        if segment.header.is_constructed() || !segment.header.is_universal() {
+            return Err(Error::CerConstraintFailed(
+                CerConstraint::InvalidStringSegment,
+            ));
+        }
+        segment.tag().assert_eq(segment_tag)?;
+        segments.push(segment.data);
+        i = rem;
+    }
+    // A constructed encoding is used only if the string is longer than 1000 octets, so there are
+    // at least 2 segments. All segments except the last one have 1000 octets.
+    let valid = match segments.split_last() {
+        Some((last, others)) => {
+            !others.is_empty()
+                && !last.is_empty()
+                && last.len() <= CER_SEGMENT_LEN
+                && others.iter().all(|s| s.len() == CER_SEGMENT_LEN)
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(Error::CerConstraintFailed(
+            CerConstraint::InvalidStringSegment,
+        ));
+    }
+    Ok(segments)
+}
+
+/// Compare encodings of `SET OF` elements (X.690 section 9.3)
+///
+/// Encodings are compared as octet strings, the shorter one being padded at its trailing end
+/// with 0-octets.
+pub(crate) fn cmp_set_of_encodings(a: &[u8], b: &[u8]) -> Ordering {
+    let len = a.len().max(b.len());
+    let pad = core::iter::repeat(&0);
+    a.iter()
+        .chain(pad.clone())
+        .take(len)
+        .cmp(b.iter().chain(pad).take(len))
+}
+
+/// Check the CER constraints for the elements of a `SET OF`, using the provided function for
+/// each element, and check that elements are sorted (X.690 section 9.3)
+pub(crate) fn check_cer_set_of<F>(any: &Any, check_item: F) -> Result<()>
+where
+    F: Fn(&Any) -> Result<()>,
+{
+    let mut i = any.data;
+    let mut previous: Option<&[u8]> = None;
+    while !i.is_empty() {
+        let (rem, item) = Any::from_cer(i)?;
+        check_item(&item)?;
+        let encoded = &i[..i.len() - rem.len()];
+        if let Some(previous) = previous {
+            if cmp_set_of_encodings(previous, encoded) == Ordering::Greater {
+                return Err(Error::CerConstraintFailed(CerConstraint::SetOfNotSorted));
+            }
+        }
+        previous = Some(encoded);
+        i = rem;
+    }
+    Ok(())
+}
+
+/// Write a constructed object using the indefinite length form, followed by end-of-content
+///
+/// The content is written by `f`.
+#[cfg(feature = "std")]
+pub(crate) fn write_cer_constructed<F>(
+    writer: &mut dyn Write,
+    class: Class,
+    tag: Tag,
+    f: F,
+) -> SerializeResult<usize>
+where
+    F: FnOnce(&mut dyn Write) -> SerializeResult<usize>,
+{
+    let header = Header::new(class, true, tag, Length::Indefinite);
+    let sz = header.write_der_header(writer)?;
+    let sz = sz + f(writer)?;
+    let sz = sz + writer.write(&[0, 0])?;
+    Ok(sz)
+}
+
+/// Write a string object (not a `BIT STRING`)
+///
+/// Strings of at most 1000 octets are encoded as primitive. Longer strings are split into
+/// `OCTET STRING` segments of 1000 octets (X.690 section 9.2).
+#[cfg(feature = "std")]
+pub(crate) fn write_cer_string(
+    writer: &mut dyn Write,
+    tag: Tag,
+    content: &[u8],
+) -> SerializeResult<usize> {
+    if content.len() <= CER_SEGMENT_LEN {
+        let header = Header::new(
+            Class::Universal,
+            false,
+            tag,
+            Length::Definite(content.len()),
+        );
+        let sz = header.write_der_header(writer)?;
+        let sz = sz + writer.write(content)?;
+        return Ok(sz);
+    }
+    write_cer_constructed(writer, Class::Universal, tag, |writer| {
+        let mut sz = 0;
+        for segment in content.chunks(CER_SEGMENT_LEN) {
+            let header = Header::new(
+                Class::Universal,
+                false,
+                Tag::OctetString,
+                Length::Definite(segment.len()),
+            );
+            sz += header.write_der_header(writer)?;
+            sz += writer.write(segment)?;
+        }
+        Ok(sz)
+    })
+}
+
+/// Write an encoded object, replacing its identifier by `class` and `tag` (IMPLICIT tagging)
+///
+/// The constructed bit of the original identifier is kept (X.690 section 8.14.3).
+#[cfg(feature = "std")]
+pub(crate) fn write_cer_implicit(
+    writer: &mut dyn Write,
+    class: Class,
+    tag: Tag,
+    encoded: &[u8],
+) -> SerializeResult<usize> {
+    let (rem, (_, constructed, _, _)) = parse_identifier(encoded).map_err(Error::from)?;
+    let sz = (class, constructed != 0, tag).write_der_header(writer)?;
+    let sz = sz + writer.write(rem)?;
+    Ok(sz)
+}
@@ -1,43 +1,148 @@
-use crate::{Tag, Tagged};
+use crate::{Integer, Tag, Tagged};
+#[cfg(feature = "std")]
+use crate::{Result, SerializeResult, ToDer};
 
-#[derive(Debug)]
+/// A DER-encoded `INTEGER`, built at compile-time
+///
+/// `ConstInt` stores the complete DER encoding (tag, length and content) in a fixed-size buffer,
+/// so it can be built by `const fn` and used in `const` or `static` items.
+/// Use [`ConstInt::as_integer`] to get the corresponding [`Integer`].
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{ConstInt, Integer};
+///
+/// static VERSION: ConstInt = ConstInt::from_i64(-129);
+///
+/// assert_eq!(VERSION.as_bytes(), &[0x02, 0x02, 0xff, 0x7f]);
+/// let int: Integer<'static> = VERSION.as_integer();
+/// assert_eq!(int.as_i64(), Ok(-129));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ConstInt {
-    buffer: [u8; 10],
+    buffer: [u8; 11],
     n: usize,
 }
 
-// XXX only ToBer/ToDer trait supported?
-
-impl Tagged for ConstInt {
-    const TAG: Tag = Tag::Integer;
-}
-
-#[derive(Debug)]
-pub struct IntBuilder {}
+impl ConstInt {
+    /// Build the DER encoding of an unsigned value
+    pub const fn from_u64(i: u64) -> Self {
+        let b = i.to_be_bytes();
+        let mut start = 0;
+        // skip leading 0s, but keep at least one byte
+        while start < 7 && b[start] == 0 {
+            start += 1;
+        }
+        // a leading 0 is required if the highest bit is set
+        Self::from_bytes(b, start, b[start] >= 0x80)
+    }
 
-impl IntBuilder {
-    pub const fn build(&self, i: u64) -> ConstInt {
+    /// Build the DER encoding of a signed value
+    pub const fn from_i64(i: i64) -> Self {
         let b = i.to_be_bytes();
-        let mut out = [0u8; 10];
-        out[0] = 0x4;
-        let src_len = b.len();
-        let mut src_index = 0;
-        while src_index < src_len && b[src_index] == 0 {
-            src_index += 1;
+        let mut start = 0;
+        // skip leading bytes if they are only sign extension
+        while start < 7
+            && ((b[start] == 0x00 && b[start + 1] < 0x80)
+                || (b[start] == 0xff && b[start + 1] >= 0x80))
+        {
+            start += 1;
         }
-        out[1] = (src_len - src_index) as u8;
+        Self::from_bytes(b, start, false)
+    }
+
+    const fn from_bytes(b: [u8; 8], start: usize, leading_zero: bool) -> Self {
+        let mut out = [0u8; 11];
+        out[0] = Tag::Integer.0 as u8;
         let mut dst_index = 2;
-        while src_index < src_len {
+        if leading_zero {
+            dst_index += 1;
+        }
+        let mut src_index = start;
+        while src_index < b.len() {
             out[dst_index] = b[src_index];
             src_index += 1;
             dst_index += 1;
         }
-        // XXX will not work: we need to allocate a Vec
-        // also, we cannot just store the bytes (there are extra zeroes at end)
-        // Integer::new(&out[..dst_index])
+        out[1] = (dst_index - 2) as u8;
         ConstInt {
             buffer: out,
             n: dst_index,
         }
     }
+
+    /// Return the DER encoding (header and content)
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.n]
+    }
+
+    /// Return the encoded content (without header)
+    #[inline]
+    pub fn content(&self) -> &[u8] {
+        &self.buffer[2..self.n]
+    }
+
+    /// Return the value as an `Integer` (borrowed)
+    #[inline]
+    pub fn as_integer(&self) -> Integer<'_> {
+        Integer::new(self.content())
+    }
+}
+
+impl<'a> From<&'a ConstInt> for Integer<'a> {
+    fn from(i: &'a ConstInt) -> Self {
+        i.as_integer()
+    }
+}
+
+impl Tagged for ConstInt {
+    const TAG: Tag = Tag::Integer;
+}
+
+#[cfg(feature = "std")]
+impl ToDer for ConstInt {
+    fn to_der_len(&self) -> Result<usize> {
+        Ok(self.n)
+    }
+
+    fn write_der(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(self.as_bytes()).map_err(Into::into)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(&self.buffer[..2]).map_err(Into::into)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        writer.write(self.content()).map_err(Into::into)
+    }
+}
+
+/// Builder for [`ConstInt`] values
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{ConstInt, IntBuilder};
+///
+/// const BUILDER: IntBuilder = IntBuilder {};
+/// const I: ConstInt = BUILDER.build(0x80);
+///
+/// assert_eq!(I.as_bytes(), &[0x02, 0x02, 0x00, 0x80]);
+/// ```
+#[derive(Debug)]
+pub struct IntBuilder {}
+
+impl IntBuilder {
+    /// Build the DER encoding of an unsigned value
+    pub const fn build(&self, i: u64) -> ConstInt {
+        ConstInt::from_u64(i)
+    }
+
+    /// Build the DER encoding of a signed value
+    pub const fn build_signed(&self, i: i64) -> ConstInt {
+        ConstInt::from_i64(i)
+    }
 }
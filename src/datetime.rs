@@ -1,4 +1,4 @@
-use crate::{Result, Tag};
+use crate::{Error, Result, Tag};
 use alloc::format;
 use alloc::string::ToString;
 use core::fmt;
@@ -78,10 +78,72 @@ impl ASN1DateTime {
 
     #[cfg(feature = "datetime")]
     pub fn to_datetime(&self) -> Result<OffsetDateTime> {
-        use crate::Error;
-
         self.to_time_datetime().map_err(|_| Error::InvalidDateTime)
     }
+
+    /// Convert to coordinated universal time, applying the offset of the time zone
+    ///
+    /// Returns an error if the time zone is undefined (local time), or if the date or time is
+    /// invalid.
+    pub fn to_utc(&self) -> Result<ASN1DateTime> {
+        let offset = match self.tz {
+            ASN1TimeZone::Undefined => return Err(Error::InvalidDateTime),
+            ASN1TimeZone::Z => 0,
+            // the sign of the offset is the sign of the hours
+            ASN1TimeZone::Offset(hh, mm) if hh < 0 => i32::from(hh) * 60 - i32::from(mm).abs(),
+            ASN1TimeZone::Offset(hh, mm) => i32::from(hh) * 60 + i32::from(mm).abs(),
+        };
+        if !(1..=12).contains(&self.month)
+            || self.day == 0
+            || self.day > days_in_month(self.year, self.month)
+            || self.hour > 23
+            || self.minute > 59
+        {
+            return Err(Error::InvalidDateTime);
+        }
+        let minutes = i32::from(self.hour) * 60 + i32::from(self.minute) - offset;
+        let (mut year, mut month, mut day) = (self.year, self.month, self.day);
+        for _ in 0..minutes.div_euclid(24 * 60) {
+            day += 1;
+            if day > days_in_month(year, month) {
+                day = 1;
+                month = if month == 12 { 1 } else { month + 1 };
+                if month == 1 {
+                    year = year.checked_add(1).ok_or(Error::InvalidDateTime)?;
+                }
+            }
+        }
+        for _ in minutes.div_euclid(24 * 60)..0 {
+            if day == 1 {
+                month = if month == 1 { 12 } else { month - 1 };
+                if month == 12 {
+                    year = year.checked_sub(1).ok_or(Error::InvalidDateTime)?;
+                }
+                day = days_in_month(year, month);
+            } else {
+                day -= 1;
+            }
+        }
+        let minutes = minutes.rem_euclid(24 * 60);
+        Ok(ASN1DateTime {
+            year,
+            month,
+            day,
+            hour: (minutes / 60) as u8,
+            minute: (minutes % 60) as u8,
+            tz: ASN1TimeZone::Z,
+            ..*self
+        })
+    }
+}
+
+fn days_in_month(year: u32, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
 }
 
 impl fmt::Display for ASN1DateTime {
@@ -36,6 +36,56 @@ pub enum DerConstraint {
     IntegerLeadingZeroes,
     /// Leading 0xff in negative Integer encoding
     IntegerLeadingFF,
+    /// Trailing zero bits in a bitstring with a named bit list
+    NamedBitsTrailingZero,
+    /// Real value is not in the canonical form (X.690 section 11.3)
+    RealNotCanonical,
+    /// Tag number is not encoded with the minimum number of octets
+    NonMinimalTag,
+    /// Length is not encoded with the minimum number of octets
+    NonMinimalLength,
+    /// Elements of a SET or SET OF are not sorted
+    SetNotSorted,
+    /// Fraction of seconds must be non-empty, and must not end with zeroes
+    InvalidTimeFraction,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CerConstraint {}
+
+#[derive(Clone, Copy, Debug, Display, PartialEq)]
+/// Error types for CER constraints
+pub enum CerConstraint {
+    /// Constructed object must use the indefinite length form
+    DefiniteLength,
+    /// Primitive string must not be longer than 1000 octets
+    StringTooLong,
+    /// Constructed string segments must be primitive, and all but the last one must be 1000 octets long
+    InvalidStringSegment,
+    /// Elements of a SET OF must be sorted by their encoding
+    SetOfNotSorted,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PemError {}
+
+#[derive(Clone, Copy, Debug, Display, PartialEq)]
+/// Error types for PEM decoding
+pub enum PemError {
+    /// Missing PEM begin line
+    MissingBegin,
+    /// Missing PEM end line
+    MissingEnd,
+    /// Labels of PEM begin and end lines do not match
+    LabelMismatch,
+    /// Invalid PEM label
+    InvalidLabel,
+    /// PEM label is not the expected one
+    UnexpectedLabel,
+    /// Line not allowed in strict mode (header, whitespace or invalid length)
+    InvalidLine,
+    /// Invalid base64 encoding
+    InvalidBase64,
 }
 
 // XXX
@@ -60,6 +110,8 @@ pub enum Error {
     InvalidValue { tag: Tag, msg: String },
     /// Invalid Tag
     InvalidTag,
+    /// Tag number is larger than the maximum supported value (2^32 - 1)
+    TagTooLarge,
     /// Unknown tag: {0:?}
     UnknownTag(u32),
     /// Unexpected Tag (expected: {expected:?}, actual: {actual:?})
@@ -87,16 +139,35 @@ pub enum Error {
 
     /// Invalid encoding or forbidden characters in string
     StringInvalidCharset,
+    /// OID must not be empty
+    OidEmpty,
+    /// OID sub-identifier is not encoded with the minimum number of bytes
+    OidArcNotMinimal,
+    /// OID last sub-identifier is truncated
+    OidArcTruncated,
     /// Invalid Date or Time
     InvalidDateTime,
 
-    /// DER Failed constraint
+    /// DER Failed constraint: {0}
     DerConstraintFailed(DerConstraint),
+    /// CER Failed constraint: {0}
+    CerConstraintFailed(CerConstraint),
+
+    /// Size constraint failed (expected: {min}..{max}, actual: {actual})
+    SizeConstraintFailed {
+        min: usize,
+        max: usize,
+        actual: usize,
+    },
 
     /// Requesting borrowed data from a temporary object
     LifetimeError,
     /// Feature is not yet implemented
     Unsupported,
+    /// serde error: {0}
+    SerdeError(String),
+    /// PEM error: {0}
+    PemError(PemError),
 
     /// incomplete data, missing: {0:?}
     Incomplete(nom::Needed),
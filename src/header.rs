@@ -3,7 +3,7 @@ use crate::der_constraint_fail_if;
 use crate::error::*;
 #[cfg(feature = "std")]
 use crate::ToDer;
-use crate::{Class, DynTagged, FromBer, FromDer, Length, Tag, ToStatic};
+use crate::{Class, DynTagged, FromBer, FromCer, FromDer, Length, Tag, ToStatic};
 use alloc::borrow::Cow;
 use core::convert::TryFrom;
 use nom::bytes::streaming::take;
@@ -272,6 +272,11 @@ impl<'a> FromBer<'a> for Header<'a> {
 impl<'a> FromDer<'a> for Header<'a> {
     fn from_der(bytes: &'a [u8]) -> ParseResult<Self> {
         let (i1, el) = parse_identifier(bytes)?;
+        der_constraint_fail_if!(
+            bytes,
+            !is_minimal_tag_encoding(el.3, el.2),
+            DerConstraint::NonMinimalTag
+        );
         let class = match Class::try_from(el.0) {
             Ok(c) => c,
             Err(_) => unreachable!(), // Cannot fail, we have read exactly 2 bits
@@ -302,10 +307,15 @@ impl<'a> FromDer<'a> for Header<'a> {
                 let (i3, llen) = take(l1)(i2)?;
                 match bytes_to_u64(llen) {
                     Ok(l) => {
-                        // DER: should have been encoded in short form (< 127)
-                        // XXX der_constraint_fail_if!(i, l < 127);
                         let l =
                             usize::try_from(l).or(Err(::nom::Err::Error(Error::InvalidLength)))?;
+                        // DER: should have been encoded in short form (< 128), or with the minimum
+                        // number of octets (10.1)
+                        der_constraint_fail_if!(
+                            i1,
+                            !is_minimal_length_encoding(&i1[..=llen.len()], l),
+                            DerConstraint::NonMinimalLength
+                        );
                         (i3, Length::Definite(l))
                     }
                     Err(_) => {
@@ -320,6 +330,24 @@ impl<'a> FromDer<'a> for Header<'a> {
     }
 }
 
+impl<'a> FromCer<'a> for Header<'a> {
+    fn from_cer(bytes: &'a [u8]) -> ParseResult<Self> {
+        let (i, hdr) = Header::from_ber(bytes)?;
+        if hdr.constructed {
+            // X.690 section 9.1: constructed objects use the indefinite form
+            if hdr.length.is_definite() {
+                return Err(nom::Err::Error(Error::CerConstraintFailed(
+                    CerConstraint::DefiniteLength,
+                )));
+            }
+            Ok((i, hdr))
+        } else {
+            // primitive objects use the definite form, with the same rules as DER
+            Header::from_der(bytes)
+        }
+    }
+}
+
 impl DynTagged for (Class, bool, Tag) {
     fn tag(&self) -> Tag {
         self.2
@@ -353,18 +381,23 @@ impl ToDer for (Class, bool, Tag) {
         let b0 = b0 | if *constructed { 0b10_0000 } else { 0 };
         if tag.0 > 30 {
             let b0 = b0 | 0b1_1111;
-            let mut sz = writer.write(&[b0])?;
+            // high-tag-number form: base 128, most significant group first, with bit 8 set on
+            // all octets but the last (8.1.2.4.2). A u32 needs at most 5 octets.
+            let mut buf = [0u8; 6];
+            let mut idx = buf.len();
             let mut val = tag.0;
             loop {
-                if val <= 127 {
-                    sz += writer.write(&[val as u8])?;
-                    return Ok(sz);
-                } else {
-                    let b = (val & 0b0111_1111) as u8 | 0b1000_0000;
-                    sz += writer.write(&[b])?;
-                    val >>= 7;
+                idx -= 1;
+                let more = if idx == buf.len() - 1 { 0 } else { 0b1000_0000 };
+                buf[idx] = (val & 0b0111_1111) as u8 | more;
+                val >>= 7;
+                if val == 0 {
+                    break;
                 }
             }
+            buf[idx - 1] = b0;
+            let sz = writer.write(&buf[idx - 1..])?;
+            Ok(sz)
         } else {
             let b0 = b0 | (tag.0 as u8);
             let sz = writer.write(&[b0])?;
@@ -137,6 +137,40 @@
 //! If the parsing succeeds, but the integer cannot fit into the expected type, the method will return
 //! an `IntegerTooLarge` error.
 //!
+//! # CER parsers/encoders
+//!
+//! [[X.690]] also defines Canonical Encoding Rules (CER), which are required by a few protocols.
+//! CER is similar to DER, except that constructed objects use the indefinite length form, and that
+//! strings longer than 1000 octets are split into segments.
+//!
+//! CER objects are parsed using the [`FromCer`] trait (which checks the CER constraints), and
+//! encoded using the [`ToCer`] trait.
+//!
+//! # DER validation
+//!
+//! The [`validate_der`] function checks an arbitrary encoding against the DER rules, without
+//! requiring a schema. It reports all violations found, with their offsets in the input.
+//!
+//! # Schema-less values
+//!
+//! [`Asn1Value`] is an owned tree of values, decoded from any BER or DER object. It can be
+//! inspected or modified, and encoded again using [`ToDer`].
+//!
+//! # Pretty-printing
+//!
+//! [`TreeFormatter`] displays BER/DER objects as an indented tree (similar to `openssl asn1parse`)
+//! or using the ASN.1 value notation. [`Any::display_tree`] is a shortcut for the default options.
+//!
+//! The `asn1-tool` binary (enabled by the `cli` feature) provides the same features from the
+//! command line: it can dump, validate, extract and convert objects read from binary, PEM, base64
+//! or hexadecimal files (`cargo install asn1-rs --features cli`).
+//!
+//! # PEM
+//!
+//! When the `pem` feature is enabled, `Pem` parses and writes PEM blocks (RFC 7468), and the
+//! `FromPem` and `ToPem` traits decode and encode objects using DER inside PEM blocks. Base64
+//! is implemented in the crate, so this feature does not require `std` (except for `ToPem`).
+//!
 //! ## Changes
 //!
 //! See `CHANGELOG.md`.
@@ -180,26 +214,44 @@ extern crate core;
 // #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(all(feature = "serde", feature = "std"))]
+mod asn1_serde;
 mod asn1_types;
 mod ber;
+mod cer;
 mod class;
+mod const_int;
 mod datetime;
 mod derive;
 mod error;
 mod header;
 mod length;
+mod macros;
+#[cfg(feature = "pem")]
+mod pem;
 mod tag;
 mod traits;
+mod tree;
+mod validate;
+mod value;
 
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use asn1_serde::*;
 pub use asn1_types::*;
 pub use class::*;
+pub use const_int::*;
 pub use datetime::*;
 pub use derive::*;
 pub use error::*;
 pub use header::*;
 pub use length::*;
+#[cfg(feature = "pem")]
+pub use pem::*;
 pub use tag::*;
 pub use traits::*;
+pub use tree::*;
+pub use validate::*;
+pub use value::*;
 
 pub use nom;
 pub use nom::{Err, IResult, Needed};
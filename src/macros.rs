@@ -0,0 +1,49 @@
+/// Helper macro to encode DER objects at compile-time
+///
+/// The `der!` macro takes a description of a value, using a notation close to ASN.1, and
+/// expands to its DER encoding as a `&'static [u8]`. This is useful for fixed structures
+/// that are embedded in code, for example algorithm identifiers.
+///
+/// The following values are supported:
+///
+/// - `SEQUENCE { v1, v2, ... }` and `SET { v1, v2, ... }` (elements of a `SET` are sorted)
+/// - `[n] v`, `[n] EXPLICIT v` and `[n] IMPLICIT v` for context-specific tagged values, and
+///   `[APPLICATION n] v` or `[PRIVATE n] v` for other classes
+/// - `OID 1.2.840.113549`, `NULL`, `BOOLEAN true`, `INTEGER -123`
+/// - `OCTET STRING "01 02 ff"` (hex-encoded) or `OCTET STRING b"bytes"`
+/// - `BIT STRING "80"` (hex-encoded). The value is always a whole number of octets, and is
+///   encoded with 0 unused bits: bit strings with unused bits cannot be described.
+/// - `UTF8String "..."`, `PrintableString "..."`, `IA5String "..."`, `NumericString "..."`
+///   and `VisibleString "..."`
+///
+/// Invalid input (unknown type, invalid OID or hex string, characters not allowed in the
+/// string type, etc.) results in a compilation error.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::der;
+///
+/// // AlgorithmIdentifier for sha256WithRSAEncryption
+/// const SHA256_WITH_RSA: &[u8] = der! {
+///     SEQUENCE { OID 1.2.840.113549.1.1.11, NULL }
+/// };
+///
+/// assert_eq!(
+///     SHA256_WITH_RSA,
+///     &[0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b, 0x05, 0x00]
+/// );
+/// ```
+///
+/// ```compile_fail
+/// use asn1_rs::der;
+///
+/// // `_` is not allowed in a PrintableString
+/// const S: &[u8] = der!(PrintableString "not_printable");
+/// ```
+#[macro_export]
+macro_rules! der {
+    ($($tokens:tt)*) => {
+        &$crate::exports::asn1_rs_impl::encode_der!($($tokens)*)
+    };
+}
@@ -0,0 +1,351 @@
+//! PEM textual encoding (RFC 7468)
+
+use crate::{Error, FromDer, PemError, Result};
+#[cfg(feature = "std")]
+use crate::{SerializeResult, ToDer};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+mod base64;
+pub use base64::*;
+
+/// A PEM block: a label, and the (decoded) content
+///
+/// PEM blocks are parsed using [`Pem::parse`] and [`Pem::parse_all`] (or a [`PemParser`], to
+/// choose the parsing rules). The content is usually a DER object, which can be decoded using
+/// [`Pem::parse_der`], or using the [`FromPem`] trait for types that do not borrow their input.
+///
+/// `Pem` implements `Display`, writing the block using the strict encoding from RFC 7468.
+///
+/// ```rust
+/// use asn1_rs::Pem;
+///
+/// let input = "Some text before the block
+/// -----BEGIN DATA-----
+/// AgEF
+/// -----END DATA-----
+/// ";
+/// let (rem, pem) = Pem::parse(input).expect("parsing failed");
+/// assert!(rem.is_empty());
+/// assert_eq!(pem.label, "DATA");
+/// assert_eq!(pem.parse_der::<u32>(), Ok(5));
+/// assert_eq!(pem.to_string(), "-----BEGIN DATA-----\nAgEF\n-----END DATA-----\n");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pem {
+    /// The label, from the begin and end lines (for ex. `CERTIFICATE`)
+    pub label: String,
+    /// The decoded content
+    pub contents: Vec<u8>,
+}
+
+impl Pem {
+    /// Label of X.509 certificates
+    pub const CERTIFICATE: &'static str = "CERTIFICATE";
+    /// Label of X.509 certificate revocation lists
+    pub const X509_CRL: &'static str = "X509 CRL";
+    /// Label of PKCS #10 certification requests
+    pub const CERTIFICATE_REQUEST: &'static str = "CERTIFICATE REQUEST";
+    /// Label of PKCS #7 structures
+    pub const PKCS7: &'static str = "PKCS7";
+    /// Label of CMS structures
+    pub const CMS: &'static str = "CMS";
+    /// Label of PKCS #8 private keys
+    pub const PRIVATE_KEY: &'static str = "PRIVATE KEY";
+    /// Label of PKCS #8 encrypted private keys
+    pub const ENCRYPTED_PRIVATE_KEY: &'static str = "ENCRYPTED PRIVATE KEY";
+    /// Label of subject public key info structures
+    pub const PUBLIC_KEY: &'static str = "PUBLIC KEY";
+
+    /// Build a PEM block
+    pub fn new(label: &str, contents: Vec<u8>) -> Self {
+        Pem {
+            label: label.to_string(),
+            contents,
+        }
+    }
+
+    /// Parse the first PEM block of `input`, using the lax rules
+    ///
+    /// Return the remaining input (after the end line) and the block. See [`PemParser`].
+    pub fn parse(input: &str) -> Result<(&str, Pem)> {
+        PemParser::new().parse(input)
+    }
+
+    /// Parse all PEM blocks of `input`, using the lax rules
+    ///
+    /// See [`PemParser`].
+    pub fn parse_all(input: &str) -> Result<Vec<Pem>> {
+        PemParser::new().parse_all(input)
+    }
+
+    /// Return true if `label` is a valid label (RFC 7468 section 3)
+    ///
+    /// Labels are made of printable ASCII characters, and can contain spaces and hyphens, except
+    /// at the beginning or the end. The empty label is valid.
+    pub fn is_valid_label(label: &str) -> bool {
+        let is_label_char = |c: u8| (0x21..=0x7e).contains(&c) && c != b'-';
+        let bytes = label.as_bytes();
+        match (bytes.first(), bytes.last()) {
+            (Some(&first), Some(&last)) => {
+                is_label_char(first)
+                    && is_label_char(last)
+                    && bytes
+                        .iter()
+                        .all(|&c| is_label_char(c) || c == b'-' || c == b' ')
+            }
+            _ => true,
+        }
+    }
+
+    /// Return an error if the label of the block is not `label`
+    pub fn check_label(&self, label: &str) -> Result<()> {
+        if self.label == label {
+            Ok(())
+        } else {
+            Err(Error::PemError(PemError::UnexpectedLabel))
+        }
+    }
+
+    /// Decode the content as a DER object
+    ///
+    /// The content must be exactly one object.
+    pub fn parse_der<'a, T>(&'a self) -> Result<T>
+    where
+        T: FromDer<'a>,
+    {
+        let (rem, t) = T::from_der(&self.contents)?;
+        if !rem.is_empty() {
+            return Err(Error::InvalidLength);
+        }
+        Ok(t)
+    }
+}
+
+impl fmt::Display for Pem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "-----BEGIN {}-----", self.label)?;
+        let encoded = base64_encode(&self.contents);
+        // base64 is ASCII, so lines can be split at any byte
+        for line in encoded.as_bytes().chunks(64) {
+            f.write_str(core::str::from_utf8(line).map_err(|_| fmt::Error)?)?;
+            f.write_str("\n")?;
+        }
+        writeln!(f, "-----END {}-----", self.label)
+    }
+}
+
+/// Parser for PEM blocks, with the lax or strict rules from RFC 7468
+///
+/// With both rules, the text before the begin line is ignored, labels must be valid and the
+/// labels of the begin and end lines must match.
+///
+/// The lax rules (the default) accept whitespace anywhere in the encoded data, lines of any
+/// length, and skip headers from RFC 1421 (lines containing `:`). The strict rules require
+/// lines of exactly 64 characters (except the last one), without whitespace or headers.
+///
+/// ```rust
+/// use asn1_rs::PemParser;
+///
+/// let input = "-----BEGIN DATA-----\n  AgEF  \n-----END DATA-----\n";
+/// assert!(PemParser::new().parse(input).is_ok());
+/// assert!(PemParser::new().with_strict(true).parse(input).is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PemParser {
+    strict: bool,
+}
+
+impl PemParser {
+    /// Create a parser using the lax rules
+    pub const fn new() -> Self {
+        PemParser { strict: false }
+    }
+
+    /// Use the strict rules
+    pub const fn with_strict(self, strict: bool) -> Self {
+        Self { strict }
+    }
+
+    /// Parse the first PEM block of `input`
+    ///
+    /// Return the remaining input (after the end line) and the block.
+    pub fn parse<'i>(&self, input: &'i str) -> Result<(&'i str, Pem)> {
+        let mut lines = Lines { input };
+        let label = loop {
+            let line = match lines.next() {
+                Some(line) => self.trim(line),
+                None => return Err(Error::PemError(PemError::MissingBegin)),
+            };
+            if let Some(label) = encapsulation_label(line, "BEGIN") {
+                break label;
+            }
+        };
+        if !Pem::is_valid_label(label) {
+            return Err(Error::PemError(PemError::InvalidLabel));
+        }
+        let mut encoded = String::new();
+        let mut line_len = None;
+        loop {
+            let line = match lines.next() {
+                Some(line) => self.trim(line),
+                None => return Err(Error::PemError(PemError::MissingEnd)),
+            };
+            if let Some(end_label) = encapsulation_label(line, "END") {
+                if end_label != label {
+                    return Err(Error::PemError(PemError::LabelMismatch));
+                }
+                break;
+            }
+            if self.strict {
+                // all lines but the last one must have 64 characters
+                if line_len.map_or(false, |len| len != 64)
+                    || line.is_empty()
+                    || line.len() > 64
+                    || line.bytes().any(|b| b.is_ascii_whitespace())
+                {
+                    return Err(Error::PemError(PemError::InvalidLine));
+                }
+                line_len = Some(line.len());
+            } else if line.contains(':') {
+                // header (RFC 1421)
+                continue;
+            }
+            encoded.extend(line.chars().filter(|c| !c.is_ascii_whitespace()));
+        }
+        let contents = base64_decode(&encoded)?;
+        Ok((lines.input, Pem::new(label, contents)))
+    }
+
+    /// Parse all PEM blocks of `input`
+    ///
+    /// The text after the last block is ignored. Return an error if there is no block.
+    pub fn parse_all(&self, input: &str) -> Result<Vec<Pem>> {
+        let (mut rem, pem) = self.parse(input)?;
+        let mut v = alloc::vec![pem];
+        loop {
+            match self.parse(rem) {
+                Ok((r, pem)) => {
+                    v.push(pem);
+                    rem = r;
+                }
+                Err(Error::PemError(PemError::MissingBegin)) => return Ok(v),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn trim<'i>(&self, line: &'i str) -> &'i str {
+        if self.strict {
+            line
+        } else {
+            line.trim()
+        }
+    }
+}
+
+/// Iterator over lines, ending with LF or CRLF (the end of line is not returned)
+struct Lines<'i> {
+    input: &'i str,
+}
+
+impl<'i> Iterator for Lines<'i> {
+    type Item = &'i str;
+
+    fn next(&mut self) -> Option<&'i str> {
+        if self.input.is_empty() {
+            return None;
+        }
+        let (line, rem) = match self.input.find('\n') {
+            Some(idx) => (&self.input[..idx], &self.input[idx + 1..]),
+            None => (self.input, ""),
+        };
+        self.input = rem;
+        Some(line.strip_suffix('\r').unwrap_or(line))
+    }
+}
+
+/// Return the label of a `-----BEGIN label-----` or `-----END label-----` line
+fn encapsulation_label<'i>(line: &'i str, kind: &str) -> Option<&'i str> {
+    line.strip_prefix("-----")?
+        .strip_prefix(kind)?
+        .strip_prefix(' ')?
+        .strip_suffix("-----")
+}
+
+/// Decode objects from PEM blocks
+///
+/// This trait is implemented for all types which can be decoded from DER without borrowing the
+/// input (for ex. `u32`, `String` or [`Asn1Value`](crate::Asn1Value)). Other types can be decoded
+/// from a parsed block using [`Pem::parse_der`].
+///
+/// ```rust
+/// use asn1_rs::{FromPem, Pem};
+///
+/// let input = "-----BEGIN DATA-----\nAgEF\n-----END DATA-----\n";
+/// assert_eq!(u32::from_pem(input, "DATA"), Ok(5));
+/// assert!(u32::from_pem(input, Pem::CERTIFICATE).is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+pub trait FromPem: Sized {
+    /// Decode the content of a PEM block, after checking its label
+    fn from_pem_block(pem: &Pem, label: &str) -> Result<Self>;
+
+    /// Decode the first PEM block of `input`, which must have the label `label`
+    fn from_pem(input: &str, label: &str) -> Result<Self> {
+        let (_, pem) = Pem::parse(input)?;
+        Self::from_pem_block(&pem, label)
+    }
+
+    /// Decode all PEM blocks of `input`, which must all have the label `label`
+    fn from_pem_all(input: &str, label: &str) -> Result<Vec<Self>> {
+        Pem::parse_all(input)?
+            .iter()
+            .map(|pem| Self::from_pem_block(pem, label))
+            .collect()
+    }
+}
+
+impl<T> FromPem for T
+where
+    T: for<'a> FromDer<'a>,
+{
+    fn from_pem_block(pem: &Pem, label: &str) -> Result<Self> {
+        pem.check_label(label)?;
+        pem.parse_der()
+    }
+}
+
+/// Encode objects as PEM blocks
+///
+/// This trait is implemented for all types implementing [`ToDer`].
+///
+/// ```rust
+/// use asn1_rs::ToPem;
+///
+/// let pem = 5u32.to_pem("DATA").expect("serialization failed");
+/// assert_eq!(pem, "-----BEGIN DATA-----\nAgEF\n-----END DATA-----\n");
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "pem", feature = "std"))))]
+pub trait ToPem {
+    /// Encode the object using DER, and return the PEM block with the label `label`
+    fn to_pem(&self, label: &str) -> SerializeResult<String>;
+}
+
+#[cfg(feature = "std")]
+impl<T> ToPem for T
+where
+    T: ToDer + ?Sized,
+{
+    fn to_pem(&self, label: &str) -> SerializeResult<String> {
+        if !Pem::is_valid_label(label) {
+            return Err(Error::PemError(PemError::InvalidLabel).into());
+        }
+        let pem = Pem::new(label, self.to_der_vec()?);
+        Ok(pem.to_string())
+    }
+}
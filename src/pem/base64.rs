@@ -0,0 +1,77 @@
+//! Base64 encoding (RFC 4648 section 4), without external dependencies
+
+use crate::{Error, PemError, Result};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode data using base64, with padding
+#[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+pub fn base64_encode(data: &[u8]) -> String {
+    let mut s = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                s.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                s.push('=');
+            }
+        }
+    }
+    s
+}
+
+/// Decode base64 data
+///
+/// Padding is required, and whitespace is not allowed. The unused bits of the last character
+/// before padding must be zero (RFC 4648 section 3.5), so every input has a single decoding.
+#[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+pub fn base64_decode(input: &str) -> Result<Vec<u8>> {
+    let input = input.as_bytes();
+    if input.len() % 4 != 0 {
+        return Err(Error::PemError(PemError::InvalidBase64));
+    }
+    let mut v = Vec::with_capacity(input.len() / 4 * 3);
+    let nchunks = input.len() / 4;
+    for (idx, chunk) in input.chunks(4).enumerate() {
+        // padding is allowed only at the end of the last chunk
+        let padding = if idx + 1 == nchunks {
+            chunk.iter().rev().take_while(|&&c| c == b'=').count()
+        } else {
+            0
+        };
+        if padding > 2 {
+            return Err(Error::PemError(PemError::InvalidBase64));
+        }
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            n = (n << 6) | u32::from(decode_char(c)?);
+        }
+        // bits that are not part of the decoded bytes
+        if n & ((1 << (2 * padding)) - 1) != 0 {
+            return Err(Error::PemError(PemError::InvalidBase64));
+        }
+        n <<= 6 * padding as u32;
+        let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        v.extend_from_slice(&bytes[..3 - padding]);
+    }
+    Ok(v)
+}
+
+fn decode_char(c: u8) -> Result<u8> {
+    match c {
+        b'A'..=b'Z' => Ok(c - b'A'),
+        b'a'..=b'z' => Ok(c - b'a' + 26),
+        b'0'..=b'9' => Ok(c - b'0' + 52),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => Err(Error::PemError(PemError::InvalidBase64)),
+    }
+}
@@ -5,7 +5,8 @@ use rusticata_macros::newtype_enum;
 /// BER/DER Tag as defined in X.680 section 8.4
 ///
 /// X.690 doesn't specify the maximum tag size so we're assuming that people
-/// aren't going to need anything more than a u32.
+/// aren't going to need anything more than a u32: the maximum tag number is [`Tag::MAX`].
+/// Parsing a larger tag number fails with [`Error::TagTooLarge`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Tag(pub u32);
 
@@ -48,6 +49,9 @@ impl display Tag {
 }
 
 impl Tag {
+    /// The maximum supported tag number
+    pub const MAX: Tag = Tag(u32::MAX);
+
     pub const fn assert_eq(&self, tag: Tag) -> Result<()> {
         if self.0 == tag.0 {
             Ok(())
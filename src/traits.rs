@@ -14,11 +14,17 @@ pub enum BerParser {}
 #[derive(Debug)]
 pub enum DerParser {}
 
+/// Phantom type representing a CER parser
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum CerParser {}
+
 #[doc(hidden)]
 pub trait ASN1Parser {}
 
 impl ASN1Parser for BerParser {}
 impl ASN1Parser for DerParser {}
+impl ASN1Parser for CerParser {}
 
 pub trait Tagged {
     const TAG: Tag;
@@ -200,6 +206,107 @@ pub trait CheckDerConstraints {
     fn check_constraints(any: &Any) -> Result<()>;
 }
 
+/// Base trait for CER object parsers
+///
+/// CER (X.690 section 9) is a restricted form of BER, similar to DER except for constructed
+/// objects and long strings: constructed objects must use the indefinite length form, and
+/// strings longer than 1000 octets must be split into segments of 1000 octets.
+///
+/// As for [`FromDer`], the simplest way to implement this trait is to implement
+/// [`TryFrom<Any>`] and [`CheckCerConstraints`], and the [`CerAutoDerive`] marker trait.
+///
+/// # Examples
+///
+/// ```
+/// use asn1_rs::{FromCer, OctetString};
+///
+/// // constructed OCTET STRING, using the indefinite length form
+/// let mut input = vec![0x24, 0x80, 0x04, 0x82, 0x03, 0xe8];
+/// input.extend_from_slice(&[0xaa; 1000]);
+/// input.extend_from_slice(&[0x04, 0x01, 0xbb, 0x00, 0x00]);
+///
+/// let (rem, s) = OctetString::from_cer(&input).expect("parsing failed");
+/// assert!(rem.is_empty());
+/// assert_eq!(s.as_ref().len(), 1001);
+/// ```
+pub trait FromCer<'a, E = Error>: Sized {
+    /// Attempt to parse input bytes into a CER object (enforcing constraints)
+    fn from_cer(bytes: &'a [u8]) -> ParseResult<'a, Self, E>;
+}
+
+/// Trait to automatically derive `FromCer`
+///
+/// This trait is only a marker, similar to [`DerAutoDerive`]: after implementing
+/// [`TryFrom<Any>`] and [`CheckCerConstraints`] for a type, a free [`FromCer`] implementation is
+/// provided by implementing this trait.
+pub trait CerAutoDerive {}
+
+impl<'a, T, E> FromCer<'a, E> for T
+where
+    T: TryFrom<Any<'a>, Error = E>,
+    T: CheckCerConstraints,
+    T: CerAutoDerive,
+    E: From<Error>,
+{
+    fn from_cer(bytes: &'a [u8]) -> ParseResult<T, E> {
+        let (i, any) = Any::from_cer(bytes).map_err(nom::Err::convert)?;
+        <T as CheckCerConstraints>::check_cer_constraints(&any)
+            .map_err(|e| nom::Err::Error(e.into()))?;
+        let result = any.try_into().map_err(nom::Err::Error)?;
+        Ok((i, result))
+    }
+}
+
+/// Verification of CER constraints
+///
+/// The header (length form) is already checked when parsing the object using
+/// [`Any::from_cer`](crate::Any), so this function only has to check the content.
+pub trait CheckCerConstraints {
+    fn check_cer_constraints(any: &Any) -> Result<()>;
+}
+
+/// Common trait for all objects that can be encoded using the CER representation
+///
+/// Primitive objects are encoded as in DER, except strings longer than 1000 octets, which are
+/// split into segments. Constructed objects use the indefinite length form.
+///
+/// # Examples
+///
+/// ```
+/// use asn1_rs::{Sequence, ToCer};
+///
+/// let seq = Sequence::new(vec![0x02, 0x01, 0x04].into());
+/// let v = seq.to_cer_vec().expect("serialization failed");
+///
+/// assert_eq!(&v, &[0x30, 0x80, 0x02, 0x01, 0x04, 0x00, 0x00]);
+/// ```
+#[cfg(feature = "std")]
+pub trait ToCer
+where
+    Self: DynTagged,
+{
+    /// Write the CER encoded representation to a newly allocated `Vec<u8>`.
+    fn to_cer_vec(&self) -> SerializeResult<Vec<u8>> {
+        let mut v = Vec::new();
+        let _ = self.write_cer(&mut v)?;
+        Ok(v)
+    }
+
+    /// Attempt to write the CER encoded representation (header and content) into this writer.
+    fn write_cer(&self, writer: &mut dyn Write) -> SerializeResult<usize>;
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> ToCer for &'a T
+where
+    T: ToCer,
+    &'a T: DynTagged,
+{
+    fn write_cer(&self, writer: &mut dyn Write) -> SerializeResult<usize> {
+        (*self).write_cer(writer)
+    }
+}
+
 /// Common trait for all objects that can be encoded using the DER representation
 ///
 /// # Examples
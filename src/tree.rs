@@ -0,0 +1,511 @@
+//! Pretty-printing of BER/DER objects, without schema
+
+use crate::ber::{ber_get_object_content, MAX_RECURSION};
+use crate::{Any, Asn1Value, Class, Error, FromBer, Header, Length, Result, Tag};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// Options to format BER/DER objects as text
+///
+/// Two styles are supported:
+///
+/// - a tree (the default), with one line per object, similar to `openssl asn1parse`. Each line
+///   starts with the offset of the object in the input, its depth, the length of its header
+///   (`hl`) and content (`l`, or `inf` for the indefinite form), and whether it is constructed
+///   (`cons`) or primitive (`prim`).
+/// - the ASN.1 value notation (X.680), see [`with_value_notation`](Self::with_value_notation).
+///
+/// Objects are parsed using BER rules, so that any valid encoding can be displayed. Values of
+/// universal types are decoded, and the content of constructed objects is displayed as a list of
+/// objects. The content of tagged objects is displayed as objects if they are constructed
+/// (`EXPLICIT` tagging), and as hexadecimal otherwise. Errors are displayed as comments, and stop
+/// the parsing of the enclosing object.
+///
+/// ```rust
+/// use asn1_rs::TreeFormatter;
+///
+/// // SEQUENCE { OID, UTF8String, [0] { NULL } }
+/// let bytes = &[
+///     0x30, 0x0d, 0x06, 0x03, 0x2a, 0x03, 0x04, 0x0c, 0x02, 0x61, 0x62, 0xa0, 0x02, 0x05, 0x00,
+/// ];
+/// let tree = TreeFormatter::new().display(bytes).to_string();
+/// assert_eq!(
+///     tree,
+///     "    0:d=0  hl=2 l=  13 cons: SEQUENCE
+///     2:d=1  hl=2 l=   3 prim:   OBJECT IDENTIFIER 1.2.3.4
+///     7:d=1  hl=2 l=   2 prim:   UTF8String \"ab\"
+///    11:d=1  hl=2 l=   2 cons:   [0]
+///    13:d=2  hl=2 l=   0 prim:     NULL
+/// "
+/// );
+///
+/// let value = TreeFormatter::new()
+///     .with_value_notation(true)
+///     .display(bytes)
+///     .to_string();
+/// assert_eq!(
+///     value,
+///     "{
+///   { 1 2 3 4 },
+///   \"ab\",
+///   [0] NULL
+/// }
+/// "
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TreeFormatter {
+    offsets: bool,
+    indent: usize,
+    max_bytes: usize,
+    value_notation: bool,
+}
+
+impl TreeFormatter {
+    /// Create a formatter with the default options
+    pub const fn new() -> Self {
+        TreeFormatter {
+            offsets: true,
+            indent: 2,
+            max_bytes: 64,
+            value_notation: false,
+        }
+    }
+
+    /// Show offsets and lengths of objects (tree only, default: `true`)
+    pub const fn with_offsets(self, offsets: bool) -> Self {
+        Self { offsets, ..self }
+    }
+
+    /// Set the number of spaces for each level of indentation (default: 2)
+    pub const fn with_indent(self, indent: usize) -> Self {
+        Self { indent, ..self }
+    }
+
+    /// Set the maximum number of bytes displayed for binary values (tree only, default: 64)
+    ///
+    /// Longer values are truncated, and followed by `...`.
+    pub const fn with_max_bytes(self, max_bytes: usize) -> Self {
+        Self { max_bytes, ..self }
+    }
+
+    /// Use the ASN.1 value notation instead of a tree (default: `false`)
+    ///
+    /// Since there is no schema, components are written without their names, `SEQUENCE`, `SET`
+    /// and their `OF` variants are all written as `{ ... }`, and tagged values are prefixed with
+    /// their tag, as in the type notation. Values are never truncated.
+    pub const fn with_value_notation(self, value_notation: bool) -> Self {
+        Self {
+            value_notation,
+            ..self
+        }
+    }
+
+    /// Return an object displaying all the BER objects from `bytes`
+    pub fn display<'a>(&self, bytes: &'a [u8]) -> TreeDisplay<'a> {
+        TreeDisplay {
+            formatter: *self,
+            input: Input::Bytes(bytes),
+        }
+    }
+
+    /// Return an object displaying `any` and its content
+    ///
+    /// Offsets are relative to the start of the object, assuming its length is encoded with the
+    /// minimum number of octets.
+    pub fn display_any<'a>(&self, any: &'a Any<'a>) -> TreeDisplay<'a> {
+        TreeDisplay {
+            formatter: *self,
+            input: Input::Any(any),
+        }
+    }
+
+    fn write_indent(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}", "", indent = depth * self.indent)
+    }
+
+    fn write_tree(&self, f: &mut fmt::Formatter, obj: &Object, depth: usize) -> fmt::Result {
+        if self.offsets {
+            write!(
+                f,
+                "{:>5}:d={:<2} hl={} l=",
+                obj.offset, depth, obj.header_len
+            )?;
+            match obj.header.length() {
+                Length::Definite(l) => write!(f, "{:>4}", l)?,
+                Length::Indefinite => f.write_str(" inf")?,
+            }
+            let form = if obj.header.is_constructed() {
+                "cons"
+            } else {
+                "prim"
+            };
+            write!(f, " {}: ", form)?;
+        }
+        self.write_indent(f, depth)?;
+        write_type(f, &obj.header)?;
+        if obj.header.is_constructed() {
+            f.write_str("\n")?;
+            let (elements, error) = obj.elements(depth);
+            for element in &elements {
+                self.write_tree(f, element, depth + 1)?;
+            }
+            if let Some((offset, e)) = error {
+                self.write_tree_error(f, offset, depth + 1, &e)?;
+            }
+            Ok(())
+        } else {
+            if !(obj.header.is_universal() && obj.header.tag() == Tag::Null) {
+                f.write_str(" ")?;
+                self.write_primitive(f, obj)?;
+            }
+            f.write_str("\n")
+        }
+    }
+
+    fn write_tree_error(
+        &self,
+        f: &mut fmt::Formatter,
+        offset: usize,
+        depth: usize,
+        e: &Error,
+    ) -> fmt::Result {
+        if self.offsets {
+            write!(f, "{:>5}:d={:<2} ", offset, depth)?;
+        }
+        self.write_indent(f, depth)?;
+        writeln!(f, "-- error: {} --", e)
+    }
+
+    /// Write a value using the value notation, without the final newline
+    fn write_value(&self, f: &mut fmt::Formatter, obj: &Object, depth: usize) -> fmt::Result {
+        if !obj.header.is_universal() {
+            write_type(f, &obj.header)?;
+            f.write_str(" ")?;
+            if !obj.header.is_constructed() {
+                return write_hex(f, obj.data, None);
+            }
+            if let (elements, None) = obj.elements(depth) {
+                if elements.len() == 1 {
+                    return self.write_value(f, &elements[0], depth);
+                }
+            }
+        }
+        if !obj.header.is_constructed() {
+            return self.write_primitive(f, obj);
+        }
+        let (elements, error) = obj.elements(depth);
+        if elements.is_empty() && error.is_none() {
+            return f.write_str("{}");
+        }
+        f.write_str("{\n")?;
+        for (idx, element) in elements.iter().enumerate() {
+            self.write_indent(f, depth + 1)?;
+            self.write_value(f, element, depth + 1)?;
+            if idx + 1 < elements.len() {
+                f.write_str(",")?;
+            }
+            f.write_str("\n")?;
+        }
+        if let Some((_, e)) = error {
+            self.write_indent(f, depth + 1)?;
+            writeln!(f, "-- error: {} --", e)?;
+        }
+        self.write_indent(f, depth)?;
+        f.write_str("}")
+    }
+
+    /// Write the value of a primitive object
+    fn write_primitive(&self, f: &mut fmt::Formatter, obj: &Object) -> fmt::Result {
+        let max_bytes = if self.value_notation {
+            None
+        } else {
+            Some(self.max_bytes)
+        };
+        if !obj.header.is_universal() {
+            return write_hex(f, obj.data, max_bytes);
+        }
+        let any = Any::new(obj.header.clone(), obj.data);
+        let value = match Asn1Value::try_from(&any) {
+            Ok(value) => value,
+            Err(e) => {
+                write_hex(f, obj.data, max_bytes)?;
+                return write!(f, " -- error: {} --", e);
+            }
+        };
+        match &value {
+            Asn1Value::Boolean(b) => f.write_str(if *b { "TRUE" } else { "FALSE" }),
+            Asn1Value::Integer(i) => match i.as_i128() {
+                Ok(i) => write!(f, "{}", i),
+                Err(_) => write_hex(f, obj.data, max_bytes),
+            },
+            Asn1Value::Enumerated(e) => match e.as_integer().as_i128() {
+                Ok(i) => write!(f, "{}", i),
+                Err(_) => write_hex(f, obj.data, max_bytes),
+            },
+            Asn1Value::BitString(b) if b.unused_bits > 0 && !b.data.is_empty() => {
+                let bytes = truncate(&b.data, max_bytes);
+                let nbits = if bytes.len() < b.data.len() {
+                    bytes.len() * 8
+                } else {
+                    bytes.len() * 8 - b.unused_bits as usize
+                };
+                f.write_str("'")?;
+                for idx in 0..nbits {
+                    let bit = (bytes[idx / 8] >> (7 - idx % 8)) & 1;
+                    write!(f, "{}", bit)?;
+                }
+                if bytes.len() < b.data.len() {
+                    f.write_str("...")?;
+                }
+                f.write_str("'B")
+            }
+            Asn1Value::BitString(b) => write_hex(f, &b.data, max_bytes),
+            Asn1Value::OctetString(v) => write_hex(f, v, max_bytes),
+            Asn1Value::Null => f.write_str("NULL"),
+            Asn1Value::Oid(oid) => {
+                if self.value_notation {
+                    if let Some(arcs) = oid.iter() {
+                        f.write_str("{")?;
+                        for arc in arcs {
+                            write!(f, " {}", arc)?;
+                        }
+                        return f.write_str(" }");
+                    }
+                }
+                write!(f, "{:#}", oid)
+            }
+            Asn1Value::Real(r) => write!(f, "{}", r),
+            Asn1Value::String(_, s) => write_cstring(f, s),
+            // time values are strings in the value notation
+            Asn1Value::UtcTime(_) | Asn1Value::GeneralizedTime(_) => {
+                write_cstring(f, &String::from_utf8_lossy(obj.data))
+            }
+            Asn1Value::Sequence(_) | Asn1Value::Set(_) | Asn1Value::Raw { .. } => {
+                write_hex(f, obj.data, max_bytes)
+            }
+        }
+    }
+}
+
+impl Default for TreeFormatter {
+    fn default() -> Self {
+        TreeFormatter::new()
+    }
+}
+
+/// Display BER/DER objects using a [`TreeFormatter`]
+#[derive(Clone, Copy, Debug)]
+pub struct TreeDisplay<'a> {
+    formatter: TreeFormatter,
+    input: Input<'a>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Input<'a> {
+    Bytes(&'a [u8]),
+    Any(&'a Any<'a>),
+}
+
+impl<'a> fmt::Display for TreeDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (objects, error) = match self.input {
+            Input::Bytes(bytes) => parse_objects(bytes, 0, MAX_RECURSION),
+            Input::Any(any) => {
+                let obj = Object {
+                    offset: 0,
+                    header: any.header.clone(),
+                    header_len: header_len(&any.header),
+                    data: any.data,
+                };
+                (alloc::vec![obj], None)
+            }
+        };
+        let formatter = &self.formatter;
+        for obj in &objects {
+            if formatter.value_notation {
+                formatter.write_value(f, obj, 0)?;
+                f.write_str("\n")?;
+            } else {
+                formatter.write_tree(f, obj, 0)?;
+            }
+        }
+        match error {
+            Some((_, e)) if formatter.value_notation => writeln!(f, "-- error: {} --", e),
+            Some((offset, e)) => formatter.write_tree_error(f, offset, 0, &e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Any<'a> {
+    /// Return an object displaying this object and its content as a tree
+    ///
+    /// This is a shortcut for `TreeFormatter::new().display_any(self)`, see [`TreeFormatter`]
+    /// for the format and options.
+    ///
+    /// ```rust
+    /// use asn1_rs::{Any, FromBer};
+    ///
+    /// let (_, any) = Any::from_ber(&[0x30, 0x03, 0x02, 0x01, 0x05]).expect("parsing failed");
+    /// println!("{}", any.display_tree());
+    /// ```
+    pub fn display_tree(&self) -> TreeDisplay<'_> {
+        TreeFormatter::new().display_any(self)
+    }
+}
+
+/// An object parsed from the input
+struct Object<'a> {
+    /// Offset of the object (start of its identifier octets) in the input
+    offset: usize,
+    header: Header<'a>,
+    header_len: usize,
+    data: &'a [u8],
+}
+
+impl<'a> Object<'a> {
+    /// Parse the elements of a constructed object at depth `depth`
+    fn elements(&self, depth: usize) -> (Vec<Object<'a>>, Option<(usize, Error)>) {
+        let offset = self.offset + self.header_len;
+        if depth + 1 >= MAX_RECURSION {
+            return (Vec::new(), Some((offset, Error::BerMaxDepth)));
+        }
+        parse_objects(self.data, offset, MAX_RECURSION - depth - 1)
+    }
+}
+
+/// Parse all objects from `bytes`, and return them with the first error, if any
+///
+/// `base` is the offset of `bytes` in the complete input.
+fn parse_objects(
+    bytes: &[u8],
+    base: usize,
+    max_depth: usize,
+) -> (Vec<Object<'_>>, Option<(usize, Error)>) {
+    let mut objects = Vec::new();
+    let mut i = bytes;
+    while !i.is_empty() {
+        let offset = base + bytes.len() - i.len();
+        match parse_object(i, offset, max_depth) {
+            Ok((rem, obj)) => {
+                objects.push(obj);
+                i = rem;
+            }
+            Err(e) => return (objects, Some((offset, e))),
+        }
+    }
+    (objects, None)
+}
+
+fn parse_object<'a>(
+    i: &'a [u8],
+    offset: usize,
+    max_depth: usize,
+) -> Result<(&'a [u8], Object<'a>)> {
+    let (rem, header) = Header::from_ber(i)?;
+    let header_len = i.len() - rem.len();
+    let (rem, data) = ber_get_object_content(rem, &header, max_depth)?;
+    let obj = Object {
+        offset,
+        header,
+        header_len,
+        data,
+    };
+    Ok((rem, obj))
+}
+
+/// Return the length of the (minimal) encoding of a header
+fn header_len(header: &Header) -> usize {
+    let tag_len = match header.raw_tag() {
+        Some(raw_tag) => raw_tag.len(),
+        None if header.tag().0 < 31 => 1,
+        None => 1 + (32 - header.tag().0.leading_zeros() as usize + 6) / 7,
+    };
+    let length_len = match header.length() {
+        Length::Definite(l) if l >= 128 => 1 + (usize::BITS - l.leading_zeros() + 7) as usize / 8,
+        _ => 1,
+    };
+    tag_len + length_len
+}
+
+fn write_type(f: &mut fmt::Formatter, header: &Header) -> fmt::Result {
+    match header.class() {
+        Class::Universal => match universal_type_name(header.tag()) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "[UNIVERSAL {}]", header.tag().0),
+        },
+        Class::ContextSpecific => write!(f, "[{}]", header.tag().0),
+        class => write!(f, "[{} {}]", class, header.tag().0),
+    }
+}
+
+/// Return the name of a universal type, as used in the ASN.1 notation
+fn universal_type_name(tag: Tag) -> Option<&'static str> {
+    let name = match tag {
+        Tag::EndOfContent => "END-OF-CONTENTS",
+        Tag::Boolean => "BOOLEAN",
+        Tag::Integer => "INTEGER",
+        Tag::BitString => "BIT STRING",
+        Tag::OctetString => "OCTET STRING",
+        Tag::Null => "NULL",
+        Tag::Oid => "OBJECT IDENTIFIER",
+        Tag::ObjectDescriptor => "ObjectDescriptor",
+        Tag::External => "EXTERNAL",
+        Tag::RealType => "REAL",
+        Tag::Enumerated => "ENUMERATED",
+        Tag::EmbeddedPdv => "EMBEDDED PDV",
+        Tag::Utf8String => "UTF8String",
+        Tag::RelativeOid => "RELATIVE-OID",
+        Tag::Sequence => "SEQUENCE",
+        Tag::Set => "SET",
+        Tag::NumericString => "NumericString",
+        Tag::PrintableString => "PrintableString",
+        Tag::TeletexString => "TeletexString",
+        Tag::VideotexString => "VideotexString",
+        Tag::Ia5String => "IA5String",
+        Tag::UtcTime => "UTCTime",
+        Tag::GeneralizedTime => "GeneralizedTime",
+        Tag::GraphicString => "GraphicString",
+        Tag::VisibleString => "VisibleString",
+        Tag::GeneralString => "GeneralString",
+        Tag::UniversalString => "UniversalString",
+        Tag::BmpString => "BMPString",
+        _ => return None,
+    };
+    Some(name)
+}
+
+fn truncate(bytes: &[u8], max_bytes: Option<usize>) -> &[u8] {
+    match max_bytes {
+        Some(max) if bytes.len() > max => &bytes[..max],
+        _ => bytes,
+    }
+}
+
+/// Write bytes using the `hstring` notation (`'0A1B'H`)
+fn write_hex(f: &mut fmt::Formatter, bytes: &[u8], max_bytes: Option<usize>) -> fmt::Result {
+    let shown = truncate(bytes, max_bytes);
+    f.write_str("'")?;
+    for b in shown {
+        write!(f, "{:02X}", b)?;
+    }
+    if shown.len() < bytes.len() {
+        f.write_str("...")?;
+    }
+    f.write_str("'H")
+}
+
+/// Write a string using the `cstring` notation (quotes are doubled)
+fn write_cstring(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for (idx, part) in s.split('"').enumerate() {
+        if idx > 0 {
+            f.write_str("\"\"")?;
+        }
+        f.write_str(part)?;
+    }
+    f.write_str("\"")
+}
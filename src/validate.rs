@@ -0,0 +1,255 @@
+//! Schema-less validation of DER encodings
+//!
+//! [`validate_der`] walks an arbitrary encoding using BER parsing rules, and reports all the
+//! violations of the DER rules (X.690 sections 10 and 11) that can be detected without knowing
+//! the ASN.1 definition of the object.
+
+use crate::ber::{
+    ber_get_object_content, is_minimal_length_encoding, is_minimal_tag_encoding, MAX_RECURSION,
+};
+use crate::cer::cmp_set_of_encodings;
+use crate::{
+    check_der_int_constraints, Any, BitString, CheckDerConstraints, Class, DerConstraint, Error,
+    FromBer, GeneralizedTime, Header, Length, Oid, Real, Result, Tag,
+};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+
+/// A violation of the DER rules, found by [`validate_der`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    /// Offset of the object (start of its identifier octets) in the input
+    pub offset: usize,
+    /// The violated rule
+    ///
+    /// Most violations are reported as [`Error::DerConstraintFailed`]. Other errors are
+    /// encodings that are not even valid BER, in which case the walk stops at this object.
+    pub error: Error,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "offset {}: {}", self.offset, self.error)
+    }
+}
+
+/// Validate a DER encoding, and return the list of all violations found
+///
+/// The input is parsed as a list of BER objects, and each object is checked recursively (content
+/// of constructed objects). The following rules are checked:
+///
+/// - tags and lengths must be encoded with the minimum number of octets, using the definite form
+/// - `BOOLEAN`, `INTEGER`, `ENUMERATED`, `BIT STRING`, `NULL`, `OBJECT IDENTIFIER` and `REAL`
+///   values must have their canonical encoding
+/// - string types must be primitive (strings cannot be segmented), and `SEQUENCE` and `SET` must
+///   be constructed
+/// - elements of a `SET` must be sorted: by tag for a `SET`, or by encoding for a `SET OF`. Since
+///   both cannot be distinguished without a schema, only elements violating both orders are
+///   reported
+/// - `UTCTime` and `GeneralizedTime` values must end with `Z` and contain seconds, and fractions of
+///   seconds must not have trailing zeroes
+///
+/// Only universal types are checked for content: objects with other classes cannot be
+/// interpreted without a schema. Primitive objects are not parsed for encapsulated data.
+///
+/// The returned list is empty if the input is valid DER.
+///
+/// ```rust
+/// use asn1_rs::{validate_der, DerConstraint, Error};
+///
+/// // SEQUENCE with a non-minimal length, containing an INTEGER with leading zeroes
+/// let bytes = &[0x30, 0x81, 0x04, 0x02, 0x02, 0x00, 0x01];
+/// let violations = validate_der(bytes);
+/// assert_eq!(violations.len(), 2);
+/// assert_eq!(violations[0].offset, 0);
+/// assert_eq!(
+///     violations[0].error,
+///     Error::DerConstraintFailed(DerConstraint::NonMinimalLength)
+/// );
+/// assert_eq!(violations[1].offset, 3);
+/// assert_eq!(
+///     violations[1].error,
+///     Error::DerConstraintFailed(DerConstraint::IntegerLeadingZeroes)
+/// );
+/// ```
+pub fn validate_der(bytes: &[u8]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    validate_objects(bytes, 0, MAX_RECURSION, &mut violations);
+    violations
+}
+
+/// An object parsed by `validate_objects`
+struct Element<'a> {
+    offset: usize,
+    class: Class,
+    tag: Tag,
+    /// Complete encoding (header and content)
+    encoding: &'a [u8],
+}
+
+/// Validate all objects from `bytes`, and return them
+///
+/// `base` is the offset of `bytes` in the complete input.
+fn validate_objects<'a>(
+    bytes: &'a [u8],
+    base: usize,
+    max_depth: usize,
+    violations: &mut Vec<Violation>,
+) -> Vec<Element<'a>> {
+    let mut elements = Vec::new();
+    let mut i = bytes;
+    while !i.is_empty() {
+        let offset = base + bytes.len() - i.len();
+        match validate_object(i, offset, max_depth, violations) {
+            Ok((rem, header)) => {
+                elements.push(Element {
+                    offset,
+                    class: header.class(),
+                    tag: header.tag(),
+                    encoding: &i[..i.len() - rem.len()],
+                });
+                i = rem;
+            }
+            Err(error) => {
+                // the end of the object cannot be found, so parsing cannot continue
+                violations.push(Violation { offset, error });
+                break;
+            }
+        }
+    }
+    elements
+}
+
+/// Validate one object, and return the remaining bytes and the object header
+fn validate_object<'a>(
+    i: &'a [u8],
+    offset: usize,
+    max_depth: usize,
+    violations: &mut Vec<Violation>,
+) -> Result<(&'a [u8], Header<'a>)> {
+    if max_depth == 0 {
+        return Err(Error::BerMaxDepth);
+    }
+    let (rem, header) = Header::from_ber(i)?;
+    let header_len = i.len() - rem.len();
+    let mut report = |constraint| {
+        violations.push(Violation {
+            offset,
+            error: Error::DerConstraintFailed(constraint),
+        })
+    };
+    let raw_tag = header.raw_tag().unwrap_or(&[]);
+    if !is_minimal_tag_encoding(raw_tag, header.tag().0) {
+        report(DerConstraint::NonMinimalTag);
+    }
+    match header.length() {
+        Length::Indefinite => report(DerConstraint::IndefiniteLength),
+        Length::Definite(l) => {
+            if !is_minimal_length_encoding(&i[raw_tag.len()..header_len], l) {
+                report(DerConstraint::NonMinimalLength);
+            }
+        }
+    }
+    let (rem, data) = ber_get_object_content(rem, &header, max_depth)?;
+    let universal = header.is_universal();
+    if header.is_constructed() {
+        if universal && is_primitive_type(header.tag()) {
+            // X.690 section 10.2
+            report(DerConstraint::Constructed);
+        }
+        let content_offset = offset + header_len;
+        let elements = validate_objects(data, content_offset, max_depth - 1, violations);
+        if universal && header.tag() == Tag::Set {
+            check_set_order(&elements, violations);
+        }
+    } else if universal {
+        if matches!(header.tag(), Tag::Sequence | Tag::Set) {
+            report(DerConstraint::NotConstructed);
+        }
+        let any = Any::new(header.clone(), data);
+        if let Err(error) = check_primitive(&any) {
+            violations.push(Violation { offset, error });
+        }
+    }
+    Ok((rem, header))
+}
+
+/// Return true if values of this universal type must be primitive in DER
+fn is_primitive_type(tag: Tag) -> bool {
+    !matches!(
+        tag,
+        Tag::Sequence | Tag::Set | Tag::External | Tag::EmbeddedPdv
+    ) && tag.0 < 31
+}
+
+/// Check the content of a primitive universal object
+fn check_primitive(any: &Any) -> Result<()> {
+    match any.tag() {
+        Tag::Boolean => {
+            if any.data.len() != 1 {
+                return Err(Error::InvalidLength);
+            }
+            // X.690 section 11.1
+            if !(any.data[0] == 0 || any.data[0] == 0xff) {
+                return Err(Error::DerConstraintFailed(DerConstraint::InvalidBoolean));
+            }
+            Ok(())
+        }
+        Tag::Integer | Tag::Enumerated => check_der_int_constraints(any),
+        Tag::BitString => BitString::check_constraints(any),
+        Tag::Null if !any.data.is_empty() => Err(Error::InvalidLength),
+        Tag::Oid | Tag::RelativeOid => Oid::check_constraints(any),
+        Tag::RealType => Real::check_constraints(any),
+        Tag::UtcTime => check_utctime(any.data),
+        Tag::GeneralizedTime => {
+            GeneralizedTime::check_constraints(any)?;
+            check_generalizedtime(any.data)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check the format of a `UTCTime` (X.690 section 11.8): `YYMMDDhhmmssZ`
+fn check_utctime(data: &[u8]) -> Result<()> {
+    if data.last() != Some(&b'Z') {
+        return Err(Error::DerConstraintFailed(DerConstraint::MissingTimeZone));
+    }
+    if data.len() != 13 {
+        return Err(Error::DerConstraintFailed(DerConstraint::MissingSeconds));
+    }
+    Ok(())
+}
+
+/// Check the seconds and fraction of a `GeneralizedTime` (X.690 section 11.7)
+///
+/// The terminating `Z` has already been checked.
+fn check_generalizedtime(data: &[u8]) -> Result<()> {
+    let data = &data[..data.len() - 1];
+    let mut parts = data.splitn(2, |&b| b == b'.');
+    // YYYYMMDDhhmmss
+    if parts.next().map_or(0, <[u8]>::len) < 14 {
+        return Err(Error::DerConstraintFailed(DerConstraint::MissingSeconds));
+    }
+    match parts.next() {
+        Some(fraction) if fraction.is_empty() || fraction.ends_with(b"0") => Err(
+            Error::DerConstraintFailed(DerConstraint::InvalidTimeFraction),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Check the order of the elements of a `SET` (X.690 sections 10.3 and 11.6)
+fn check_set_order(elements: &[Element], violations: &mut Vec<Violation>) {
+    for pair in elements.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let tag_order = (a.class as u8, a.tag.0) < (b.class as u8, b.tag.0);
+        let encoding_order = cmp_set_of_encodings(a.encoding, b.encoding) != Ordering::Greater;
+        if !tag_order && !encoding_order {
+            violations.push(Violation {
+                offset: b.offset,
+                error: Error::DerConstraintFailed(DerConstraint::SetNotSorted),
+            });
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! Owned tree of ASN.1 values, decoded without schema
+
+use crate::ber::MAX_RECURSION;
+#[cfg(feature = "std")]
+use crate::cer::cmp_set_of_encodings;
+use crate::{
+    Any, BigEnumerated, BitString, BmpString, CheckDerConstraints, Class, DynTagged, Error,
+    FromBer, FromDer, GeneralString, GeneralizedTime, GraphicString, Ia5String, Integer, Null,
+    NumericString, OctetString, Oid, ParseResult, PrintableString, Real, Result, Tag,
+    TeletexString, UniversalString, UtcTime, Utf8String, VideotexString, VisibleString,
+};
+#[cfg(feature = "std")]
+use crate::{Header, Length, SerializeResult, ToDer};
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// An owned ASN.1 value, decoded without schema
+///
+/// Objects of universal class are decoded according to their tag: primitive types to their
+/// value, and `SEQUENCE` and `SET` to the list of their elements (recursively). Objects of
+/// other classes (tagged values) and of unsupported universal types are kept raw, since their
+/// content cannot be interpreted without a schema.
+///
+/// Values can be modified in place, and encoded again using [`ToDer`]. Encoding does not check
+/// the values (for ex. the characters of strings), so invalid objects can be built on purpose.
+/// However, values are normalized as required by DER: `REAL` values are encoded in their
+/// canonical form, and times are converted to UTC (encoding returns an error for a
+/// `GeneralizedTime` in local time, or a `UTCTime` which would be out of range).
+///
+/// ```rust
+/// use asn1_rs::{Asn1Value, FromDer, Integer, ToDer};
+///
+/// // SEQUENCE { INTEGER 1, BOOLEAN TRUE }
+/// let bytes = &[0x30, 0x06, 0x02, 0x01, 0x01, 0x01, 0x01, 0xff];
+/// let (_, mut value) = Asn1Value::from_der(bytes).expect("parsing failed");
+/// if let Some(elements) = value.elements_mut() {
+///     elements[0] = Asn1Value::Integer(Integer::from(2));
+///     elements.pop();
+/// }
+/// assert_eq!(value.to_der_vec().unwrap(), &[0x30, 0x03, 0x02, 0x01, 0x02]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Asn1Value {
+    Boolean(bool),
+    Integer(Integer<'static>),
+    BitString(BitString<'static>),
+    OctetString(Vec<u8>),
+    Null,
+    /// Object identifier (absolute or relative)
+    Oid(Oid<'static>),
+    Real(Real),
+    Enumerated(BigEnumerated<'static>),
+    /// Character string, with the tag of its type (for ex. [`Tag::Utf8String`])
+    String(Tag, String),
+    UtcTime(UtcTime),
+    GeneralizedTime(GeneralizedTime),
+    Sequence(Vec<Asn1Value>),
+    Set(Vec<Asn1Value>),
+    /// Object that could not be interpreted, with its raw content
+    Raw {
+        class: Class,
+        constructed: bool,
+        tag: Tag,
+        data: Vec<u8>,
+    },
+}
+
+impl Asn1Value {
+    /// Decode a value and its elements, using BER or DER rules
+    fn decode(any: &Any, der: bool, max_depth: usize) -> Result<Self> {
+        if max_depth == 0 {
+            return Err(Error::BerMaxDepth);
+        }
+        macro_rules! parse {
+            ($ty:ty) => {{
+                // convert first: DER constraints may assume that the content is valid
+                let value = <$ty>::try_from(any.clone())?;
+                if der {
+                    <$ty>::check_constraints(any)?;
+                }
+                value
+            }};
+        }
+        macro_rules! parse_string {
+            ($($ty:ident),*) => {
+                match any.tag() {
+                    $( Tag::$ty => Some(parse!($ty).data.into_owned()), )*
+                    _ => None,
+                }
+            };
+        }
+        if !any.header.is_universal() {
+            return Ok(Asn1Value::raw(any));
+        }
+        let string = parse_string!(
+            Utf8String,
+            PrintableString,
+            Ia5String,
+            NumericString,
+            VisibleString,
+            GraphicString,
+            GeneralString,
+            TeletexString,
+            VideotexString,
+            BmpString,
+            UniversalString
+        );
+        if let Some(s) = string {
+            return Ok(Asn1Value::String(any.tag(), s));
+        }
+        let value = match any.tag() {
+            Tag::Boolean => Asn1Value::Boolean(parse!(bool)),
+            Tag::Integer => Asn1Value::Integer(Integer {
+                data: Cow::Owned(parse!(Integer).data.into_owned()),
+            }),
+            Tag::BitString => {
+                let b = parse!(BitString);
+                // X.690 section 8.6.2: at most 7 unused bits, and none if there is no data
+                if b.unused_bits > 7 || (b.unused_bits > 0 && b.data.is_empty()) {
+                    return Err(Tag::BitString.invalid_value("invalid number of unused bits"));
+                }
+                Asn1Value::BitString(BitString {
+                    unused_bits: b.unused_bits,
+                    data: Cow::Owned(b.data.into_owned()),
+                })
+            }
+            Tag::OctetString => Asn1Value::OctetString(parse!(OctetString).into_cow().into_owned()),
+            Tag::Null => {
+                parse!(Null);
+                Asn1Value::Null
+            }
+            Tag::Oid => Asn1Value::Oid(parse!(Oid).to_owned()),
+            Tag::RelativeOid => {
+                let oid = any.clone().relative_oid()?.to_owned();
+                if der {
+                    Oid::check_constraints(any)?;
+                }
+                Asn1Value::Oid(oid)
+            }
+            Tag::RealType => Asn1Value::Real(parse!(Real)),
+            Tag::Enumerated => Asn1Value::Enumerated(BigEnumerated {
+                data: Cow::Owned(parse!(BigEnumerated).data.into_owned()),
+            }),
+            Tag::UtcTime => Asn1Value::UtcTime(parse!(UtcTime)),
+            Tag::GeneralizedTime => Asn1Value::GeneralizedTime(parse!(GeneralizedTime)),
+            Tag::Sequence | Tag::Set => {
+                any.header.assert_constructed()?;
+                let mut elements = Vec::new();
+                let mut i = any.data;
+                while !i.is_empty() {
+                    let (rem, element) = if der {
+                        Any::from_der(i)?
+                    } else {
+                        Any::from_ber(i)?
+                    };
+                    elements.push(Asn1Value::decode(&element, der, max_depth - 1)?);
+                    i = rem;
+                }
+                if any.tag() == Tag::Sequence {
+                    Asn1Value::Sequence(elements)
+                } else {
+                    Asn1Value::Set(elements)
+                }
+            }
+            _ => Asn1Value::raw(any),
+        };
+        Ok(value)
+    }
+
+    fn raw(any: &Any) -> Self {
+        Asn1Value::Raw {
+            class: any.class(),
+            constructed: any.header.is_constructed(),
+            tag: any.tag(),
+            data: any.data.to_vec(),
+        }
+    }
+
+    /// Return the class of the value
+    pub fn class(&self) -> Class {
+        match self {
+            Asn1Value::Raw { class, .. } => *class,
+            _ => Class::Universal,
+        }
+    }
+
+    /// Return true if the value is encoded in the constructed form
+    pub fn is_constructed(&self) -> bool {
+        match self {
+            Asn1Value::Sequence(_) | Asn1Value::Set(_) => true,
+            Asn1Value::Raw { constructed, .. } => *constructed,
+            _ => false,
+        }
+    }
+
+    /// Return the elements of a `SEQUENCE` or `SET`
+    pub fn elements(&self) -> Option<&[Asn1Value]> {
+        match self {
+            Asn1Value::Sequence(v) | Asn1Value::Set(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Return the elements of a `SEQUENCE` or `SET`, for modification
+    pub fn elements_mut(&mut self) -> Option<&mut Vec<Asn1Value>> {
+        match self {
+            Asn1Value::Sequence(v) | Asn1Value::Set(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Return the length of the encoding of all elements of a `SEQUENCE` or `SET`
+    #[cfg(feature = "std")]
+    fn elements_len(&self) -> Result<usize> {
+        self.elements()
+            .unwrap_or_default()
+            .iter()
+            .map(ToDer::to_der_len)
+            .sum()
+    }
+
+    /// Return the object used to encode this value, or `None` for `SEQUENCE` and `SET`
+    #[cfg(feature = "std")]
+    fn encoder(&self) -> Result<Option<Box<dyn ToDer + '_>>> {
+        macro_rules! string {
+            ($tag:expr, $s:expr, $($ty:ident),*) => {
+                match $tag {
+                    $( Tag::$ty => Box::new($ty::new($s)) as Box<dyn ToDer>, )*
+                    // not a string type: encode the characters as is
+                    tag => {
+                        let header =
+                            Header::new(Class::Universal, false, tag, Length::Definite($s.len()));
+                        Box::new(Any::new(header, $s.as_bytes()))
+                    }
+                }
+            };
+        }
+        let encoder: Box<dyn ToDer> = match self {
+            Asn1Value::Boolean(b) => Box::new(*b),
+            Asn1Value::Integer(i) => Box::new(i),
+            Asn1Value::BitString(b) => Box::new(b),
+            Asn1Value::OctetString(v) => Box::new(OctetString::new(v)),
+            Asn1Value::Null => Box::new(Null::new()),
+            Asn1Value::Oid(oid) => Box::new(oid),
+            Asn1Value::Real(r) => Box::new(r.canonical()?),
+            Asn1Value::Enumerated(e) => Box::new(e),
+            Asn1Value::String(tag, s) => string!(
+                *tag,
+                s.as_str(),
+                Utf8String,
+                PrintableString,
+                Ia5String,
+                NumericString,
+                VisibleString,
+                GraphicString,
+                GeneralString,
+                TeletexString,
+                VideotexString,
+                BmpString,
+                UniversalString
+            ),
+            Asn1Value::UtcTime(t) => Box::new(utc_time_to_utc(t)?),
+            Asn1Value::GeneralizedTime(t) => Box::new(GeneralizedTime(t.0.to_utc()?)),
+            Asn1Value::Raw {
+                class,
+                constructed,
+                tag,
+                data,
+            } => {
+                let header = Header::new(*class, *constructed, *tag, Length::Definite(data.len()));
+                Box::new(Any::new(header, data))
+            }
+            Asn1Value::Sequence(_) | Asn1Value::Set(_) => return Ok(None),
+        };
+        Ok(Some(encoder))
+    }
+}
+
+/// Convert a `UTCTime` to UTC, keeping the year in the range 1950 to 2049 (X.509)
+#[cfg(feature = "std")]
+fn utc_time_to_utc(t: &UtcTime) -> Result<UtcTime> {
+    let mut dt = t.0.clone();
+    dt.year = if dt.year >= 50 {
+        1900 + dt.year
+    } else {
+        2000 + dt.year
+    };
+    let mut dt = dt.to_utc()?;
+    if !(1950..=2049).contains(&dt.year) {
+        return Err(Tag::UtcTime.invalid_value("year out of range (1950 to 2049)"));
+    }
+    dt.year %= 100;
+    Ok(UtcTime(dt))
+}
+
+impl<'a> TryFrom<Any<'a>> for Asn1Value {
+    type Error = Error;
+
+    fn try_from(any: Any<'a>) -> Result<Asn1Value> {
+        TryFrom::try_from(&any)
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b Any<'a>> for Asn1Value {
+    type Error = Error;
+
+    fn try_from(any: &'b Any<'a>) -> Result<Asn1Value> {
+        Asn1Value::decode(any, false, MAX_RECURSION)
+    }
+}
+
+impl<'a> FromDer<'a> for Asn1Value {
+    fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self> {
+        let (rem, any) = Any::from_der(bytes)?;
+        let value = Asn1Value::decode(&any, true, MAX_RECURSION)?;
+        Ok((rem, value))
+    }
+}
+
+impl DynTagged for Asn1Value {
+    fn tag(&self) -> Tag {
+        match self {
+            Asn1Value::Boolean(_) => Tag::Boolean,
+            Asn1Value::Integer(_) => Tag::Integer,
+            Asn1Value::BitString(_) => Tag::BitString,
+            Asn1Value::OctetString(_) => Tag::OctetString,
+            Asn1Value::Null => Tag::Null,
+            Asn1Value::Oid(oid) => oid.tag(),
+            Asn1Value::Real(_) => Tag::RealType,
+            Asn1Value::Enumerated(_) => Tag::Enumerated,
+            Asn1Value::String(tag, _) => *tag,
+            Asn1Value::UtcTime(_) => Tag::UtcTime,
+            Asn1Value::GeneralizedTime(_) => Tag::GeneralizedTime,
+            Asn1Value::Sequence(_) => Tag::Sequence,
+            Asn1Value::Set(_) => Tag::Set,
+            Asn1Value::Raw { tag, .. } => *tag,
+        }
+    }
+}
+
+/// `SET` elements are encoded in the order required by DER (sorted by encoding)
+#[cfg(feature = "std")]
+impl ToDer for Asn1Value {
+    fn to_der_len(&self) -> Result<usize> {
+        if let Some(encoder) = self.encoder()? {
+            return encoder.to_der_len();
+        }
+        let len = self.elements_len()?;
+        let header = Header::new(Class::Universal, true, self.tag(), Length::Definite(len));
+        Ok(header.to_der_len()? + len)
+    }
+
+    fn write_der_header(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        if let Some(encoder) = self.encoder()? {
+            return encoder.write_der_header(writer);
+        }
+        let len = self.elements_len()?;
+        let header = Header::new(Class::Universal, true, self.tag(), Length::Definite(len));
+        header.write_der_header(writer)
+    }
+
+    fn write_der_content(&self, writer: &mut dyn std::io::Write) -> SerializeResult<usize> {
+        match self {
+            Asn1Value::Sequence(elements) => {
+                let mut sz = 0;
+                for element in elements {
+                    sz += element.write_der(writer)?;
+                }
+                Ok(sz)
+            }
+            Asn1Value::Set(elements) => {
+                let mut encodings = elements
+                    .iter()
+                    .map(ToDer::to_der_vec)
+                    .collect::<SerializeResult<Vec<_>>>()?;
+                encodings.sort_by(|a, b| cmp_set_of_encodings(a, b));
+                let mut sz = 0;
+                for encoding in &encodings {
+                    sz += writer.write(encoding)?;
+                }
+                Ok(sz)
+            }
+            _ => match self.encoder()? {
+                Some(encoder) => encoder.write_der_content(writer),
+                None => Ok(0),
+            },
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use asn1_rs::*;
 use hex_literal::hex;
 use nom::Needed;
+use std::convert::TryFrom;
 #[cfg(feature = "datetime")]
 use time::macros::datetime;
 
@@ -41,6 +42,19 @@ fn from_ber_bitstring() {
     assert_eq!(&result.data[..], &input[4..]);
 }
 
+#[test]
+fn from_ber_bitstring_constructed() {
+    // X.690 section 8.6.4.2 example
+    let input = &hex!("23 80 03 03 00 0a 3b 03 05 04 5f 29 1c d0 00 00");
+    let (rem, result) = BitString::from_ber(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(result.unused_bits, 4);
+    assert_eq!(&result.data[..], &hex!("0a 3b 5f 29 1c d0"));
+    // unused bits are only allowed in the last segment
+    let input = &hex!("23 0c 03 03 01 0a 3b 03 05 04 5f 29 1c d0");
+    assert!(BitString::from_ber(input).is_err());
+}
+
 #[test]
 fn from_ber_embedded_pdv() {
     let input = &hex!("2b 0d a0 07 81 05 2a 03 04 05 06 82 02 aa a0");
@@ -53,6 +67,37 @@ fn from_ber_embedded_pdv() {
     assert_eq!(result.data_value, &[0xaa, 0xa0]);
 }
 
+#[test]
+fn from_ber_embedded_pdv_descriptor() {
+    // identification: context-negotiation, data-value-descriptor present
+    let input = &hex!("2b 16 a0 0b a3 09 80 01 03 81 04 2a 03 04 05 81 03 61 62 63 82 02 aa a0");
+    let (rem, result) = EmbeddedPdv::from_ber(input).expect("parsing failed");
+    assert_eq!(rem, &[]);
+    assert_eq!(
+        result.identification,
+        PdvIdentification::ContextNegotiation {
+            presentation_context_id: Integer::from(3),
+            presentation_syntax: Oid::from(&[1, 2, 3, 4, 5]).unwrap(),
+        }
+    );
+    assert_eq!(
+        result.data_value_descriptor.as_ref().map(|d| d.as_ref()),
+        Some("abc")
+    );
+    assert_eq!(result.data_value, &[0xaa, 0xa0]);
+    // invalid identification tag
+    let input = &hex!("2b 08 a0 02 86 00 82 02 aa a0");
+    let _ = EmbeddedPdv::from_ber(input).expect_err("parsing should fail");
+    // syntax: invalid OID (non-minimal sub-identifier), constructed form
+    let input = &hex!("2b 0a a0 04 81 02 80 01 82 02 aa a0");
+    let _ = EmbeddedPdv::from_ber(input).expect_err("parsing should fail");
+    let input = &hex!("2b 0b a0 05 a1 03 06 01 2a 82 02 aa a0");
+    let _ = EmbeddedPdv::from_ber(input).expect_err("parsing should fail");
+    // transfer-syntax: empty OID
+    let input = &hex!("2b 08 a0 02 84 00 82 02 aa a0");
+    let _ = EmbeddedPdv::from_ber(input).expect_err("parsing should fail");
+}
+
 #[test]
 fn from_ber_endofcontent() {
     let input = &hex!("00 00");
@@ -60,6 +105,34 @@ fn from_ber_endofcontent() {
     assert_eq!(rem, &[]);
 }
 
+#[test]
+fn from_ber_generalstring() {
+    // ASCII
+    let input = &hex!("1b 03 61 62 63");
+    let (rem, result) = GeneralString::from_ber(input).expect("parsing failed");
+    assert_eq!(rem, &[]);
+    assert_eq!(result.as_ref(), "abc");
+    // ISO 2022: designate ISO 8859-1 as G1
+    let input = &hex!("1b 07 1b 2d 41 63 61 66 e9");
+    let (_, result) = GeneralString::from_ber(input).expect("parsing failed");
+    assert_eq!(result.as_ref(), "café");
+    assert_eq!(result.raw_bytes(), Some(&input[2..]));
+    // ISO 2022: UTF-8
+    let input = &hex!("1b 0b 61 1b 25 47 e2 82 ac 1b 25 40 62");
+    let (_, result) = GeneralString::from_ber(input).expect("parsing failed");
+    assert_eq!(result.as_ref(), "a€b");
+    // ISO 2022: the designated G1 set is restored after UTF-8
+    let input = &hex!("1b 0d 1b 2d 41 1b 25 47 e2 82 ac 1b 25 40 e9");
+    let (_, result) = GeneralString::from_ber(input).expect("parsing failed");
+    assert_eq!(result.as_ref(), "€é");
+    // G1 not designated
+    let input = &hex!("1b 04 63 61 66 e9");
+    let _ = GeneralString::from_ber(input).expect_err("parsing should fail");
+    // unsupported escape sequence
+    let input = &hex!("1b 03 1b 24 42");
+    let _ = GeneralString::from_ber(input).expect_err("parsing should fail");
+}
+
 #[test]
 fn from_ber_generalizedtime() {
     let input = &hex!("18 0F 32 30 30 32 31 32 31 33 31 34 32 39 32 33 5A FF");
@@ -130,6 +203,35 @@ fn from_ber_int() {
     assert_eq!(rem, &[0xff, 0xff]);
 }
 
+#[test]
+fn from_ber_oid_invalid() {
+    // empty
+    let input = &hex!("06 00");
+    assert_eq!(Oid::from_ber(input), Err(Err::Error(Error::OidEmpty)));
+    // leading 0x80 in sub-identifier
+    let input = &hex!("06 04 2a 80 86 48");
+    assert_eq!(
+        Oid::from_ber(input),
+        Err(Err::Error(Error::OidArcNotMinimal))
+    );
+    let input = &hex!("0d 02 80 01");
+    assert_eq!(
+        Oid::from_ber_relative(input),
+        Err(Err::Error(Error::OidArcNotMinimal))
+    );
+    // last sub-identifier not terminated
+    let input = &hex!("06 03 2a 86 c8");
+    assert_eq!(
+        Oid::from_ber(input),
+        Err(Err::Error(Error::OidArcTruncated))
+    );
+    // lenient parsing
+    let input = &hex!("06 04 2a 80 86 48");
+    let (rem, oid) = Oid::from_ber_lenient(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(oid.as_bytes(), &input[2..]);
+}
+
 #[test]
 fn from_ber_relative_oid() {
     let input = &hex!("0d 04 c2 7b 03 02");
@@ -175,37 +277,65 @@ fn from_ber_octetstring() {
     assert_eq!(rem, &[]);
 }
 
+#[test]
+fn from_ber_octetstring_constructed() {
+    // nested constructed segments, using both length forms
+    let input = &hex!("24 80 04 02 41 41 24 80 04 01 42 00 00 24 03 04 01 43 00 00");
+    let (rem, result) = OctetString::from_ber(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(result.as_ref(), b"AABC");
+    // segments must be OCTET STRING
+    let input = &hex!("24 80 0c 02 41 41 00 00");
+    assert!(OctetString::from_ber(input).is_err());
+    // restricted character strings also use OCTET STRING segments
+    let input = &hex!("2c 80 04 02 41 41 04 01 42 00 00");
+    let (_, result) = Utf8String::from_ber(input).expect("parsing failed");
+    assert_eq!(result.as_ref(), "AAB");
+}
+
 #[test]
 fn from_ber_real_binary() {
     const EPSILON: f32 = 0.00001;
     // binary, base = 2
     let input = &hex!("09 03 80 ff 01 ff ff");
     let (rem, result) = Real::from_ber(input).expect("parsing failed");
-    assert_eq!(result, Real::binary(1.0, 2, -1));
+    assert_eq!(result, Real::binary(1.0, 2, -1).unwrap());
     assert!((result.f32() - 0.5).abs() < EPSILON);
     assert_eq!(rem, &[0xff, 0xff]);
     // binary, base = 2 and scale factor
     let input = &hex!("09 03 94 ff 0d ff ff");
     let (rem, result) = Real::from_ber(input).expect("parsing failed");
-    assert_eq!(result, Real::binary(26.0, 2, -3).with_enc_base(8));
+    assert_eq!(
+        result,
+        Real::binary(26.0, 2, -3).unwrap().with_enc_base(8).unwrap()
+    );
     assert!((result.f32() - 3.25).abs() < EPSILON);
     assert_eq!(rem, &[0xff, 0xff]);
     // binary, base = 16
     let input = &hex!("09 03 a0 fe 01 ff ff");
     let (rem, result) = Real::from_ber(input).expect("parsing failed");
-    assert_eq!(result, Real::binary(1.0, 2, -8).with_enc_base(16));
+    assert_eq!(
+        result,
+        Real::binary(1.0, 2, -8).unwrap().with_enc_base(16).unwrap()
+    );
     assert!((result.f32() - 0.00390625).abs() < EPSILON);
     assert_eq!(rem, &[0xff, 0xff]);
     // binary, exponent = 0
     let input = &hex!("09 03 80 00 01 ff ff");
     let (rem, result) = Real::from_ber(input).expect("parsing failed");
-    assert_eq!(result, Real::binary(1.0, 2, 0));
+    assert_eq!(result, Real::binary(1.0, 2, 0).unwrap());
     assert!((result.f32() - 1.0).abs() < EPSILON);
     assert_eq!(rem, &[0xff, 0xff]);
     // 2 octets for exponent and negative exponent
     let input = &hex!("09 04 a1 ff 01 03 ff ff");
     let (rem, result) = Real::from_ber(input).expect("parsing failed");
-    assert_eq!(result, Real::binary(3.0, 2, -1020).with_enc_base(16));
+    assert_eq!(
+        result,
+        Real::binary(3.0, 2, -1020)
+            .unwrap()
+            .with_enc_base(16)
+            .unwrap()
+    );
     let epsilon = 1e-311_f64;
     assert!((result.f64() - 2.67e-307).abs() < epsilon);
     assert_eq!(rem, &[0xff, 0xff]);
@@ -240,6 +370,47 @@ fn from_ber_real_string() {
     assert_eq!(rem, &[0xff, 0xff]);
 }
 
+#[test]
+fn from_ber_real_decimal_forms() {
+    // NR1, with leading spaces
+    let (_, result) = Real::from_ber(&hex!("09 05 01 20 20 2d 37")).expect("parsing failed");
+    assert_eq!(result.f64(), -7.0);
+    assert_eq!(result.to_string(), "-7");
+    // NR2, with comma
+    let (_, result) = Real::from_ber(&hex!("09 04 02 2c 32 35")).expect("parsing failed");
+    assert_eq!(result.f64(), 0.25);
+    assert_eq!(result.to_string(), "0.25");
+    // NR3, with signed exponent
+    let (_, result) =
+        Real::from_ber(&hex!("09 08 03 2b 31 2e 35 65 2b 33")).expect("parsing failed");
+    assert_eq!(result.f64(), 1500.0);
+    assert_eq!(result.to_string(), "1.5E3");
+    assert_eq!(result, Real::from(1500.0));
+    // NR1 must not have a decimal mark
+    Real::from_ber(&hex!("09 03 01 31 2e")).expect_err("invalid NR1");
+    // NR2 must have a decimal mark
+    Real::from_ber(&hex!("09 02 02 31")).expect_err("invalid NR2");
+    // invalid form
+    Real::from_ber(&hex!("09 02 04 31")).expect_err("invalid form");
+    // exponent overflow
+    for value in ["1.55E-9223372036854775807", "10E9223372036854775807"] {
+        let mut input = vec![0x09, value.len() as u8 + 1, 0x03];
+        input.extend_from_slice(value.as_bytes());
+        Real::from_ber(&input).expect_err("exponent overflow");
+    }
+}
+
+#[test]
+fn from_ber_real_special_x690_2008() {
+    let (_, result) = Real::from_ber(&hex!("09 01 42")).expect("parsing failed");
+    assert_eq!(result, Real::NotANumber);
+    assert!(result.f64().is_nan());
+    assert_eq!(result.to_string(), "NOT-A-NUMBER");
+    let (_, result) = Real::from_ber(&hex!("09 01 43")).expect("parsing failed");
+    assert_eq!(result, Real::MinusZero);
+    assert!(result.f64().is_sign_negative());
+}
+
 #[test]
 #[allow(clippy::approx_constant)]
 fn from_ber_real_string_primitive() {
@@ -305,7 +476,15 @@ fn from_ber_tag_incomplete() {
 fn from_ber_tag_overflow() {
     let input = &hex!("9f a2 a2 a2 a2 a2 a2 22 01 00");
     let res = Any::from_ber(input).expect_err("parsing should have failed");
-    assert_eq!(res, nom::Err::Error(Error::InvalidTag));
+    assert_eq!(res, nom::Err::Error(Error::TagTooLarge));
+    // maximum tag number
+    let input = &hex!("9f 8f ff ff ff 7f 01 00");
+    let (_, any) = Any::from_ber(input).expect("parsing failed");
+    assert_eq!(any.tag(), Tag::MAX);
+    // maximum + 1
+    let input = &hex!("9f 90 80 80 80 00 01 00");
+    let res = Any::from_ber(input).expect_err("parsing should have failed");
+    assert_eq!(res, nom::Err::Error(Error::TagTooLarge));
 }
 
 #[test]
@@ -329,6 +508,34 @@ fn from_ber_iter_sequence_incomplete() {
     assert_eq!(iter.next(), None);
 }
 
+#[test]
+fn from_ber_teletexstring() {
+    // ASCII is borrowed
+    let input = &hex!("14 03 61 62 63");
+    let (rem, result) = TeletexString::from_ber(input).expect("parsing failed");
+    assert_eq!(rem, &[]);
+    assert_eq!(result.as_ref(), "abc");
+    // diacritical marks, and supplementary set characters
+    let input = &hex!("14 0b 4d c8 75 6e 63 68 65 6e 20 a4 e8");
+    let (_, result) = TeletexString::from_ber(input).expect("parsing failed");
+    assert_eq!(result.as_ref(), "München $Ł");
+    assert_eq!(result.raw_bytes(), Some(&input[2..]));
+    // diacritical mark without precomposed character
+    let input = &hex!("14 02 c2 71");
+    let (_, result) = TeletexString::from_ber(input).expect("parsing failed");
+    assert_eq!(result.as_ref(), "q\u{301}");
+    // diacritical mark at end of string
+    let input = &hex!("14 02 61 c2");
+    let _ = TeletexString::from_ber(input).expect_err("parsing should fail");
+    // unassigned character
+    let input = &hex!("14 01 c0");
+    let _ = TeletexString::from_ber(input).expect_err("parsing should fail");
+    // control characters
+    let input = &hex!("14 06 61 0d 0a e8 0c 62");
+    let (_, result) = TeletexString::from_ber(input).expect("parsing failed");
+    assert_eq!(result.as_ref(), "a\r\nŁ\x0cb");
+}
+
 #[test]
 fn from_ber_universalstring() {
     let input = &hex!("1C 10 00000061 00000062 00000063 00000064");
@@ -336,3 +543,76 @@ fn from_ber_universalstring() {
     assert_eq!(result.as_ref(), "abcd");
     assert_eq!(rem, &[]);
 }
+
+#[test]
+fn from_ber_videotexstring() {
+    let input = &hex!("15 05 63 61 66 c2 65");
+    let (rem, result) = VideotexString::from_ber(input).expect("parsing failed");
+    assert_eq!(rem, &[]);
+    assert_eq!(result.as_ref(), "café");
+}
+
+#[test]
+fn from_ber_asn1_value() {
+    // indefinite lengths are accepted, and encoded again with definite lengths
+    let input = &hex!("30 80 0c 01 61 31 80 02 01 01 00 00 00 00");
+    let (rem, any) = Any::from_ber(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    let value = Asn1Value::try_from(&any).expect("decoding failed");
+    assert_eq!(
+        value,
+        Asn1Value::Sequence(vec![
+            Asn1Value::String(Tag::Utf8String, "a".to_string()),
+            Asn1Value::Set(vec![Asn1Value::Integer(Integer::from(1))]),
+        ])
+    );
+    let v = value.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("30 08 0c 01 61 31 03 02 01 01"));
+    // non-minimal encodings are accepted in BER
+    let (_, any) = Any::from_ber(&hex!("02 02 00 01")).expect("parsing failed");
+    let value = Asn1Value::try_from(any).expect("decoding failed");
+    match value {
+        Asn1Value::Integer(i) => assert_eq!(i.as_u32(), Ok(1)),
+        _ => panic!("expected an integer"),
+    }
+    // values are normalized when encoding to DER
+    for (input, expected) in [
+        // REAL: canonical form
+        (&hex!("09 03 80 01 04")[..], &hex!("09 03 80 03 01")[..]),
+        // times: converted to UTC
+        (
+            &hex!("18 13 32 30 32 32 30 31 30 32 30 33 30 34 30 35 2b 30 31 30 30"),
+            b"\x18\x0f20220102020405Z",
+        ),
+        (
+            &hex!("18 13 32 30 32 32 30 31 30 31 30 30 33 30 30 30 2b 30 31 30 30"),
+            b"\x18\x0f20211231233000Z",
+        ),
+        (
+            &hex!("17 11 39 39 31 32 33 31 32 33 33 30 30 30 2d 30 31 30 30"),
+            b"\x17\x0d000101003000Z",
+        ),
+    ] {
+        let (_, any) = Any::from_ber(input).expect("parsing failed");
+        let value = Asn1Value::try_from(any).expect("decoding failed");
+        let v = value.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, expected);
+        assert_eq!(value.to_der_len(), Ok(v.len()));
+        assert!(Asn1Value::from_der(&v).is_ok());
+    }
+    // local time cannot be converted to UTC
+    let (_, any) = Any::from_ber(b"\x18\x0e20220102030405").expect("parsing failed");
+    let value = Asn1Value::try_from(any).expect("decoding failed");
+    assert!(value.to_der_vec().is_err());
+    // UTCTime out of range after conversion
+    let (_, any) = Any::from_ber(b"\x17\x11491231233000-0100").expect("parsing failed");
+    let value = Asn1Value::try_from(any).expect("decoding failed");
+    assert!(value.to_der_vec().is_err());
+    // empty ENUMERATED
+    let _ = BigEnumerated::from_ber(&hex!("0a 00")).expect_err("parsing should fail");
+    let _ = Asn1Value::from_ber(&hex!("0a 00")).expect_err("parsing should fail");
+    // invalid number of unused bits in BIT STRING
+    let _ = Asn1Value::from_ber(&hex!("03 01 08")).expect_err("parsing should fail");
+    let _ = Asn1Value::from_ber(&hex!("03 02 09 ff")).expect_err("parsing should fail");
+    let _ = Asn1Value::from_ber(&hex!("03 01 01")).expect_err("parsing should fail");
+}
@@ -0,0 +1,220 @@
+use asn1_rs::*;
+use hex_literal::hex;
+use std::iter::FromIterator;
+
+/// Build a constructed string (indefinite length), with segments of the given lengths
+fn segmented(tag: u8, segment_tag: u8, lengths: &[usize]) -> Vec<u8> {
+    let mut v = vec![tag | 0x20, 0x80];
+    for (idx, &len) in lengths.iter().enumerate() {
+        v.push(segment_tag);
+        if len < 128 {
+            v.push(len as u8);
+        } else {
+            v.extend_from_slice(&[0x82, (len >> 8) as u8, len as u8]);
+        }
+        v.extend(std::iter::repeat(b'a' + idx as u8).take(len));
+    }
+    v.extend_from_slice(&[0, 0]);
+    v
+}
+
+#[test]
+fn from_cer_header() {
+    // constructed objects must use the indefinite form
+    let input = &hex!("30 03 02 01 01");
+    assert_eq!(
+        Any::from_cer(input),
+        Err(Err::Error(Error::CerConstraintFailed(
+            CerConstraint::DefiniteLength
+        )))
+    );
+    let input = &hex!("30 80 02 01 01 00 00");
+    let (rem, any) = Any::from_cer(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(any.data, &hex!("02 01 01"));
+    // primitive objects use the definite form
+    let input = &hex!("02 01 01");
+    let (_, any) = Any::from_cer(input).expect("parsing failed");
+    assert_eq!(any.data, &[1]);
+}
+
+#[test]
+fn from_cer_primitive() {
+    let (_, v) = u32::from_cer(&hex!("02 01 05")).expect("parsing failed");
+    assert_eq!(v, 5);
+    // same constraints as DER
+    assert_eq!(
+        u32::from_cer(&hex!("02 02 00 05")),
+        Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::IntegerLeadingZeroes
+        )))
+    );
+    let (_, b) = Boolean::from_cer(&hex!("01 01 ff")).expect("parsing failed");
+    assert!(b.bool());
+    assert!(Boolean::from_cer(&hex!("01 01 01")).is_err());
+}
+
+#[test]
+fn from_cer_octetstring() {
+    let (_, s) = OctetString::from_cer(&hex!("04 03 01 02 03")).expect("parsing failed");
+    assert_eq!(s.as_ref(), &[1, 2, 3]);
+    // 1001 octets: one full segment, and one segment of 1 octet
+    let input = segmented(0x04, 0x04, &[1000, 1]);
+    let (rem, s) = OctetString::from_cer(&input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(s.as_ref().len(), 1001);
+    assert_eq!(s.as_ref()[1000], b'b');
+    // long primitive string
+    let mut input = vec![0x04, 0x82, 0x03, 0xe9];
+    input.extend_from_slice(&[0; 1001]);
+    assert_eq!(
+        OctetString::from_cer(&input),
+        Err(Err::Error(Error::CerConstraintFailed(
+            CerConstraint::StringTooLong
+        )))
+    );
+    // first segment is not 1000 octets
+    let input = segmented(0x04, 0x04, &[999, 2]);
+    assert_eq!(
+        OctetString::from_cer(&input),
+        Err(Err::Error(Error::CerConstraintFailed(
+            CerConstraint::InvalidStringSegment
+        )))
+    );
+    // short string must be primitive
+    let input = segmented(0x04, 0x04, &[10]);
+    assert_eq!(
+        OctetString::from_cer(&input),
+        Err(Err::Error(Error::CerConstraintFailed(
+            CerConstraint::InvalidStringSegment
+        )))
+    );
+}
+
+#[test]
+fn from_cer_string() {
+    let input = segmented(0x0c, 0x04, &[1000, 1000, 5]);
+    let (_, s) = Utf8String::from_cer(&input).expect("parsing failed");
+    assert_eq!(s.as_ref().len(), 2005);
+    assert!(s.as_ref().ends_with("ccccc"));
+    // segments must be OCTET STRING
+    let input = segmented(0x0c, 0x0c, &[1000, 5]);
+    assert!(Utf8String::from_cer(&input).is_err());
+}
+
+#[test]
+fn from_cer_bitstring() {
+    let mut input = vec![0x23, 0x80, 0x03, 0x82, 0x03, 0xe8, 0x00];
+    input.extend_from_slice(&[0xff; 999]);
+    input.extend_from_slice(&hex!("03 02 04 f0 00 00"));
+    let (_, b) = BitString::from_cer(&input).expect("parsing failed");
+    assert_eq!(b.unused_bits, 4);
+    assert_eq!(b.data.len(), 1000);
+    // unused bits are not allowed in the first segment
+    input[6] = 1;
+    assert_eq!(
+        BitString::from_cer(&input),
+        Err(Err::Error(Error::CerConstraintFailed(
+            CerConstraint::InvalidStringSegment
+        )))
+    );
+}
+
+#[test]
+fn from_cer_sequence_of() {
+    let input = &hex!("30 80 02 01 01 02 01 02 00 00");
+    let (rem, v) = <Vec<u32>>::from_cer(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(v, vec![1, 2]);
+    let (_, seq) = Sequence::from_cer(input).expect("parsing failed");
+    let v = seq
+        .cer_iter::<u32, Error>()
+        .collect::<Result<Vec<_>>>()
+        .expect("parsing items failed");
+    assert_eq!(v, vec![1, 2]);
+    // definite length
+    let input = &hex!("30 06 02 01 01 02 01 02");
+    assert!(<Vec<u32>>::from_cer(input).is_err());
+}
+
+#[test]
+fn from_cer_set_of() {
+    let input = &hex!("31 80 02 01 01 02 01 02 00 00");
+    let (_, set) = <SetOf<u32>>::from_cer(input).expect("parsing failed");
+    assert_eq!(set.as_ref(), &[1, 2]);
+    // elements must be sorted
+    let input = &hex!("31 80 02 01 02 02 01 01 00 00");
+    assert!(matches!(
+        <SetOf<u32>>::from_cer(input),
+        Err(Err::Error(Error::CerConstraintFailed(
+            CerConstraint::SetOfNotSorted
+        )))
+    ));
+}
+
+#[test]
+fn from_cer_tagged() {
+    let input = &hex!("a0 80 02 01 05 00 00");
+    let (_, t) = TaggedExplicit::<u32, Error, 0>::from_cer(input).expect("parsing failed");
+    assert_eq!(t.into_inner(), 5);
+    let input = &hex!("a0 03 02 01 05");
+    assert!(TaggedExplicit::<u32, Error, 0>::from_cer(input).is_err());
+    let input = &hex!("81 01 05");
+    let (_, t) = TaggedImplicit::<u32, Error, 1>::from_cer(input).expect("parsing failed");
+    assert_eq!(t.into_inner(), 5);
+}
+
+#[test]
+fn to_cer_primitive() {
+    assert_eq!(5u32.to_cer_vec().unwrap(), &hex!("02 01 05"));
+    assert_eq!(
+        OctetString::new(&[1, 2]).to_cer_vec().unwrap(),
+        &hex!("04 02 01 02")
+    );
+    assert_eq!(Null {}.to_cer_vec().unwrap(), &hex!("05 00"));
+}
+
+#[test]
+fn to_cer_long_strings() {
+    let data = vec![0x42; 2500];
+    let s = OctetString::new(&data);
+    let v = s.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v[..6], &hex!("24 80 04 82 03 e8"));
+    assert_eq!(&v[v.len() - 2..], &[0, 0]);
+    let (_, s2) = OctetString::from_cer(&v).expect("parsing failed");
+    assert_eq!(s2.as_ref(), &data[..]);
+
+    let text = "x".repeat(1001);
+    let s = Utf8String::from(text.as_str());
+    let v = s.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v[..2], &hex!("2c 80"));
+    let (_, s2) = Utf8String::from_cer(&v).expect("parsing failed");
+    assert_eq!(s2.as_ref(), text);
+
+    let b = BitString::from_bits((0..8002).map(|i| i % 3 == 0));
+    let v = b.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v[..7], &hex!("23 80 03 82 03 e8 00"));
+    let (_, b2) = BitString::from_cer(&v).expect("parsing failed");
+    assert_eq!(b2, b);
+}
+
+#[test]
+fn to_cer_constructed() {
+    let v = vec![1u32, 2];
+    assert_eq!(
+        v.to_cer_vec().unwrap(),
+        &hex!("30 80 02 01 01 02 01 02 00 00")
+    );
+    // SET OF elements are sorted
+    let set = SetOf::from_iter(vec![0x101u32, 2, 1]);
+    let v = set.to_cer_vec().unwrap();
+    assert_eq!(v, &hex!("31 80 02 01 01 02 01 02 02 02 01 01 00 00"));
+    assert!(<SetOf<u32>>::from_cer(&v).is_ok());
+    // tagged values
+    let t = TaggedExplicit::<u32, Error, 0>::explicit(5);
+    assert_eq!(t.to_cer_vec().unwrap(), &hex!("a0 80 02 01 05 00 00"));
+    let t = TaggedImplicit::<u32, Error, 1>::implicit(5);
+    assert_eq!(t.to_cer_vec().unwrap(), &hex!("81 01 05"));
+    let t = TaggedImplicit::<Vec<u32>, Error, 2>::implicit(vec![5]);
+    assert_eq!(t.to_cer_vec().unwrap(), &hex!("a2 80 02 01 05 00 00"));
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn asn1_tool(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_asn1-tool"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("could not run asn1-tool");
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn cli_dump() {
+    // hexadecimal input
+    let output = asn1_tool(&["dump", "--no-offsets"], b"30 06 02 01 05 0c 01 61\n");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "SEQUENCE\n  INTEGER 5\n  UTF8String \"a\"\n"
+    );
+    // binary input, with value notation
+    let output = asn1_tool(
+        &["parse"],
+        &[0x30, 0x83, 0x00, 0x00, 0x03, 0x01, 0x01, 0xff],
+    );
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "{\n  TRUE\n}\n");
+    // PEM input, with several blocks
+    let pem =
+        b"-----BEGIN A-----\nAgEF\n-----END A-----\n-----BEGIN B-----\nAQH/\n-----END B-----\n";
+    let output = asn1_tool(&["parse"], pem);
+    assert_eq!(stdout(&output), "5\nTRUE\n");
+    let output = asn1_tool(&["parse", "--block", "1"], pem);
+    assert_eq!(stdout(&output), "TRUE\n");
+    let output = asn1_tool(
+        &["parse", "--strict"],
+        b"-----BEGIN A-----\n AgEF\n-----END A-----\n",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    // base64 input
+    let output = asn1_tool(&["parse", "--inform", "base64"], b"MAMCAQU=");
+    assert_eq!(stdout(&output), "{\n  5\n}\n");
+}
+
+#[test]
+fn cli_validate() {
+    let output = asn1_tool(&["validate"], b"30 04 02 02 00 01");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "valid BER\n");
+    let output = asn1_tool(&["validate", "--der"], b"30 04 02 02 00 01");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "offset 2: DER Failed constraint: Leading zeroes in Integer encoding\n1 DER violation(s)\n"
+    );
+    let output = asn1_tool(&["validate"], b"30 04 01 02 00 00");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "offset 0: Invalid Length\n");
+}
+
+#[test]
+fn cli_extract() {
+    // SEQUENCE { INTEGER, OCTET STRING { SEQUENCE { BOOLEAN } } }
+    let input = b"30 0a 02 01 05 04 05 30 03 01 01 ff";
+    let output = asn1_tool(&["extract", "1.0.0", "--outform", "hex"], input);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "0101ff\n");
+    let output = asn1_tool(&["extract", "0"], input);
+    assert_eq!(output.stdout, &[0x02, 0x01, 0x05]);
+    let output = asn1_tool(&["extract", "2"], input);
+    assert_eq!(output.status.code(), Some(1));
+    let output = asn1_tool(&["extract", "0.0"], input);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn cli_convert() {
+    // BER: indefinite length, non-minimal INTEGER, unsorted SET, segmented OCTET STRING
+    let input = b"30 80 02 02 00 01 31 06 02 01 02 01 01 01 24 80 04 01 61 04 01 62 00 00 00 00";
+    let output = asn1_tool(&["convert", "--outform", "hex"], input);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "300f02010131060101ff02010204026162\n");
+    let output = asn1_tool(
+        &["convert", "--to", "ber", "--outform", "hex"],
+        b"30 03 02 01 01",
+    );
+    assert_eq!(stdout(&output), "30800201010000\n");
+    let output = asn1_tool(
+        &["convert", "--outform", "pem", "--label", "DATA"],
+        b"02 01 05",
+    );
+    assert_eq!(
+        stdout(&output),
+        "-----BEGIN DATA-----\nAgEF\n-----END DATA-----\n"
+    );
+    let output = asn1_tool(&["convert", "--outform", "pem"], b"02 01 05");
+    assert_eq!(output.status.code(), Some(2));
+    // usage errors
+    let output = asn1_tool(&["convert", "--to", "xer"], b"");
+    assert_eq!(output.status.code(), Some(2));
+    // nesting is limited: 100000 nested SEQUENCEs (definite length, built from the innermost)
+    let mut headers = Vec::new();
+    let mut len = 0usize;
+    for _ in 0..100_000 {
+        let header = match len {
+            0..=0x7f => vec![0x30, len as u8],
+            0x80..=0xff => vec![0x30, 0x81, len as u8],
+            0x100..=0xffff => vec![0x30, 0x82, (len >> 8) as u8, len as u8],
+            _ => vec![0x30, 0x83, (len >> 16) as u8, (len >> 8) as u8, len as u8],
+        };
+        len += header.len();
+        headers.push(header);
+    }
+    let input: Vec<u8> = headers.into_iter().rev().flatten().collect();
+    for to in ["der", "ber"] {
+        let output = asn1_tool(&["convert", "--inform", "der", "--to", to], &input);
+        assert_eq!(output.status.code(), Some(1));
+        let stderr = std::str::from_utf8(&output.stderr).unwrap();
+        assert!(stderr.contains("depth"), "unexpected error: {}", stderr);
+    }
+}
+
+#[test]
+fn cli_convert_validate() {
+    for (input, expected) in [
+        (
+            &b"30 80 02 02 00 01 31 06 02 01 02 01 01 01 24 80 04 01 61 04 01 62 00 00 00 00"[..],
+            "300f02010131060101ff02010204026162\n",
+        ),
+        // GeneralizedTime with an offset: converted to UTC
+        (
+            b"18 13 32 30 32 32 30 31 30 32 30 33 30 34 30 35 2b 30 31 30 30",
+            "180f32303232303130323032303430355a\n",
+        ),
+        // REAL: canonical form
+        (b"09 03 80 01 04", "0903800301\n"),
+    ] {
+        let output = asn1_tool(&["convert", "--outform", "hex"], input);
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), expected);
+        let output = asn1_tool(&["validate", "--der"], &output.stdout);
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), "0 DER violation(s)\n");
+    }
+    // local time cannot be converted to UTC
+    let output = asn1_tool(
+        &["convert"],
+        b"18 0e 32 30 32 32 30 31 30 32 30 33 30 34 30 35",
+    );
+    assert_eq!(output.status.code(), Some(1));
+}
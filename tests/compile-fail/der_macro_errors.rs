@@ -0,0 +1,16 @@
+use asn1_rs::der;
+
+fn main() {
+    // unknown type
+    let _ = der!(REAL 1);
+    // invalid OID
+    let _ = der!(OID 1.2.a);
+    // invalid hex string
+    let _ = der!(OCTET STRING "0g");
+    // character not allowed in a PrintableString
+    let _ = der!(PrintableString "not_printable");
+    // missing value
+    let _ = der!(SEQUENCE { INTEGER });
+    // both IMPLICIT and EXPLICIT
+    let _ = der!([0] IMPLICIT EXPLICIT NULL);
+}
@@ -50,16 +50,21 @@ fn from_der_bitstring() {
         )))
     );
     //
-    // long form of length (invalid, < 127)
+    // long form of length (invalid, < 128)
     //
-    // let input = &hex!("03 81 04 06 6e 5d c0");
-    // let res = BitString::from_der(input);
-    // assert_eq!(res, Err(Err::Error(Error::DerConstraintFailed)));
+    let input = &hex!("03 81 04 06 6e 5d c0");
+    let res = BitString::from_der(input);
+    assert_eq!(
+        res,
+        Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::NonMinimalLength
+        )))
+    );
 }
 
 #[test]
 fn from_der_bitstring_constructed() {
-    let bytes: &[u8] = &hex!("23 81 0c 03 03 00 0a 3b 03 05 04 5f 29 1c d0");
+    let bytes: &[u8] = &hex!("23 0c 03 03 00 0a 3b 03 05 04 5f 29 1c d0");
     assert_eq!(
         BitString::from_der(bytes),
         Err(Err::Error(Error::ConstructUnexpected))
@@ -75,6 +80,27 @@ fn from_der_bmpstring() {
     assert_eq!(rem, &[]);
 }
 
+#[test]
+fn from_der_raw_bmpstring() {
+    let input = &hex!("1e 06 00 55 00 73 20 ac");
+    let (rem, result) = RawBmpString::from_der(input).expect("parsing failed");
+    assert_eq!(rem, &[]);
+    assert_eq!(result.as_bytes(), &input[2..]);
+    assert!(result.chars().eq("Us€".chars()));
+    assert_eq!(result.string(), "Us€");
+    // surrogates are not valid in UCS-2, even when paired
+    let input = &hex!("1e 02 d8 3d");
+    let _ = RawBmpString::from_der(input).expect_err("parsing should fail");
+    let input = &hex!("1e 04 d8 3d de 00");
+    let _ = RawBmpString::from_der(input).expect_err("parsing should fail");
+    let _ = RawBmpString::new(&input[2..]).expect_err("surrogates should be rejected");
+    let _ = BmpString::from_der(input).expect_err("parsing should fail");
+    // odd length
+    let input = &hex!("1e 03 00 55 00");
+    let _ = RawBmpString::from_der(input).expect_err("parsing should fail");
+    let _ = BmpString::from_der(input).expect_err("parsing should fail");
+}
+
 #[test]
 fn from_der_bool() {
     let input = &hex!("01 01 00");
@@ -97,6 +123,73 @@ fn from_der_bool() {
     );
 }
 
+#[test]
+fn from_der_directorystring() {
+    let input = &hex!("13 0b 4d 61 72 69 65 20 43 75 72 69 65");
+    let (rem, result) = DirectoryString::from_der(input).expect("parsing failed");
+    assert_eq!(rem, &[]);
+    assert_eq!(
+        result,
+        DirectoryString::PrintableString(PrintableString::new("Marie Curie"))
+    );
+    assert_eq!(result.to_der_vec().expect("serialization failed"), input);
+    // BMPString
+    let input = &hex!("1e 08 00 55 00 73 00 65 00 72");
+    let (_, result) = DirectoryString::from_der(input).expect("parsing failed");
+    assert_eq!(result.tag(), Tag::BmpString);
+    assert_eq!(result.as_ref(), "User");
+    // not an alternative of the CHOICE
+    let input = &hex!("16 04 55 73 65 72");
+    let _ = DirectoryString::from_der(input).expect_err("parsing should fail");
+    // SIZE (1..MAX)
+    let input = &hex!("0c 00");
+    let _ = DirectoryString::from_der(input).expect_err("parsing should fail");
+}
+
+#[cfg(feature = "stringprep")]
+#[test]
+fn directorystring_matches() {
+    let (_, printable) =
+        DirectoryString::from_der(&hex!("13 0b 4d 61 72 69 65 20 43 75 72 69 65")).unwrap();
+    let (_, bmp) = DirectoryString::from_der(&hex!(
+        "1e 1a 00 20 00 6d 00 61 00 72 00 69 00 65 00 20 00 20 00 43 00 55 00 52 00 49 00 45"
+    ))
+    .unwrap();
+    assert!(printable.matches(&bmp));
+    // compatibility characters and accents
+    let a = DirectoryString::Utf8String(Utf8String::new("ﬁlé"));
+    let b = DirectoryString::TeletexString(TeletexString::from("FILE\u{301}".to_string()));
+    assert_eq!(a.normalized().unwrap(), "filé");
+    assert!(a.matches(&b));
+    assert!(!a.matches(&printable));
+    // prohibited characters
+    let c = DirectoryString::Utf8String(Utf8String::new("a\u{e000}"));
+    assert!(c.normalized().is_err());
+    assert!(!c.matches(&c));
+}
+
+#[test]
+fn from_der_constrained_int() {
+    type Version = ConstrainedInt<1, 255>;
+    let (rem, v) = Version::from_der(&hex!("02 02 00 ff")).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(v.value(), 255);
+    assert_eq!(v.offset(), 254);
+    assert!(matches!(
+        Version::from_der(&hex!("02 02 01 00")),
+        Err(Err::Error(Error::InvalidValue { .. }))
+    ));
+    // negative range
+    type Temperature = ConstrainedInt<-40, 85>;
+    let (_, t) = Temperature::from_der(&hex!("02 01 d8")).expect("parsing failed");
+    assert_eq!(t.value(), -40);
+    assert_eq!(t.offset(), 0);
+    assert_eq!(Temperature::range(), 125);
+    assert!(Temperature::from_der(&hex!("02 01 d7")).is_err());
+    // DER constraints are checked
+    assert!(Version::from_der(&hex!("02 02 00 01")).is_err());
+}
+
 #[test]
 fn from_der_enumerated() {
     let input = &hex!("0a 01 02");
@@ -105,6 +198,42 @@ fn from_der_enumerated() {
     assert_eq!(result.0, 2);
 }
 
+#[test]
+fn from_der_enumerated_signed() {
+    let input = &hex!("0a 01 fe");
+    let (_, result) = Enumerated::from_der(input).expect("parsing failed");
+    assert_eq!(result, Enumerated(-2));
+    let input = &hex!("0a 02 00 80");
+    let (_, result) = Enumerated::from_der(input).expect("parsing failed");
+    assert_eq!(result, Enumerated(128));
+    // non-minimal encodings
+    let input = &hex!("0a 02 00 01");
+    assert_eq!(
+        Enumerated::from_der(input),
+        Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::IntegerLeadingZeroes
+        )))
+    );
+    let input = &hex!("0a 02 ff 80");
+    assert_eq!(
+        Enumerated::from_der(input),
+        Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::IntegerLeadingFF
+        )))
+    );
+    let (_, result) = Enumerated::from_ber(input).expect("parsing failed");
+    assert_eq!(result, Enumerated(-128));
+    // too large for i64, but not for BigEnumerated
+    let input = &hex!("0a 09 01 00 00 00 00 00 00 00 00");
+    assert_eq!(
+        Enumerated::from_der(input),
+        Err(Err::Error(Error::IntegerTooLarge))
+    );
+    let (_, result) = BigEnumerated::from_der(input).expect("parsing failed");
+    assert_eq!(result.as_ref(), &input[2..]);
+    assert_eq!(result.as_i64(), Err(Error::IntegerTooLarge));
+}
+
 #[test]
 fn from_der_generalizedtime() {
     let input = &hex!("18 0F 32 30 30 32 31 32 31 33 31 34 32 39 32 33 5A FF");
@@ -158,6 +287,48 @@ fn from_der_indefinite_length() {
     );
 }
 
+#[test]
+fn from_der_header_non_minimal() {
+    let non_minimal = |constraint| Err(Err::Error(Error::DerConstraintFailed(constraint)));
+    // high-tag-number form for a tag < 31
+    let input = &hex!("1f 02 01 00");
+    assert_eq!(
+        Header::from_der(input),
+        non_minimal(DerConstraint::NonMinimalTag)
+    );
+    // leading 0x80 in high-tag-number form
+    let input = &hex!("5f 80 21 00");
+    assert_eq!(
+        Header::from_der(input),
+        non_minimal(DerConstraint::NonMinimalTag)
+    );
+    let (_, header) = Header::from_der(&hex!("5f 21 00")).expect("parsing failed");
+    assert_eq!(header.tag(), Tag(33));
+    // long form for a length < 128
+    let input = &hex!("04 81 01 ff");
+    assert_eq!(
+        Header::from_der(input),
+        non_minimal(DerConstraint::NonMinimalLength)
+    );
+    // leading zero in long form
+    let input = &hex!("04 82 00 80");
+    assert_eq!(
+        Header::from_der(input),
+        non_minimal(DerConstraint::NonMinimalLength)
+    );
+    let (_, header) = Header::from_der(&hex!("04 81 80")).expect("parsing failed");
+    assert_eq!(header.length(), Length::Definite(128));
+    // BER accepts all forms
+    for input in [
+        &hex!("1f 02 01 00")[..],
+        &hex!("5f 80 21 00"),
+        &hex!("04 81 01 ff"),
+        &hex!("04 82 00 80"),
+    ] {
+        assert!(Header::from_ber(input).is_ok());
+    }
+}
+
 #[test]
 fn from_der_int() {
     let input = &hex!("02 01 02 ff ff");
@@ -202,9 +373,25 @@ fn from_der_oid() {
     assert_eq!(rem, &[]);
 }
 
+#[test]
+fn from_der_oid_invalid() {
+    let input = &hex!("06 00");
+    assert_eq!(Oid::from_der(input), Err(Err::Error(Error::OidEmpty)));
+    let input = &hex!("06 02 80 01");
+    assert_eq!(
+        Oid::from_der(input),
+        Err(Err::Error(Error::OidArcNotMinimal))
+    );
+    let input = &hex!("06 02 2a 86");
+    assert_eq!(
+        Oid::from_der(input),
+        Err(Err::Error(Error::OidArcTruncated))
+    );
+}
+
 #[test]
 fn from_der_optional() {
-    let input = &hex!("30 0a 0a 03 00 00 01 02 03 01 00 01");
+    let input = &hex!("30 08 0a 01 01 02 03 01 00 01");
     let (rem, result) = Sequence::from_der_and_then(input, |input| {
         let (i, obj0) = <Option<Enumerated>>::from_der(input)?;
         let (i, obj1) = u32::from_der(i)?;
@@ -216,6 +403,18 @@ fn from_der_optional() {
     assert_eq!(rem, &[]);
 }
 
+#[test]
+fn from_der_raw_universalstring() {
+    let input = &hex!("1c 08 00 00 00 55 00 01 f6 00");
+    let (rem, result) = RawUniversalString::from_der(input).expect("parsing failed");
+    assert_eq!(rem, &[]);
+    assert_eq!(result.as_bytes(), &input[2..]);
+    assert!(result.chars().eq("U😀".chars()));
+    // invalid character
+    let input = &hex!("1c 04 00 11 00 00");
+    let _ = RawUniversalString::from_der(input).expect_err("parsing should fail");
+}
+
 #[test]
 fn from_der_relative_oid() {
     let input = &hex!("0d 04 c2 7b 03 02");
@@ -372,6 +571,78 @@ fn from_der_opt_int() {
     assert_eq!(res, Err(nom::Err::Incomplete(Needed::new(1))));
 }
 
+#[test]
+fn from_der_fixed_size() {
+    // [u8; N] is OCTET STRING (SIZE (N))
+    let (_, b) = <[u8; 3]>::from_der(&hex!("04 03 01 02 03")).expect("parsing failed");
+    assert_eq!(b, [1, 2, 3]);
+    assert_eq!(
+        <[u8; 4]>::from_der(&hex!("04 03 01 02 03")),
+        Err(Err::Error(Error::SizeConstraintFailed {
+            min: 4,
+            max: 4,
+            actual: 3
+        }))
+    );
+    // SEQUENCE SIZE (N) OF
+    let input = &hex!("30 06 02 01 01 02 01 02");
+    let (_, seq) = FixedSequenceOf::<u8, 2>::from_der(input).expect("parsing failed");
+    assert_eq!(seq.into_inner(), [1, 2]);
+    assert_eq!(
+        FixedSequenceOf::<u8, 1>::from_der(input),
+        Err(Err::Error(Error::SizeConstraintFailed {
+            min: 1,
+            max: 1,
+            actual: 2
+        }))
+    );
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn from_der_heapless_vec() {
+    let input = &hex!("30 06 02 01 01 02 01 02");
+    let (_, v) = heapless::Vec::<u32, 4>::from_der(input).expect("parsing failed");
+    assert_eq!(v.as_slice(), &[1, 2]);
+    assert_eq!(
+        heapless::Vec::<u32, 1>::from_der(input),
+        Err(Err::Error(Error::SizeConstraintFailed {
+            min: 0,
+            max: 1,
+            actual: 2
+        }))
+    );
+}
+
+#[test]
+fn from_der_size_constrained() {
+    // OCTET STRING (SIZE (1..4))
+    type S<'a> = SizeConstrained<OctetString<'a>, 1, 4>;
+    let (_, s) = S::from_der(&hex!("04 02 01 02")).expect("parsing failed");
+    let b: &[u8] = s.inner().as_ref();
+    assert_eq!(b, &[1, 2]);
+    assert_eq!(
+        S::from_der(&hex!("04 00")),
+        Err(Err::Error(Error::SizeConstraintFailed {
+            min: 1,
+            max: 4,
+            actual: 0
+        }))
+    );
+    assert!(S::from_der(&hex!("04 05 01 02 03 04 05")).is_err());
+    // BIT STRING (SIZE (12)): size is the number of bits
+    let (_, _) = SizeConstrained::<BitString, 12, 12>::from_der(&hex!("03 03 04 ff f0"))
+        .expect("parsing failed");
+    // UTF8String (SIZE (1..2)): size is the number of characters
+    let (_, _) = SizeConstrained::<Utf8String, 1, 2>::from_der(&hex!("0c 04 c3 a9 c3 a9"))
+        .expect("parsing failed");
+    // SEQUENCE SIZE (2) OF INTEGER
+    type V = SizeConstrained<Vec<u32>, 2, 2>;
+    let (_, v) = V::from_der(&hex!("30 06 02 01 01 02 01 02")).expect("parsing failed");
+    assert_eq!(v.into_inner(), vec![1, 2]);
+    assert!(V::from_ber(&hex!("30 03 02 01 01")).is_err());
+}
+
 #[test]
 fn from_der_tagged_explicit() {
     let input = &hex!("a0 03 02 01 02");
@@ -510,3 +781,195 @@ fn from_der_tagged_implicit_all() {
     let _ = parse_der_tagged_implicit::<_, Ia5String, _>(2)(input)
         .expect_err("parsing should have failed");
 }
+
+#[test]
+fn from_der_real() {
+    // canonical values
+    for bytes in [
+        &hex!("09 03 80 ff 01")[..],
+        &hex!("09 04 81 fc 04 03"),
+        &hex!("09 05 03 31 2e 45 32"),
+        &hex!("09 09 03 2d 31 32 35 2e 45 2b 30"),
+        &hex!("09 01 40"),
+        &hex!("09 00"),
+    ] {
+        Real::from_der(bytes).expect("parsing failed");
+    }
+    // non-canonical values
+    for bytes in [
+        // even mantissa
+        &hex!("09 03 80 00 02")[..],
+        // base 8
+        &hex!("09 03 90 ff 0d"),
+        // scaling factor
+        &hex!("09 03 84 ff 0d"),
+        // leading 0 in exponent
+        &hex!("09 04 81 00 01 01"),
+        // leading 0 in mantissa
+        &hex!("09 04 80 01 00 01"),
+        // not NR3
+        &hex!("09 02 01 31"),
+        // trailing 0 in mantissa
+        &hex!("09 06 03 31 30 2e 45 31"),
+        // leading 0 in mantissa
+        &hex!("09 06 03 30 31 2e 45 31"),
+        // no full stop
+        &hex!("09 04 03 31 45 32"),
+        // exponent 0 without +
+        &hex!("09 05 03 31 2e 45 30"),
+        // + in exponent
+        &hex!("09 06 03 31 2e 45 2b 32"),
+    ] {
+        assert_eq!(
+            Real::from_der(bytes),
+            Err(Err::Error(Error::DerConstraintFailed(
+                DerConstraint::RealNotCanonical
+            ))),
+            "{:x?}",
+            bytes
+        );
+    }
+}
+
+#[test]
+fn validate_der_valid() {
+    let input = &hex!(
+        "30 29 01 01 ff 02 02 00 80 03 02 06 c0 05 00 06 03 2a 86 48
+         31 06 02 01 01 02 01 02 17 0d 32 32 30 31 30 31 30 30 30 30 30 30 5a"
+    );
+    assert_eq!(validate_der(input), vec![]);
+    // SET with components sorted by tag, but not by encoding
+    let input = &hex!("31 06 a0 02 05 00 81 00");
+    assert_eq!(validate_der(input), vec![]);
+}
+
+#[test]
+fn validate_der_violations() {
+    let violation = |offset, constraint| Violation {
+        offset,
+        error: Error::DerConstraintFailed(constraint),
+    };
+    // non-minimal tag and length, indefinite length
+    let input = &hex!("1f 02 01 00 04 81 01 ff 30 80 05 00 00 00 bf 80 05 00");
+    assert_eq!(
+        validate_der(input),
+        vec![
+            violation(0, DerConstraint::NonMinimalTag),
+            violation(4, DerConstraint::NonMinimalLength),
+            violation(8, DerConstraint::IndefiniteLength),
+            violation(14, DerConstraint::NonMinimalTag),
+        ]
+    );
+    // values, in a SEQUENCE
+    let input = &hex!("30 14 01 01 01 02 02 ff 80 03 02 04 ff 24 80 04 01 00 00 00 05 00");
+    assert_eq!(
+        validate_der(input),
+        vec![
+            violation(2, DerConstraint::InvalidBoolean),
+            violation(5, DerConstraint::IntegerLeadingFF),
+            violation(9, DerConstraint::UnusedBitsNotZero),
+            violation(13, DerConstraint::IndefiniteLength),
+            violation(13, DerConstraint::Constructed),
+        ]
+    );
+    // SET OF not sorted
+    let input = &hex!("31 06 02 01 02 02 01 01");
+    assert_eq!(
+        validate_der(input),
+        vec![violation(5, DerConstraint::SetNotSorted)]
+    );
+    // time formats
+    let input = &hex!(
+        "17 0b 32 32 30 31 30 31 30 30 30 30 5a
+         18 12 32 30 32 32 30 31 30 31 30 30 30 30 30 30 2e 31 30 5a"
+    );
+    assert_eq!(
+        validate_der(input),
+        vec![
+            violation(0, DerConstraint::MissingSeconds),
+            violation(13, DerConstraint::InvalidTimeFraction),
+        ]
+    );
+}
+
+#[test]
+fn validate_der_invalid_ber() {
+    // truncated content: parsing stops, but violations found before are kept
+    let input = &hex!("30 07 02 02 00 01 02 05 01");
+    assert_eq!(
+        validate_der(input),
+        vec![
+            Violation {
+                offset: 2,
+                error: Error::DerConstraintFailed(DerConstraint::IntegerLeadingZeroes),
+            },
+            Violation {
+                offset: 6,
+                error: Error::Incomplete(Needed::new(4)),
+            },
+        ]
+    );
+}
+
+#[test]
+fn from_der_asn1_value() {
+    // SEQUENCE { OID, UTF8String, [0] { INTEGER }, SET { BOOLEAN, INTEGER } }
+    let input = &hex!(
+        "30 19 06 06 2a 86 48 86 f7 0d 0c 02 61 62 a0 03 02 01 05
+         31 06 01 01 ff 02 01 02"
+    );
+    let (rem, mut value) = Asn1Value::from_der(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(
+        value,
+        Asn1Value::Sequence(vec![
+            Asn1Value::Oid(oid!(1.2.840 .113549)),
+            Asn1Value::String(Tag::Utf8String, "ab".to_string()),
+            Asn1Value::Raw {
+                class: Class::ContextSpecific,
+                constructed: true,
+                tag: Tag(0),
+                data: hex!("02 01 05").to_vec(),
+            },
+            Asn1Value::Set(vec![
+                Asn1Value::Boolean(true),
+                Asn1Value::Integer(Integer::from(2)),
+            ]),
+        ])
+    );
+    assert_eq!(value.to_der_len(), Ok(input.len()));
+    assert_eq!(value.to_der_vec().expect("serialization failed"), input);
+    // tagged values can be decoded from their content
+    if let Some(Asn1Value::Raw { data, .. }) = value.elements().and_then(|e| e.get(2)) {
+        let (_, inner) = Asn1Value::from_der(data).expect("parsing failed");
+        assert_eq!(inner, Asn1Value::Integer(Integer::from(5)));
+    } else {
+        panic!("expected a raw value");
+    }
+    // modify the tree: SET elements are sorted when encoding
+    let elements = value.elements_mut().unwrap();
+    elements[1] = Asn1Value::String(Tag::PrintableString, "xyz".to_string());
+    elements[3]
+        .elements_mut()
+        .unwrap()
+        .insert(0, Asn1Value::Null);
+    let v = value.to_der_vec().expect("serialization failed");
+    assert_eq!(value.to_der_len(), Ok(v.len()));
+    assert_eq!(
+        &v,
+        &hex!(
+            "30 1c 06 06 2a 86 48 86 f7 0d 13 03 78 79 7a a0 03 02 01 05
+             31 08 01 01 ff 02 01 02 05 00"
+        )
+    );
+    // DER constraints are checked recursively
+    let input = &hex!("30 04 02 02 00 01");
+    assert_eq!(
+        Asn1Value::from_der(input),
+        Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::IntegerLeadingZeroes
+        )))
+    ); // invalid values are rejected before checking DER constraints
+    assert!(Asn1Value::from_der(&hex!("01 00")).is_err());
+    assert!(Asn1Value::from_der(&hex!("30 02 01 00")).is_err());
+}
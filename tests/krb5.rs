@@ -6,7 +6,7 @@
 use asn1_rs::*;
 use hex_literal::hex;
 
-const PRINCIPAL_NAME: &[u8] = &hex!("30 81 11 a0 03 02 01 00 a1 0a 30 81 07 1b 05 4a 6f 6e 65 73");
+const PRINCIPAL_NAME: &[u8] = &hex!("30 10 a0 03 02 01 00 a1 09 30 07 1b 05 4a 6f 6e 65 73");
 
 /// PrincipalName   ::= SEQUENCE {
 ///         name-type       [0] Int32,
@@ -32,8 +32,7 @@ impl Tagged for PrincipalName {
 
 impl<'a> FromDer<'a> for PrincipalName {
     fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self> {
-        // XXX in the example above, PRINCIPAL_NAME does not respect DER constraints (length is using long form while < 127)
-        let (rem, seq) = Sequence::from_ber(bytes)?;
+        let (rem, seq) = Sequence::from_der(bytes)?;
         seq.and_then(|data| {
             let input = &data;
             let (i, t) = parse_der_tagged_explicit::<_, u32, _>(0)(input)?;
@@ -0,0 +1,349 @@
+#![cfg(feature = "serde")]
+
+use asn1_rs::*;
+use hex_literal::hex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Header {
+    version: u8,
+    name: String,
+    flags: Vec<bool>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Body {
+    Empty,
+    Value(i64),
+    Pair(u32, String),
+    Point { x: i32, y: i32 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Message {
+    header: Header,
+    body: Body,
+    comment: Option<String>,
+}
+
+/// Bytes, serialized as `OCTET STRING`
+#[derive(Debug, PartialEq)]
+struct Bytes(Vec<u8>);
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let v = <&[u8]>::deserialize(deserializer)?;
+        Ok(Bytes(v.to_vec()))
+    }
+}
+
+#[test]
+fn serde_to_der_struct() {
+    let header = Header {
+        version: 2,
+        name: "abc".to_string(),
+        flags: vec![true, false],
+    };
+    let v = serde_to_der(&header).expect("serialization failed");
+    assert_eq!(
+        &v,
+        &hex!("30 10 02 01 02 0c 03 61 62 63 30 06 01 01 ff 01 01 00")
+    );
+    let (rem, header2) = serde_from_der::<Header>(&v).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(header2, header);
+}
+
+#[test]
+fn serde_to_der_enum() {
+    for (body, expected) in [
+        (Body::Empty, &hex!("80 00")[..]),
+        (Body::Value(-1), &hex!("a1 03 02 01 ff")),
+        (
+            Body::Pair(1, "a".to_string()),
+            &hex!("a2 06 02 01 01 0c 01 61"),
+        ),
+        (Body::Point { x: 1, y: 2 }, &hex!("a3 06 02 01 01 02 01 02")),
+    ] {
+        let v = serde_to_der(&body).expect("serialization failed");
+        assert_eq!(&v, expected);
+        let (_, body2) = serde_from_der::<Body>(&v).expect("parsing failed");
+        assert_eq!(body2, body);
+    }
+    // unknown variant
+    assert!(serde_from_der::<Body>(&hex!("84 00")).is_err());
+}
+
+#[test]
+fn serde_to_der_optional() {
+    let mut msg = Message {
+        header: Header {
+            version: 1,
+            name: String::new(),
+            flags: vec![],
+        },
+        body: Body::Empty,
+        comment: None,
+    };
+    // absent OPTIONAL value is not encoded
+    let v = serde_to_der(&msg).expect("serialization failed");
+    assert_eq!(&v, &hex!("30 0b 30 07 02 01 01 0c 00 30 00 80 00"));
+    let (_, msg2) = serde_from_der::<Message>(&v).expect("parsing failed");
+    assert_eq!(msg2, msg);
+
+    msg.comment = Some("hi".to_string());
+    let v = serde_to_der(&msg).expect("serialization failed");
+    assert_eq!(&v[v.len() - 4..], &hex!("0c 02 68 69"));
+    let (_, msg2) = serde_from_der::<Message>(&v).expect("parsing failed");
+    assert_eq!(msg2, msg);
+
+    // absent values are accepted only at the end of a SEQUENCE
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct S {
+        a: Option<u32>,
+        b: Option<u32>,
+    }
+    let s = S {
+        a: Some(5),
+        b: None,
+    };
+    let v = serde_to_der(&s).expect("serialization failed");
+    assert_eq!(&v, &hex!("30 03 02 01 05"));
+    let (_, s2) = serde_from_der::<S>(&v).expect("parsing failed");
+    assert_eq!(s2, s);
+    let s = S {
+        a: None,
+        b: Some(5),
+    };
+    assert!(serde_to_der(&s).is_err());
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct T {
+        a: Option<u32>,
+        b: u32,
+    }
+    assert!(serde_to_der(&T { a: None, b: 5 }).is_err());
+    let t = T { a: Some(1), b: 5 };
+    let v = serde_to_der(&t).expect("serialization failed");
+    let (_, t2) = serde_from_der::<T>(&v).expect("parsing failed");
+    assert_eq!(t2, t);
+    // SEQUENCE OF cannot contain absent values
+    let v = vec![Some(1), Some(2)];
+    let bytes = serde_to_der(&v).expect("serialization failed");
+    let (_, v2) = serde_from_der::<Vec<Option<u32>>>(&bytes).expect("parsing failed");
+    assert_eq!(v2, v);
+    assert!(serde_to_der(&vec![Some(1), None, Some(2)]).is_err());
+    assert!(serde_to_der(&Some(None::<u32>)).is_err());
+}
+
+#[test]
+fn serde_to_der_other_types() {
+    let v = serde_to_der(&Bytes(vec![1, 2, 3])).expect("serialization failed");
+    assert_eq!(&v, &hex!("04 03 01 02 03"));
+    let (_, b) = serde_from_der::<Bytes>(&v).expect("parsing failed");
+    assert_eq!(b, Bytes(vec![1, 2, 3]));
+
+    let v = serde_to_der(&()).expect("serialization failed");
+    assert_eq!(&v, &hex!("05 00"));
+
+    let v = serde_to_der(&(1u8, 'x', 0.5f64)).expect("serialization failed");
+    assert_eq!(&v, &hex!("30 0e 02 01 01 0c 01 78 09 06 03 35 2e 45 2d 31"));
+    let (_, t) = serde_from_der::<(u8, char, f64)>(&v).expect("parsing failed");
+    assert_eq!(t, (1, 'x', 0.5));
+
+    let mut map = BTreeMap::new();
+    map.insert(1u32, "one".to_string());
+    map.insert(2u32, "two".to_string());
+    let v = serde_to_der(&map).expect("serialization failed");
+    assert_eq!(
+        &v,
+        &hex!("30 14 30 08 02 01 01 0c 03 6f 6e 65 30 08 02 01 02 0c 03 74 77 6f")
+    );
+    let (_, map2) = serde_from_der::<BTreeMap<u32, String>>(&v).expect("parsing failed");
+    assert_eq!(map2, map);
+}
+
+#[test]
+fn serde_from_ber_der() {
+    // strings of other types are accepted
+    let input = &hex!("30 0b 02 01 02 13 01 61 30 03 01 01 ff");
+    let (_, header) = serde_from_der::<Header>(input).expect("parsing failed");
+    assert_eq!(header.name, "a");
+    // indefinite length is accepted only in BER
+    let input = &hex!("30 80 02 01 02 0c 00 30 00 00 00");
+    assert!(serde_from_der::<Header>(input).is_err());
+    let (rem, header) = serde_from_ber::<Header>(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(header.version, 2);
+    // DER constraints are checked
+    let input = &hex!("30 08 02 02 00 02 0c 00 30 00");
+    assert!(serde_from_der::<Header>(input).is_err());
+    assert!(serde_from_ber::<Header>(input).is_ok());
+    // trailing elements
+    let input = &hex!("30 0a 02 01 02 0c 00 30 00 05 00");
+    assert!(serde_from_ber::<Header>(input).is_err());
+    // invalid values are rejected before checking DER constraints
+    let input = &hex!("bd 04 79 00 01 00 00");
+    assert!(serde_from_der::<serde_json::Value>(input).is_err());
+    assert!(serde_from_der::<bool>(&hex!("01 00")).is_err());
+    // times are checked
+    assert!(serde_from_ber::<serde_json::Value>(&hex!("18 00")).is_err());
+    let input = b"\x18\x0f20200101000000+";
+    assert!(serde_from_ber::<serde_json::Value>(input).is_err());
+    let input = b"\x18\x0f20200101000000Z";
+    let (_, value) = serde_from_der::<serde_json::Value>(input).expect("parsing failed");
+    assert_eq!(value, "20200101000000Z");
+    // empty ENUMERATED
+    assert!(serde_from_ber::<serde_json::Value>(&hex!("0a 00")).is_err());
+}
+
+#[test]
+fn serde_from_ber_max_depth() {
+    // nested SEQUENCEs, built from the innermost one
+    let mut headers = Vec::new();
+    let mut len = 0usize;
+    for _ in 0..200_000 {
+        let header = match len {
+            0..=0x7f => vec![0x30, len as u8],
+            0x80..=0xff => vec![0x30, 0x81, len as u8],
+            0x100..=0xffff => vec![0x30, 0x82, (len >> 8) as u8, len as u8],
+            _ => vec![0x30, 0x83, (len >> 16) as u8, (len >> 8) as u8, len as u8],
+        };
+        len += header.len();
+        headers.push(header);
+    }
+    let input: Vec<u8> = headers.into_iter().rev().flatten().collect();
+    let res = serde_from_ber::<serde_json::Value>(&input);
+    assert!(matches!(res, Err(Err::Error(Error::BerMaxDepth))));
+    // a few levels are accepted
+    let input = &hex!("30 06 30 04 30 02 30 00");
+    let (_, value) = serde_from_ber::<serde_json::Value>(input).expect("parsing failed");
+    assert_eq!(value, serde_json::json!([[[[]]]]));
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Types<'a> {
+    oid: Oid<'a>,
+    relative_oid: Oid<'a>,
+    int: Integer<'a>,
+    big_int: Integer<'a>,
+    bits: BitString<'a>,
+    unaligned_bits: BitString<'a>,
+    octets: OctetString<'a>,
+    generalized_time: GeneralizedTime,
+    utc_time: UtcTime,
+}
+
+fn types() -> Types<'static> {
+    Types {
+        oid: oid!(1.2.840 .113549),
+        relative_oid: Oid::from_relative(&[1, 2]).unwrap(),
+        int: Integer::from(-300),
+        big_int: Integer::from_u128(1 << 64),
+        bits: BitString::new(0, &[0x0a, 0x3b]),
+        unaligned_bits: BitString::new(4, &[0xa0]),
+        octets: OctetString::new(&[0xde, 0xad]),
+        generalized_time: GeneralizedTime::new(ASN1DateTime::new(
+            2022,
+            1,
+            2,
+            3,
+            4,
+            5,
+            Some(60),
+            ASN1TimeZone::Offset(-1, 30),
+        )),
+        utc_time: UtcTime::new(ASN1DateTime::new(
+            49,
+            12,
+            31,
+            23,
+            59,
+            59,
+            None,
+            ASN1TimeZone::Z,
+        )),
+    }
+}
+
+#[test]
+fn serde_types_json() {
+    let t = types();
+    let json = serde_json::to_value(&t).expect("serialization failed");
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "oid": "1.2.840.113549",
+            "relative_oid": "rel. 1.2",
+            "int": -300,
+            "big_int": "0x010000000000000000",
+            "bits": "'0A3B'H",
+            "unaligned_bits": "'1010'B",
+            "octets": "dead",
+            "generalized_time": "2022-01-02T03:04:05.060-01:30",
+            "utc_time": "2049-12-31T23:59:59Z",
+        })
+    );
+    let t2: Types = serde_json::from_value(json).expect("deserialization failed");
+    assert_eq!(t2, t);
+    // integers as strings
+    let i: Integer = serde_json::from_str(r#""-18446744073709551616""#).unwrap();
+    assert_eq!(i.as_i128(), Ok(-(1 << 64)));
+    assert!(serde_json::from_str::<Integer>(r#""0x""#).is_err());
+    // invalid values
+    assert!(serde_json::from_str::<Oid>(r#""1.a""#).is_err());
+    assert!(serde_json::from_str::<BitString>(r#""'012'B""#).is_err());
+    assert!(serde_json::from_str::<GeneralizedTime>(r#""2022-01-02 03:04""#).is_err());
+    assert!(serde_json::from_str::<UtcTime>(r#""2050-01-02T03:04:05Z""#).is_err());
+    // Any cannot be deserialized from a human-readable format
+    let any = Any::from_der(&hex!("02 01 05")).unwrap().1;
+    assert_eq!(serde_json::to_string(&any).unwrap(), r#""020105""#);
+    assert!(serde_json::from_str::<Any>(r#""020105""#).is_err());
+    // but Asn1Value can
+    let value = Asn1Value::from_der(&hex!("30 06 02 01 05 01 01 ff"))
+        .unwrap()
+        .1;
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, r#""30060201050101ff""#);
+    let value2: Asn1Value = serde_json::from_str(&json).expect("deserialization failed");
+    assert_eq!(value2, value);
+    assert!(serde_json::from_str::<Asn1Value>(r#""0201""#).is_err());
+    assert!(serde_json::from_str::<Asn1Value>(r#""02010""#).is_err());
+}
+
+#[test]
+fn serde_types_der() {
+    let mut t = types();
+    // DER times are always encoded in UTC
+    t.generalized_time.0.tz = ASN1TimeZone::Z;
+    let v = serde_to_der(&t).expect("serialization failed");
+    // types are encoded as themselves
+    let (_, seq) = Sequence::from_der(&v).expect("parsing failed");
+    let (rem, oid) = Oid::from_der(&seq.content).expect("parsing failed");
+    assert_eq!(oid, t.oid);
+    let (rem, oid) = Oid::from_der_relative(rem).expect("parsing failed");
+    assert_eq!(oid, t.relative_oid);
+    let (_, int) = Integer::from_der(rem).expect("parsing failed");
+    assert_eq!(int.as_i32(), Ok(-300));
+    let (rem, t2) = serde_from_der::<Types>(&v).expect("deserialization failed");
+    assert!(rem.is_empty());
+    assert_eq!(t2, t);
+
+    let any = Any::from_der(&hex!("a0 03 02 01 05")).unwrap().1;
+    let v = serde_to_der(&(&any, 1)).expect("serialization failed");
+    assert_eq!(&v, &hex!("30 08 a0 03 02 01 05 02 01 01"));
+    let (_, (any2, _)) = serde_from_der::<(Any, u8)>(&v).expect("deserialization failed");
+    assert_eq!(any2, any);
+    let value = Asn1Value::try_from(&any).expect("conversion failed");
+    let v = serde_to_der(&(&value, 1)).expect("serialization failed");
+    assert_eq!(&v, &hex!("30 08 a0 03 02 01 05 02 01 01"));
+    let (_, (value2, _)) = serde_from_der::<(Asn1Value, u8)>(&v).expect("deserialization failed");
+    assert_eq!(value2, value);
+}
@@ -48,7 +48,32 @@ fn to_der_tag() {
     let v = (Class::Universal, false, Tag(0x1a1a))
         .to_der_vec()
         .expect("serialization failed");
-    assert_eq!(&v, &[0b1_1111, 0x9a, 0x34]);
+    assert_eq!(&v, &[0b1_1111, 0xb4, 0x1a]);
+}
+
+#[test]
+fn to_der_tag_boundaries() {
+    for (tag, expected) in [
+        (30, &hex!("1e")[..]),
+        (31, &hex!("1f 1f")),
+        (127, &hex!("1f 7f")),
+        (128, &hex!("1f 81 00")),
+        (16383, &hex!("1f ff 7f")),
+        (16384, &hex!("1f 81 80 00")),
+        (0x0fff_ffff, &hex!("1f ff ff ff 7f")),
+        (0x1000_0000, &hex!("1f 81 80 80 80 00")),
+        (Tag::MAX.0, &hex!("1f 8f ff ff ff 7f")),
+    ] {
+        let t = (Class::Universal, false, Tag(tag));
+        let v = t.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, expected, "tag {}", tag);
+        assert_eq!(t.to_der_len(), Ok(expected.len()));
+        // round-trip
+        let mut input = v.clone();
+        input.push(0);
+        let (_, header) = Header::from_der(&input).expect("parsing failed");
+        assert_eq!(header.tag(), Tag(tag));
+    }
 }
 
 #[test]
@@ -90,6 +115,90 @@ fn to_der_bitstring() {
     assert_eq!(&v, &hex!("03 04 06 6e 5d c0"));
     let (_, result) = BitString::from_der(&v).expect("parsing failed");
     assert!(bitstring.eq(&result));
+    // unused bits are cleared when encoding
+    let bitstring = BitString::new(4, &hex!("6f"));
+    let v = bitstring.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("03 02 04 60"));
+    // an empty bitstring has no unused bits
+    let bitstring = BitString::new(3, &[]);
+    let v = bitstring.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("03 01 00"));
+    // built from bits
+    let bitstring =
+        BitString::from_bits([false, true, true, false, true, true, true, false, false]);
+    let v = bitstring.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("03 03 07 6e 00"));
+}
+
+named_bit_string! {
+    #[derive(Debug, Default, PartialEq)]
+    struct KeyUsage {
+        digital_signature = 0,
+        non_repudiation = 1,
+        key_encipherment = 2,
+        data_encipherment = 3,
+        key_agreement = 4,
+        key_cert_sign = 5,
+        crl_sign = 6,
+        encipher_only = 7,
+        decipher_only = 8,
+    }
+}
+
+#[test]
+fn to_der_named_bit_string() {
+    let ku = KeyUsage {
+        key_cert_sign: true,
+        crl_sign: true,
+        ..Default::default()
+    };
+    let v = ku.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("03 02 01 06"));
+    let (_, result) = KeyUsage::from_der(&v).expect("parsing failed");
+    assert_eq!(result, ku);
+    // bit 8 requires a second byte
+    let ku = KeyUsage {
+        decipher_only: true,
+        ..Default::default()
+    };
+    let v = ku.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("03 03 07 00 80"));
+    // no bits set
+    let v = KeyUsage::default()
+        .to_der_vec()
+        .expect("serialization failed");
+    assert_eq!(&v, &hex!("03 01 00"));
+    // trailing zero bits are not allowed in DER, but accepted in BER
+    let bytes = &hex!("03 02 00 06");
+    assert_eq!(
+        KeyUsage::from_der(bytes),
+        Err(Err::Error(Error::DerConstraintFailed(
+            DerConstraint::NamedBitsTrailingZero
+        )))
+    );
+    let (_, result) = KeyUsage::from_ber(bytes).expect("parsing failed");
+    assert!(result.key_cert_sign && result.crl_sign);
+}
+
+#[test]
+fn to_der_bmpstring() {
+    let s = BmpString::from("User€");
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("1e 0a 00 55 00 73 00 65 00 72 20 ac"));
+    let (_, s2) = BmpString::from_der(&v).expect("decoding serialized object failed");
+    assert!(s.eq(&s2));
+    // raw bytes are written unchanged
+    let (_, raw) = RawBmpString::from_der(&v).expect("decoding serialized object failed");
+    assert_eq!(raw.to_der_vec().expect("serialization failed"), v);
+    assert_eq!(raw, RawBmpString::try_from("User€").unwrap());
+    // characters outside the BMP cannot be encoded
+    let s = BmpString::from("\u{1f600}");
+    assert_eq!(s.to_der_len(), Err(Error::StringInvalidCharset));
+    assert!(s.to_der_vec().is_err());
+    assert_eq!(
+        RawBmpString::try_from("\u{1f600}"),
+        Err(Error::StringInvalidCharset)
+    );
 }
 
 #[test]
@@ -111,6 +220,89 @@ fn to_der_bool() {
     assert_eq!(&v, &[0x01, 0x01, 0x8a]);
 }
 
+#[test]
+fn to_der_embedded_pdv() {
+    let syntax = Oid::from(&[1, 2, 3, 4, 5, 6]).unwrap();
+    let transfer = Oid::from(&[2, 1, 1]).unwrap();
+    // identification alternatives
+    let alternatives = vec![
+        (
+            PdvIdentification::Syntaxes {
+                s_abstract: syntax.clone(),
+                s_transfer: transfer.clone(),
+            },
+            &hex!("a0 0b 80 05 2a 03 04 05 06 81 02 51 01")[..],
+        ),
+        (
+            PdvIdentification::Syntax(syntax.clone()),
+            &hex!("81 05 2a 03 04 05 06")[..],
+        ),
+        (
+            PdvIdentification::PresentationContextId(Integer::from(3)),
+            &hex!("82 01 03")[..],
+        ),
+        (
+            PdvIdentification::ContextNegotiation {
+                presentation_context_id: Integer::from(3),
+                presentation_syntax: transfer.clone(),
+            },
+            &hex!("a3 07 80 01 03 81 02 51 01")[..],
+        ),
+        (
+            PdvIdentification::TransferSyntax(transfer.clone()),
+            &hex!("84 02 51 01")[..],
+        ),
+        (PdvIdentification::Fixed, &hex!("85 00")[..]),
+    ];
+    for (identification, expected) in alternatives {
+        let v = identification.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, expected);
+        let pdv = EmbeddedPdv {
+            identification,
+            data_value_descriptor: None,
+            data_value: &[0xaa, 0xa0],
+        };
+        let v = pdv.to_der_vec().expect("serialization failed");
+        let (rem, result) = EmbeddedPdv::from_der(&v).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(result, pdv);
+    }
+    // with data-value-descriptor
+    let pdv = EmbeddedPdv {
+        identification: PdvIdentification::Syntax(syntax),
+        data_value_descriptor: Some(ObjectDescriptor::new("abc")),
+        data_value: &[0xaa, 0xa0],
+    };
+    let v = pdv.to_der_vec().expect("serialization failed");
+    assert_eq!(
+        &v,
+        &hex!("2b 12 a0 07 81 05 2a 03 04 05 06 81 03 61 62 63 82 02 aa a0")
+    );
+    let (_, result) = EmbeddedPdv::from_der(&v).expect("parsing failed");
+    assert_eq!(result, pdv);
+}
+
+#[test]
+fn to_der_enumerated() {
+    for (value, expected) in [
+        (0, &hex!("0a 01 00")[..]),
+        (127, &hex!("0a 01 7f")[..]),
+        (128, &hex!("0a 02 00 80")[..]),
+        (-1, &hex!("0a 01 ff")[..]),
+        (-128, &hex!("0a 01 80")[..]),
+        (-129, &hex!("0a 02 ff 7f")[..]),
+        (i64::MIN, &hex!("0a 08 80 00 00 00 00 00 00 00")[..]),
+    ] {
+        let e = Enumerated(value);
+        let v = e.to_der_vec().expect("serialization failed");
+        assert_eq!(&v, expected);
+        let (_, result) = Enumerated::from_der(&v).expect("parsing failed");
+        assert_eq!(result, e);
+        let big = BigEnumerated::from(e);
+        assert_eq!(big.to_der_vec().expect("serialization failed"), expected);
+    }
+}
+
 #[test]
 fn to_der_generalizedtime() {
     // date without millisecond
@@ -130,6 +322,16 @@ fn to_der_generalizedtime() {
     assert_eq!(&v[2..], b"19991231235959.123Z");
     let (_, time2) = GeneralizedTime::from_der(&v).expect("decoding serialized object failed");
     assert!(time.eq(&time2));
+    //
+    // millisecond with leading or trailing zeroes
+    let dt = ASN1DateTime::new(1999, 12, 31, 23, 59, 59, Some(60), ASN1TimeZone::Z);
+    let time = GeneralizedTime::new(dt);
+    let v = time.to_der_vec().expect("serialization failed");
+    assert_eq!(&v[..2], &hex!("18 12"));
+    assert_eq!(&v[2..], b"19991231235959.06Z");
+    assert_eq!(time.to_der_len(), Ok(v.len()));
+    let (_, time2) = GeneralizedTime::from_der(&v).expect("decoding serialized object failed");
+    assert!(time.eq(&time2));
 }
 
 fn encode_decode_assert_int<T>(t: T, expected: &[u8])
@@ -158,7 +360,64 @@ fn to_der_integer() {
     // signed i32 (> 0)
     encode_decode_assert_int(4, &[0x02, 0x01, 0x04]);
     // signed i32 (< 0)
-    encode_decode_assert_int(-4, &[0x02, 0x05, 0x00, 0xff, 0xff, 0xff, 0xfc]);
+    encode_decode_assert_int(-4, &[0x02, 0x01, 0xfc]);
+    // minimal encodings
+    encode_decode_assert_int(128u8, &[0x02, 0x02, 0x00, 0x80]);
+    encode_decode_assert_int(128i64, &[0x02, 0x02, 0x00, 0x80]);
+    encode_decode_assert_int(-128i64, &[0x02, 0x01, 0x80]);
+    encode_decode_assert_int(-129i64, &[0x02, 0x02, 0xff, 0x7f]);
+    encode_decode_assert_int(-1i64, &[0x02, 0x01, 0xff]);
+    encode_decode_assert_int(i64::MIN, &hex!("02 08 80 00 00 00 00 00 00 00"));
+}
+
+#[test]
+fn to_der_const_int() {
+    const I0: ConstInt = ConstInt::from_u64(0);
+    const I128: ConstInt = ConstInt::from_u64(128);
+    const U64_MAX: ConstInt = ConstInt::from_u64(u64::MAX);
+    const INEG129: ConstInt = ConstInt::from_i64(-129);
+    const I64_MIN: ConstInt = ConstInt::from_i64(i64::MIN);
+    assert_eq!(I0.to_der_vec().unwrap(), &hex!("02 01 00"));
+    assert_eq!(I128.to_der_vec().unwrap(), &hex!("02 02 00 80"));
+    assert_eq!(
+        U64_MAX.to_der_vec().unwrap(),
+        &hex!("02 09 00 ff ff ff ff ff ff ff ff")
+    );
+    assert_eq!(INEG129.to_der_vec().unwrap(), &hex!("02 02 ff 7f"));
+    assert_eq!(
+        I64_MIN.to_der_vec().unwrap(),
+        &hex!("02 08 80 00 00 00 00 00 00 00")
+    );
+    // decode the encoded values
+    assert_eq!(U64_MAX.as_integer().as_u64(), Ok(u64::MAX));
+    assert_eq!(INEG129.as_integer().as_i64(), Ok(-129));
+    let (_, int) = Integer::from_der(I128.as_bytes()).expect("parsing failed");
+    assert_eq!(int, I128.as_integer());
+}
+
+#[test]
+fn to_der_constrained_int() {
+    let i = ConstrainedInt::<0, { i64::MAX }>::new(128).unwrap();
+    assert_eq!(i.to_der_vec().unwrap(), &hex!("02 02 00 80"));
+    let i = ConstrainedInt::<-200, 0>::new(-129).unwrap();
+    assert_eq!(i.to_der_vec().unwrap(), &hex!("02 02 ff 7f"));
+    assert!(ConstrainedInt::<-200, 0>::new(1).is_err());
+}
+
+#[test]
+fn to_der_fixed_size() {
+    let b = [1u8, 2, 3];
+    assert_eq!(b.to_der_vec().unwrap(), &hex!("04 03 01 02 03"));
+    let seq = FixedSequenceOf::from([1u32, 2]);
+    assert_eq!(seq.to_der_vec().unwrap(), &hex!("30 06 02 01 01 02 01 02"));
+}
+
+#[test]
+fn to_der_size_constrained() {
+    let bytes: &[u8] = &[1, 2, 3];
+    let s = SizeConstrained::<_, 1, 4>::new(bytes).unwrap();
+    assert_eq!(s.to_der_vec().unwrap(), &hex!("04 03 01 02 03"));
+    assert!(SizeConstrained::<_, 4, 4>::new(bytes).is_err());
 }
 
 #[test]
@@ -175,37 +434,40 @@ fn to_der_octetstring() {
 #[test]
 fn to_der_real_binary() {
     // base = 2, value = 4
-    let r = Real::binary(2.0, 2, 1);
+    let r = Real::binary(2.0, 2, 1).unwrap();
     let v = r.to_der_vec().expect("serialization failed");
     assert_eq!(&v, &hex!("09 03 80 02 01"));
     let (_, result) = Real::from_der(&v).expect("parsing failed");
     assert!((r.f64() - result.f64()).abs() < f64::EPSILON);
     //
     // base = 2, value = 0.5
-    let r = Real::binary(0.5, 2, 0);
+    let r = Real::binary(0.5, 2, 0).unwrap();
     let v = r.to_der_vec().expect("serialization failed");
     assert_eq!(&v, &hex!("09 03 80 ff 01"));
     let (_, result) = Real::from_der(&v).expect("parsing failed");
     assert!((r.f64() - result.f64()).abs() < f64::EPSILON);
     //
     // base = 2, value = 3.25, but change encoding base (8)
-    let r = Real::binary(3.25, 2, 0).with_enc_base(8);
+    let r = Real::binary(3.25, 2, 0).unwrap().with_enc_base(8).unwrap();
     let v = r.to_der_vec().expect("serialization failed");
     // note: this encoding has a scale factor (not DER compliant)
     assert_eq!(&v, &hex!("09 03 94 ff 0d"));
-    let (_, result) = Real::from_der(&v).expect("parsing failed");
+    let (_, result) = Real::from_ber(&v).expect("parsing failed");
     assert!((r.f64() - result.f64()).abs() < f64::EPSILON);
     //
     // base = 2, value = 0.00390625, but change encoding base (16)
-    let r = Real::binary(0.00390625, 2, 0).with_enc_base(16);
+    let r = Real::binary(0.00390625, 2, 0)
+        .unwrap()
+        .with_enc_base(16)
+        .unwrap();
     let v = r.to_der_vec().expect("serialization failed");
     // note: this encoding has a scale factor (not DER compliant)
     assert_eq!(&v, &hex!("09 03 a0 fe 01"));
-    let (_, result) = Real::from_der(&v).expect("parsing failed");
+    let (_, result) = Real::from_ber(&v).expect("parsing failed");
     assert!((r.f64() - result.f64()).abs() < f64::EPSILON);
     //
     // 2 octets for exponent, negative exponent and abs(exponent) is all 1's and fills the whole octet(s)
-    let r = Real::binary(3.0, 2, -1020);
+    let r = Real::binary(3.0, 2, -1020).unwrap();
     let v = r.to_der_vec().expect("serialization failed");
     assert_eq!(&v, &hex!("09 04 81 fc 04 03"));
     let (_, result) = Real::from_der(&v).expect("parsing failed");
@@ -213,7 +475,7 @@ fn to_der_real_binary() {
     //
     // 3 octets for exponent, and
     // check that first 9 bits for exponent are not all 1's
-    let r = Real::binary(1.0, 2, 262140);
+    let r = Real::binary(1.0, 2, 262140).unwrap();
     let v = r.to_der_vec().expect("serialization failed");
     assert_eq!(&v, &hex!("09 05 82 03 ff fc 01"));
     let (_, result) = Real::from_der(&v).expect("parsing failed");
@@ -222,12 +484,27 @@ fn to_der_real_binary() {
     //
     // >3 octets for exponent, and
     // mantissa < 0
-    let r = Real::binary(-1.0, 2, 76354972);
+    let r = Real::binary(-1.0, 2, 76354972).unwrap();
     let v = r.to_der_vec().expect("serialization failed");
     let (_, result) = Real::from_der(&v).expect("parsing failed");
     assert_eq!(&v, &hex!("09 07 c3 04 04 8d 15 9c 01"));
     // XXX value cannot be represented as f64 (-inf)
     assert!(result.f64().is_infinite());
+    //
+    // other bases are encoded using base 2
+    let r = Real::binary(1.0, 10, 3).unwrap();
+    assert_eq!(r.f64(), 1000.0);
+    assert_eq!(r.to_der_vec().unwrap(), &hex!("09 03 80 03 7d"));
+    assert_eq!(
+        Real::binary(3.0, 4, -2).unwrap(),
+        Real::binary(3.0, 2, -4).unwrap()
+    );
+    assert_eq!(Real::binary(5.0, 0, 1).unwrap(), Real::Zero);
+    // exponent does not fit into i32
+    Real::binary(1.0, 1 << 30, i32::MAX).expect_err("exponent overflow");
+    let r = Real::binary(1.0, 16, i32::MAX).unwrap();
+    r.canonical().expect_err("exponent overflow");
+    r.with_enc_base(2).expect_err("exponent overflow");
 }
 
 #[test]
@@ -257,9 +534,44 @@ fn to_der_real_string() {
     //  non-zero value, base 10
     let r = Real::new(1.2345);
     let v = r.to_der_vec().expect("serialization failed");
-    // assert_eq!(&v, &hex!("09 00"));
+    assert_eq!(&v, &hex!("09 0a 03 31 32 33 34 35 2e 45 2d 34"));
     let (_, result) = Real::from_der(&v).expect("parsing failed");
     assert!(r.eq(&result));
+    // the original representation is kept
+    let r = Real::decimal(DecimalForm::NR2, " +1,50").expect("invalid value");
+    let v = r.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("09 07 02 20 2b 31 2c 35 30"));
+    let (_, result) = Real::from_ber(&v).expect("parsing failed");
+    assert_eq!(result, r);
+    assert_eq!(
+        result.canonical().unwrap().to_der_vec().unwrap(),
+        hex!("09 07 03 31 35 2e 45 2d 31")
+    );
+}
+
+#[test]
+fn to_der_real_canonical() {
+    // base 8 with scaling factor, converted to base 2
+    let r = Real::binary(3.25, 2, 0).unwrap().with_enc_base(8).unwrap();
+    let v = r
+        .canonical()
+        .unwrap()
+        .to_der_vec()
+        .expect("serialization failed");
+    assert_eq!(&v, &hex!("09 03 80 fe 0d"));
+    let (_, result) = Real::from_der(&v).expect("parsing failed");
+    assert_eq!(result, r);
+    // decimal, exponent 0
+    let r = Real::decimal(DecimalForm::NR1, "-12").expect("invalid value");
+    assert_eq!(r.canonical().unwrap().to_string(), "-12.E0");
+    let v = r
+        .canonical()
+        .unwrap()
+        .to_der_vec()
+        .expect("serialization failed");
+    assert_eq!(&v, &hex!("09 08 03 2d 31 32 2e 45 2b 30"));
+    let (_, result) = Real::from_der(&v).expect("parsing failed");
+    assert_eq!(result, r);
 }
 
 #[test]
@@ -309,6 +621,38 @@ fn to_der_tagged_implicit() {
     assert!(tagged.eq(&t2));
 }
 
+#[test]
+fn to_der_teletexstring() {
+    let s = TeletexString::from("Ærø Åbenrå");
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("14 0c e1 72 f9 20 ca 41 62 65 6e 72 ca 61"));
+    let (_, s2) = TeletexString::from_der(&v).expect("decoding serialized object failed");
+    assert!(s.eq(&s2));
+    // original encoding is preserved ('$' as 0xa4)
+    let input = &hex!("14 02 31 a4");
+    let (_, s) = TeletexString::from_der(input).expect("parsing failed");
+    assert_eq!(s.as_ref(), "1$");
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, input);
+    assert_eq!(
+        TeletexString::new("1$").to_der_vec().unwrap(),
+        hex!("14 02 31 24")
+    );
+    // not in T.61 repertoire
+    let _ = TeletexString::new("€")
+        .to_der_vec()
+        .expect_err("serialization should fail");
+}
+
+#[test]
+fn to_der_generalstring() {
+    let s = GeneralString::from("café");
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("1b 07 1b 2d 41 63 61 66 e9"));
+    let (_, s2) = GeneralString::from_der(&v).expect("decoding serialized object failed");
+    assert!(s.eq(&s2));
+}
+
 #[test]
 fn to_der_utctime() {
     let dt = ASN1DateTime::new(99, 12, 31, 23, 59, 59, None, ASN1TimeZone::Z);
@@ -341,3 +685,55 @@ fn to_der_universalstring() {
     let (_, s2) = UniversalString::from_der(&v).expect("decoding serialized object failed");
     assert!(s.eq(&s2));
 }
+
+#[test]
+fn to_der_universalstring_non_ascii() {
+    let s = UniversalString::from("é😀");
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("1c 08 000000e9 0001f600"));
+    let (_, s2) = UniversalString::from_der(&v).expect("decoding serialized object failed");
+    assert!(s.eq(&s2));
+    let (_, raw) = RawUniversalString::from_der(&v).expect("decoding serialized object failed");
+    assert_eq!(raw.to_der_vec().expect("serialization failed"), v);
+    assert_eq!(raw.string(), "é😀");
+}
+
+#[test]
+fn der_macro() {
+    const ALG: &[u8] = der! { SEQUENCE { OID 1.2.840.113549.1.1.11, NULL } };
+    assert_eq!(ALG, &hex!("30 0d 06 09 2a 86 48 86 f7 0d 01 01 0b 05 00"));
+    // primitive types
+    assert_eq!(der!(BOOLEAN true), &hex!("01 01 ff"));
+    assert_eq!(der!(INTEGER 128), &hex!("02 02 00 80"));
+    assert_eq!(der!(INTEGER - 129), &hex!("02 02 ff 7f"));
+    assert_eq!(der!(INTEGER 0), &hex!("02 01 00"));
+    assert_eq!(der!(OCTET STRING "01 02 ff"), &hex!("04 03 01 02 ff"));
+    assert_eq!(der!(OCTET STRING b"abc"), &hex!("04 03 61 62 63"));
+    // BIT STRING is always encoded with 0 unused bits
+    assert_eq!(der!(BIT STRING "80"), &hex!("03 02 00 80"));
+    assert_eq!(der!(BIT STRING "0f 01"), &hex!("03 03 00 0f 01"));
+    assert_eq!(der!(BIT STRING ""), &hex!("03 01 00"));
+    assert_eq!(der!(PrintableString "Test"), &hex!("13 04 54 65 73 74"));
+    assert_eq!(der!(UTF8String "é"), &hex!("0c 02 c3 a9"));
+    // tagged values
+    assert_eq!(der!([0] INTEGER 2), &hex!("a0 03 02 01 02"));
+    assert_eq!(der!([1] IMPLICIT INTEGER 2), &hex!("81 01 02"));
+    assert_eq!(
+        der!([APPLICATION 2] IMPLICIT SEQUENCE { NULL }),
+        &hex!("62 02 05 00")
+    );
+    assert_eq!(der!([31] IMPLICIT NULL), &hex!("9f 1f 00"));
+    // SET elements are sorted
+    assert_eq!(
+        der!(SET { INTEGER 1, BOOLEAN false }),
+        &hex!("31 06 01 01 00 02 01 01")
+    );
+    // the result can be parsed
+    let (rem, any) = Any::from_der(der!(SEQUENCE {
+        INTEGER 1,
+        [0] EXPLICIT SEQUENCE { OID 2.5.4.3, UTF8String "test" },
+    }))
+    .expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(any.tag(), Tag::Sequence);
+}
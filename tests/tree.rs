@@ -0,0 +1,118 @@
+use asn1_rs::*;
+use hex_literal::hex;
+
+// SEQUENCE (indefinite) {
+//   BOOLEAN, INTEGER (large), BIT STRING (unaligned), OCTET STRING, UTCTime,
+//   [APPLICATION 1] { INTEGER, NULL }, [1] (primitive), SET {}
+// }
+const INPUT: &[u8] = &hex!(
+    "30 80 01 01 ff 02 11 01 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+     03 02 04 a0 04 04 de ad be ef 17 0d 39 39 31 32 33 31 32 33 35 39 35 39 5a
+     61 05 02 01 ff 05 00 81 02 61 22 31 00 00 00"
+);
+
+#[test]
+fn tree_display() {
+    let s = TreeFormatter::new()
+        .with_max_bytes(2)
+        .display(INPUT)
+        .to_string();
+    assert_eq!(
+        s,
+        r#"    0:d=0  hl=2 l= inf cons: SEQUENCE
+    2:d=1  hl=2 l=   1 prim:   BOOLEAN TRUE
+    5:d=1  hl=2 l=  17 prim:   INTEGER '0100...'H
+   24:d=1  hl=2 l=   2 prim:   BIT STRING '1010'B
+   28:d=1  hl=2 l=   4 prim:   OCTET STRING 'DEAD...'H
+   34:d=1  hl=2 l=  13 prim:   UTCTime "991231235959Z"
+   49:d=1  hl=2 l=   5 cons:   [APPLICATION 1]
+   51:d=2  hl=2 l=   1 prim:     INTEGER -1
+   54:d=2  hl=2 l=   0 prim:     NULL
+   56:d=1  hl=2 l=   2 prim:   [1] '6122'H
+   60:d=1  hl=2 l=   0 cons:   SET
+"#
+    );
+    // without offsets
+    let s = TreeFormatter::new()
+        .with_offsets(false)
+        .with_indent(4)
+        .display(&hex!("30 03 02 01 05"))
+        .to_string();
+    assert_eq!(s, "SEQUENCE\n    INTEGER 5\n");
+}
+
+#[test]
+fn tree_value_notation() {
+    let s = TreeFormatter::new()
+        .with_value_notation(true)
+        .display(INPUT)
+        .to_string();
+    assert_eq!(
+        s,
+        r#"{
+  TRUE,
+  '0100000000000000000000000000000000'H,
+  '1010'B,
+  'DEADBEEF'H,
+  "991231235959Z",
+  [APPLICATION 1] {
+    -1,
+    NULL
+  },
+  [1] '6122'H,
+  {}
+}
+"#
+    );
+    // strings are quoted
+    let s = TreeFormatter::new()
+        .with_value_notation(true)
+        .display(&hex!("0c 03 61 22 62"))
+        .to_string();
+    assert_eq!(s, "\"a\"\"b\"\n");
+}
+
+#[test]
+fn tree_display_errors() {
+    // invalid value: displayed as hex
+    let s = TreeFormatter::new()
+        .display(&hex!("01 02 00 00"))
+        .to_string();
+    assert_eq!(
+        s,
+        "    0:d=0  hl=2 l=   2 prim: BOOLEAN '0000'H -- error: Invalid Length --\n"
+    );
+    let s = TreeFormatter::new()
+        .display(&hex!("03 02 6b 01"))
+        .to_string();
+    assert_eq!(
+        s,
+        "    0:d=0  hl=2 l=   2 prim: BIT STRING '6B01'H -- error: Invalid Value when parsing \
+         object with tag Tag(3) invalid number of unused bits --\n"
+    );
+    let s = TreeFormatter::new()
+        .with_value_notation(true)
+        .display(&hex!("03 02 6b 01"))
+        .to_string();
+    assert!(s.starts_with("'6B01'H -- error:"));
+    // truncated element: parsing of the SEQUENCE stops
+    let s = TreeFormatter::new()
+        .display(&hex!("30 05 02 01 01 02 03"))
+        .to_string();
+    assert_eq!(
+        s,
+        "    0:d=0  hl=2 l=   5 cons: SEQUENCE
+    2:d=1  hl=2 l=   1 prim:   INTEGER 1
+    5:d=1    -- error: incomplete data, missing: Size(3) --
+"
+    );
+}
+
+#[test]
+fn tree_display_any() {
+    let (_, any) = Any::from_der(&hex!("a0 03 02 01 05")).expect("parsing failed");
+    assert_eq!(
+        any.display_tree().to_string(),
+        "    0:d=0  hl=2 l=   3 cons: [0]\n    2:d=1  hl=2 l=   1 prim:   INTEGER 5\n"
+    );
+}